@@ -1,11 +1,12 @@
 //! ARM64 architecture-specific CPU detection.
 //!
 //! On macOS, Apple Silicon chips are identified via `hw.cpufamily` sysctl and
-//! distinguished by P-core / E-core counts.  On Linux and other platforms a
-//! generic ARM fallback is returned.
+//! distinguished by P-core / E-core counts.  On Linux the implementer and part
+//! numbers from `/proc/cpuinfo` name the vendor and cores; on Windows the
+//! registry holds the marketing name (e.g. Snapdragon on a Surface Pro X);
+//! elsewhere a generic ARM fallback is returned.
 
-use crate::cpu::info::Frequency;
-use crate::cpu::{ArmFeatures, CpuError, CpuInfo, Vendor, Version};
+use crate::cpu::{ArmFeatures, CpuError, CpuInfo, Midr, Vendor, Version};
 
 /// Detect CPU information for ARM64 systems.
 ///
@@ -13,34 +14,108 @@ use crate::cpu::{ArmFeatures, CpuError, CpuInfo, Vendor, Version};
 ///
 /// Returns `CpuError` if CPU detection fails.
 pub fn detect_cpu() -> Result<CpuInfo, CpuError> {
+    detect_cpu_reporting(&mut Vec::new())
+}
+
+/// Like [`detect_cpu`], but records in `warnings` why optional fields were left empty.
+// Infallible today; the Result mirrors the x86_64 backend so callers stay uniform.
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) fn detect_cpu_reporting(warnings: &mut Vec<String>) -> Result<CpuInfo, CpuError> {
     // On macOS, attempt Apple Silicon identification first.
     #[cfg(all(target_os = "macos", feature = "macos"))]
-    if let Some(info) = apple_silicon::detect() {
+    if let Some(info) = apple_silicon::detect(warnings) {
         return Ok(info);
     }
 
     // Generic ARM fallback (Linux, bare-metal, etc.)
+    let midr = detect_midr();
+    #[cfg(target_os = "linux")]
+    if midr.is_empty() {
+        warnings.push("CPU implementer not found in /proc/cpuinfo, using generic ARM name".to_string());
+    }
+    #[cfg(all(target_os = "windows", feature = "windows"))]
+    let registry = detect_registry_processor();
+    #[cfg(not(all(target_os = "windows", feature = "windows")))]
+    let registry: Option<(Vendor, String)> = None;
+
+    let cores = crate::utils::platform::detect_core_counts();
+    let features = detect_arm_features();
+    let (vendor, brand_string) = match (midr.first(), registry) {
+        (Some(core), _) => (core.vendor(), crate::cpu::midr::brand_string(&midr)),
+        (None, Some((vendor, name))) => (vendor, Some(name)),
+        (None, None) => (Vendor::ARM, None),
+    };
     Ok(CpuInfo {
-        vendor: Vendor::ARM,
-        brand_string: String::from("ARM Processor"),
-        version: Version {
-            family: 0,
-            model: 0,
-            stepping: 0,
-        },
-        physical_cores: u32::try_from(num_cpus::get_physical()).unwrap_or(0),
-        logical_cores: u32::try_from(num_cpus::get()).unwrap_or(0),
-        frequency: Frequency::default(),
-        cache_sizes: [None; 4],
-        features: detect_arm_features(),
-        microarch: None,
-        hypervisor: None,
-        peak_flops: None,
-        p_cores: None,
-        e_cores: None,
+        vendor,
+        brand_string: brand_string.unwrap_or_else(|| String::from("ARM Processor")),
+        version: midr.first().map_or_else(Version::default, Midr::version),
+        physical_cores: cores.physical,
+        logical_cores: cores.logical,
+        sockets: cores.packages,
+        features,
+        sve_vector_bits: features
+            .contains(ArmFeatures::SVE)
+            .then(detect_sve_vector_bits)
+            .flatten(),
+        #[cfg(feature = "std")]
+        frequency: crate::arch::detect_frequency_for_info(warnings),
+        clusters: crate::arch::detect_clusters_for_info(),
+        temperature_celsius: crate::arch::detect_temperature_for_info(warnings),
+        numa_nodes: crate::cpu::detect_numa_nodes(),
+        packages: crate::cpu::detect_packages(),
+        governor: crate::cpu::detect_governor(),
+        epp: crate::cpu::detect_epp(),
+        mitigations: crate::cpu::detect_mitigations(),
+        ..CpuInfo::default()
     })
 }
 
+/// Read the distinct core types from `/proc/cpuinfo` (Linux only).
+fn detect_midr() -> Vec<Midr> {
+    #[cfg(target_os = "linux")]
+    return std::fs::read_to_string("/proc/cpuinfo")
+        .map(|cpuinfo| crate::cpu::midr::parse_proc_cpuinfo(&cpuinfo))
+        .unwrap_or_default();
+
+    #[cfg(not(target_os = "linux"))]
+    return Vec::new();
+}
+
+/// Vendor and marketing name from the registry (Windows only).
+///
+/// `HKLM\HARDWARE\DESCRIPTION\System\CentralProcessor\0` holds the name in
+/// `ProcessorNameString`, e.g. "Snapdragon (TM) 8cx Gen 3 @ 3.0 GHz", and the
+/// maker in `VendorIdentifier`.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+fn detect_registry_processor() -> Option<(Vendor, String)> {
+    let key = windows_registry::LOCAL_MACHINE
+        .open(r"HARDWARE\DESCRIPTION\System\CentralProcessor\0")
+        .ok()?;
+    let name = key.get_string("ProcessorNameString").ok()?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let identifier = key.get_string("VendorIdentifier").unwrap_or_default();
+    debug_log!("Registry reports processor {name:?} from {identifier:?}");
+    Some((vendor_from_names(&identifier, name), name.to_string()))
+}
+
+/// The vendor a Windows vendor identifier or processor name mentions, else `Vendor::ARM`.
+#[cfg_attr(not(all(target_os = "windows", feature = "windows")), allow(dead_code))]
+fn vendor_from_names(identifier: &str, name: &str) -> Vendor {
+    let text = format!("{identifier} {name}").to_ascii_lowercase();
+    if text.contains("qualcomm") || text.contains("snapdragon") {
+        Vendor::Qualcomm
+    } else if text.contains("nvidia") {
+        Vendor::NVIDIA
+    } else if text.contains("apple") {
+        Vendor::Apple
+    } else {
+        Vendor::ARM
+    }
+}
+
 // ── ARM feature detection ────────────────────────────────────────────────────
 
 fn detect_arm_features() -> ArmFeatures {
@@ -87,6 +162,38 @@ fn detect_arm_features() -> ArmFeatures {
                 if has("atomics") {
                     features |= ArmFeatures::ATOMICS;
                 }
+                if has("sve") {
+                    features |= ArmFeatures::SVE;
+                }
+                if has("sve2") {
+                    features |= ArmFeatures::SVE2;
+                }
+            }
+        }
+
+        // On Windows, std's runtime detection asks IsProcessorFeaturePresent,
+        // without the unsafe FFI call this crate forbids.
+        #[cfg(target_os = "windows")]
+        {
+            use std::arch::is_aarch64_feature_detected;
+            if is_aarch64_feature_detected!("aes") {
+                features |= ArmFeatures::AES;
+            }
+            if is_aarch64_feature_detected!("pmull") {
+                features |= ArmFeatures::PMULL;
+            }
+            // Windows reports the crypto extension as a whole, SHA-1 included
+            if is_aarch64_feature_detected!("sha2") {
+                features |= ArmFeatures::SHA1 | ArmFeatures::SHA2;
+            }
+            if is_aarch64_feature_detected!("crc") {
+                features |= ArmFeatures::CRC32;
+            }
+            if is_aarch64_feature_detected!("lse") {
+                features |= ArmFeatures::ATOMICS;
+            }
+            if is_aarch64_feature_detected!("dotprod") {
+                features |= ArmFeatures::ASIMDDP;
             }
         }
     }
@@ -94,6 +201,28 @@ fn detect_arm_features() -> ArmFeatures {
     features
 }
 
+/// SVE vector length in bits, from the kernel's default for new processes (Linux only).
+///
+/// `/proc/sys/abi/sve_default_vector_length` holds the length in bytes, which
+/// is the hardware maximum unless an administrator has lowered it.
+fn detect_sve_vector_bits() -> Option<u16> {
+    #[cfg(target_os = "linux")]
+    return std::fs::read_to_string("/proc/sys/abi/sve_default_vector_length")
+        .ok()
+        .and_then(|content| parse_sve_vector_length(&content));
+
+    #[cfg(not(target_os = "linux"))]
+    return None;
+}
+
+/// Convert a vector length in bytes, as the kernel reports it, to bits.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_sve_vector_length(content: &str) -> Option<u16> {
+    let bytes: u16 = content.trim().parse().ok()?;
+    // Architecturally a multiple of 16 bytes, up to 256 (2048 bits)
+    (bytes > 0 && bytes % 16 == 0 && bytes <= 256).then_some(bytes * 8)
+}
+
 // ── Apple Silicon detection (macOS only) ─────────────────────────────────────
 
 #[cfg(all(target_os = "macos", feature = "macos"))]
@@ -101,7 +230,7 @@ mod apple_silicon {
     use super::detect_arm_features;
     use crate::cpu::info::Frequency;
     use crate::cpu::uarch::Microarch;
-    use crate::cpu::{CpuInfo, Vendor, Version};
+    use crate::cpu::{CpuInfo, Vendor};
 
     /// Read a sysctl key as a `u32`, reinterpreting signed bits correctly.
     ///
@@ -136,6 +265,14 @@ mod apple_silicon {
         }
     }
 
+    /// Read a sysctl key as a trimmed string.
+    fn sysctl_string(name: &str) -> Option<String> {
+        use sysctl::{Ctl, Sysctl};
+        let value = Ctl::new(name).ok()?.value_string().ok()?;
+        let value = value.trim_end_matches('\0').trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
     /// Read cache sizes from macOS sysctl.
     ///
     /// Returns `[L1i, L1d, L2, L3]` in KB. Uses P-core (perflevel0) values
@@ -147,10 +284,17 @@ mod apple_silicon {
             kb
         };
 
+        // Older macOS releases lack the perflevel keys; fall back to the system-wide ones.
+        let cache = |level: &str| {
+            sysctl_u64(&format!("hw.perflevel0.{level}"))
+                .or_else(|| sysctl_u64(&format!("hw.{level}")))
+                .map(bytes_to_kb)
+        };
+
         [
-            sysctl_u64("hw.perflevel0.l1icachesize").map(bytes_to_kb),
-            sysctl_u64("hw.perflevel0.l1dcachesize").map(bytes_to_kb),
-            sysctl_u64("hw.perflevel0.l2cachesize").map(bytes_to_kb),
+            cache("l1icachesize"),
+            cache("l1dcachesize"),
+            cache("l2cachesize"),
             // macOS doesn't expose L3 via perflevel sysctl; it may not exist
             // as a distinct level on Apple Silicon (the SLC is not reported here).
             None,
@@ -243,38 +387,48 @@ mod apple_silicon {
 
     /// Perform Apple Silicon detection and return a populated `CpuInfo`.
     ///
-    /// Returns `None` if the CPU family is unrecognised (non-Apple ARM hardware).
-    pub fn detect() -> Option<CpuInfo> {
-        let family = sysctl_u32("hw.cpufamily")?;
-        let (generation, microarch) = classify_family(family)?;
+    /// The brand string comes from `machdep.cpu.brand_string` (e.g. "Apple M2
+    /// Pro"); `hw.cpufamily` supplies the microarchitecture and frequency
+    /// lookup. Returns `None` if neither identifies an Apple chip (non-Apple
+    /// ARM hardware). Non-fatal sensor failures are appended to `warnings`.
+    pub fn detect(warnings: &mut Vec<String>) -> Option<CpuInfo> {
+        let classified = sysctl_u32("hw.cpufamily").and_then(classify_family);
+        let sysctl_brand = sysctl_string("machdep.cpu.brand_string").filter(|b| b.starts_with("Apple"));
+        if classified.is_none() && sysctl_brand.is_none() {
+            return None;
+        }
 
         // P-cores are perflevel 0 (fastest), E-cores are perflevel 1.
-        let p_cores = sysctl_u32("hw.perflevel0.physicalcpu").unwrap_or(0);
-        let e_cores = sysctl_u32("hw.perflevel1.physicalcpu").unwrap_or(0);
+        // Both keys are missing on macOS releases before 12.
+        let p_cores = sysctl_u32("hw.perflevel0.physicalcpu");
+        let e_cores = sysctl_u32("hw.perflevel1.physicalcpu");
 
-        let variant = chip_variant(generation, p_cores, e_cores);
-        let brand_string = format!("Apple {generation}{variant}");
+        let variant = classified.map_or("", |(generation, _)| {
+            chip_variant(generation, p_cores.unwrap_or(0), e_cores.unwrap_or(0))
+        });
+        let brand_string = sysctl_brand.unwrap_or_else(|| {
+            // `classified` is known to be `Some` here
+            let generation = classified.map_or("", |(generation, _)| generation);
+            format!("Apple {generation}{variant}")
+        });
 
-        let physical_cores = u32::try_from(num_cpus::get_physical()).unwrap_or(0);
-        let logical_cores = u32::try_from(num_cpus::get()).unwrap_or(0);
+        // num_cpus covers the case where the perflevel keys are missing
+        let cores = crate::utils::platform::detect_core_counts();
+        let (physical_cores, logical_cores) = (cores.physical, cores.logical);
 
         let features = detect_arm_features();
         let cache_sizes = detect_cache_sizes();
 
         // Apple Silicon frequency from lookup table (not available via sysctl)
-        let max_freq = lookup_frequency(generation, variant);
-        let frequency = Frequency {
-            base: None,
-            max: max_freq,
-            current: None,
-        };
+        let max_freq = classified.and_then(|(generation, _)| lookup_frequency(generation, variant));
+        let frequency = Frequency::new(None, max_freq, None);
 
         // Peak FLOPS: NEON is 128-bit = 2 DP ops/cycle.
         // Apple Silicon has FMA so multiply-add counts as 2 FLOP/cycle.
         // Use P-core count and max frequency for peak calculation.
         let peak_flops = max_freq.map(|mhz| {
             let clock_ghz = mhz / 1000.0;
-            let cores = f64::from(p_cores);
+            let cores = f64::from(p_cores.unwrap_or(physical_cores));
             let neon_dp_width = 2.0; // 128-bit NEON = 2 doubles
             let fma_factor = 2.0; // Apple Silicon always has FMA
             cores * clock_ghz * neon_dp_width * fma_factor
@@ -283,21 +437,23 @@ mod apple_silicon {
         Some(CpuInfo {
             vendor: Vendor::Apple,
             brand_string,
-            version: Version {
-                family: 0,
-                model: 0,
-                stepping: 0,
-            },
             physical_cores,
             logical_cores,
+            // Apple Silicon is always a single package
+            sockets: Some(1),
             frequency,
             cache_sizes,
             features,
-            microarch: Some(microarch),
-            hypervisor: None,
+            process_node: classified
+                .as_ref()
+                .and_then(|(_, microarch)| microarch.process_node())
+                .map(str::to_string),
+            microarch: classified.map(|(_, microarch)| microarch),
             peak_flops,
-            p_cores: Some(p_cores),
-            e_cores: Some(e_cores),
+            p_cores,
+            e_cores,
+            temperature_celsius: crate::arch::detect_temperature_for_info(warnings),
+            ..CpuInfo::default()
         })
     }
 
@@ -346,9 +502,12 @@ mod tests {
     #[test]
     fn test_detect_cpu_runs() {
         let info = detect_cpu().unwrap();
-        // On macOS Apple Silicon the vendor will be Apple; on other ARM it is ARM.
+        // On macOS Apple Silicon the vendor will be Apple; on Linux it follows the MIDR implementer.
         assert!(
-            matches!(info.vendor, Vendor::ARM | Vendor::Apple),
+            matches!(
+                info.vendor,
+                Vendor::ARM | Vendor::Apple | Vendor::Qualcomm | Vendor::NVIDIA
+            ),
             "Expected ARM or Apple vendor, got {:?}",
             info.vendor
         );
@@ -357,6 +516,40 @@ mod tests {
         assert!(info.physical_cores > 0);
     }
 
+    #[test]
+    fn test_parse_sve_vector_length() {
+        // Graviton3 (256-bit) and A64FX (512-bit)
+        assert_eq!(parse_sve_vector_length("32\n"), Some(256));
+        assert_eq!(parse_sve_vector_length("64"), Some(512));
+        assert_eq!(parse_sve_vector_length("0"), None);
+        assert_eq!(parse_sve_vector_length("24"), None);
+        assert_eq!(parse_sve_vector_length(""), None);
+    }
+
+    #[test]
+    fn test_vendor_from_names() {
+        assert_eq!(
+            vendor_from_names("Qualcomm Technologies Inc", "Snapdragon (TM) 8cx Gen 3 @ 3.0 GHz"),
+            Vendor::Qualcomm
+        );
+        assert_eq!(
+            vendor_from_names("", "Snapdragon(R) X Elite - X1E78100 - Qualcomm(R) Oryon(TM) CPU"),
+            Vendor::Qualcomm
+        );
+        assert_eq!(vendor_from_names("ARM Limited", "ARMv8 (64-bit) Family 8"), Vendor::ARM);
+    }
+
+    #[test]
+    #[cfg(all(target_os = "windows", feature = "windows"))]
+    fn test_windows_registry_brand() {
+        let info = detect_cpu().unwrap();
+        assert_ne!(
+            info.brand_string, "ARM Processor",
+            "registry name should replace the fallback"
+        );
+        assert!(info.features.contains(ArmFeatures::NEON));
+    }
+
     #[test]
     #[cfg(all(target_os = "macos", feature = "macos"))]
     fn test_apple_silicon_detected() {
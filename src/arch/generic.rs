@@ -0,0 +1,99 @@
+//! Fallback CPU detection for architectures without a dedicated backend.
+//!
+//! On PowerPC, s390x, MIPS, RISC-V, 32-bit x86 and the like there is no
+//! identification instruction this crate reads, so only what the operating
+//! system reports is filled in: core and package counts, NUMA layout and, on
+//! Linux, the processor name from `/proc/cpuinfo`. The vendor is
+//! `Vendor::Unknown` and no feature flags are reported.
+
+use crate::cpu::{CpuError, CpuInfo, Vendor};
+
+/// Detect what the operating system reports about the CPU.
+///
+/// # Errors
+///
+/// Never fails; the `Result` matches the native backends.
+pub fn detect_cpu() -> Result<CpuInfo, CpuError> {
+    detect_cpu_reporting(&mut Vec::new())
+}
+
+/// Like [`detect_cpu`], but records in `warnings` why optional fields were left empty.
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) fn detect_cpu_reporting(warnings: &mut Vec<String>) -> Result<CpuInfo, CpuError> {
+    let cores = crate::utils::platform::detect_core_counts();
+    if cores.logical == 0 {
+        warnings.push("core count detection failed, reporting 0 cores".to_string());
+    }
+
+    let brand_string = proc_cpuinfo_name().unwrap_or_else(|| {
+        warnings.push(format!(
+            "no processor name for {} in /proc/cpuinfo, using a generic name",
+            std::env::consts::ARCH
+        ));
+        format!("{} Processor", std::env::consts::ARCH)
+    });
+
+    Ok(CpuInfo {
+        vendor: Vendor::Unknown,
+        brand_string,
+        physical_cores: cores.physical,
+        logical_cores: cores.logical,
+        sockets: cores.packages,
+        temperature_celsius: super::detect_temperature_for_info(warnings),
+        numa_nodes: crate::cpu::detect_numa_nodes(),
+        packages: crate::cpu::detect_packages(),
+        governor: crate::cpu::detect_governor(),
+        epp: crate::cpu::detect_epp(),
+        mitigations: crate::cpu::detect_mitigations(),
+        ..CpuInfo::default()
+    })
+}
+
+/// The processor name in `/proc/cpuinfo` (Linux with `std` only).
+fn proc_cpuinfo_name() -> Option<String> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    return std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| cpuinfo_name(&cpuinfo).map(str::to_string));
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    None
+}
+
+/// The first non-empty name field of `/proc/cpuinfo` content.
+///
+/// Kernels name it differently per architecture: `model name` (x86, s390x,
+/// `LoongArch`), `cpu model` (MIPS) or `cpu` (PowerPC).
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+fn cpuinfo_name(cpuinfo: &str) -> Option<&str> {
+    ["model name", "cpu model", "cpu"].into_iter().find_map(|wanted| {
+        cpuinfo
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, value)| key.trim() == wanted && !value.trim().is_empty())
+            .map(|(_, value)| value.trim())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpuinfo_name() {
+        let power9 = "processor\t: 0\ncpu\t\t: POWER9 (raw), altivec supported\nclock\t\t: 2166.000000MHz\n";
+        assert_eq!(cpuinfo_name(power9), Some("POWER9 (raw), altivec supported"));
+
+        let mips = "system type\t\t: MT7621\nprocessor\t\t: 0\ncpu model\t\t: MIPS 1004Kc V2.15\n";
+        assert_eq!(cpuinfo_name(mips), Some("MIPS 1004Kc V2.15"));
+
+        assert_eq!(cpuinfo_name("processor\t: 0\nmodel name\t: \n"), None);
+    }
+
+    #[test]
+    fn test_detect_cpu_is_generic() {
+        let info = detect_cpu().unwrap();
+        assert_eq!(info.vendor, Vendor::Unknown);
+        assert!(!info.brand_string.is_empty());
+    }
+}
@@ -1,9 +1,76 @@
 //! Architecture-specific CPU detection implementations
 //!
 //! This module contains CPU detection implementations for different architectures.
+//! Targets without a dedicated backend use `generic`, which reports only
+//! what the operating system knows.
 
 #[cfg(target_arch = "x86_64")]
 pub mod x86_64;
 
 #[cfg(target_arch = "aarch64")]
 pub mod aarch64;
+
+#[cfg(target_arch = "wasm32")]
+pub mod wasm32;
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "wasm32")))]
+pub mod generic;
+
+/// Resolve CPU frequency using the `frequency` feature when available,
+/// falling back to all-`None` when the feature is compiled out.
+#[cfg(feature = "std")]
+#[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), allow(dead_code))]
+#[cfg_attr(not(feature = "frequency"), allow(clippy::ptr_arg, unused_variables))]
+pub(crate) fn detect_frequency_for_info(warnings: &mut Vec<String>) -> crate::cpu::Frequency {
+    #[cfg(feature = "frequency")]
+    {
+        crate::cpu::frequency::detect_frequency().unwrap_or_else(|e| {
+            debug_log!("frequency detection failed: {e}");
+            warnings.push(format!("frequency detection failed, using fallback: {e}"));
+            crate::cpu::Frequency::default()
+        })
+    }
+
+    #[cfg(not(feature = "frequency"))]
+    {
+        crate::cpu::Frequency::default()
+    }
+}
+
+/// The frequency domains of big.LITTLE-style CPUs, `None` when all cores share one clock.
+#[cfg_attr(not(target_arch = "aarch64"), allow(dead_code))]
+pub(crate) fn detect_clusters_for_info() -> Option<Vec<crate::cpu::ClusterFrequency>> {
+    #[cfg(all(feature = "frequency", target_os = "linux"))]
+    {
+        let clusters = crate::cpu::detect_cluster_frequencies();
+        debug_log!("cpufreq policies: {}", clusters.len());
+        (clusters.len() > 1).then_some(clusters)
+    }
+
+    #[cfg(not(all(feature = "frequency", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Read the current CPU temperature when the `thermal` feature is enabled.
+///
+/// Sensor failures are folded into `None` (and recorded in `warnings`) so that
+/// a missing or inaccessible sensor never prevents the rest of the CPU
+/// information from being reported.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+#[cfg_attr(not(feature = "thermal"), allow(clippy::ptr_arg, unused_variables))]
+pub(crate) fn detect_temperature_for_info(warnings: &mut Vec<String>) -> Option<f32> {
+    #[cfg(feature = "thermal")]
+    {
+        crate::cpu::thermal::detect_temperature()
+            .map_err(|e| warnings.push(format!("temperature sensor unavailable: {e}")))
+            .ok()
+            .flatten()
+    }
+
+    #[cfg(not(feature = "thermal"))]
+    {
+        None
+    }
+}
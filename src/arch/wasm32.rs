@@ -0,0 +1,86 @@
+//! WebAssembly (`wasm32`) CPU detection.
+//!
+//! A WebAssembly module cannot see the host processor: there is no CPUID and
+//! no OS to query. The only figure a browser exposes is
+//! `navigator.hardwareConcurrency`, read with the `wasm` feature; everything
+//! else is reported as unknown.
+
+use crate::cpu::{CpuError, CpuInfo, Vendor};
+
+/// Brand string reported for every WebAssembly host
+const WASM_BRAND: &str = "WebAssembly Runtime";
+
+/// Detect what a WebAssembly host reveals about its CPU.
+///
+/// # Errors
+///
+/// Never fails; the `Result` matches the native backends.
+pub fn detect_cpu() -> Result<CpuInfo, CpuError> {
+    detect_cpu_reporting(&mut Vec::new())
+}
+
+/// Like [`detect_cpu`], but records in `warnings` why optional fields were left empty.
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) fn detect_cpu_reporting(warnings: &mut Vec<String>) -> Result<CpuInfo, CpuError> {
+    let logical = logical_cores();
+    if logical == 0 {
+        warnings.push("core count unavailable outside a browser with the `wasm` feature".to_string());
+    }
+
+    // Hosts do not distinguish SMT siblings, so each logical core counts as physical
+    Ok(CpuInfo {
+        vendor: Vendor::Unknown,
+        brand_string: WASM_BRAND.to_string(),
+        physical_cores: logical,
+        logical_cores: logical,
+        ..CpuInfo::default()
+    })
+}
+
+/// Logical cores from `navigator.hardwareConcurrency` in a window or worker,
+/// else from the standard library (WASI with threads), else `0`.
+fn logical_cores() -> u32 {
+    #[cfg(feature = "wasm")]
+    if let Some(count) = hardware_concurrency() {
+        return count;
+    }
+
+    std::thread::available_parallelism()
+        .ok()
+        .and_then(|count| u32::try_from(count.get()).ok())
+        .unwrap_or(0)
+}
+
+/// `navigator.hardwareConcurrency` of the current global scope, if it is a window or worker.
+#[cfg(feature = "wasm")]
+fn hardware_concurrency() -> Option<u32> {
+    use web_sys::wasm_bindgen::JsCast;
+
+    let global = web_sys::js_sys::global();
+    let count = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+        window.navigator().hardware_concurrency()
+    } else {
+        global
+            .dyn_ref::<web_sys::WorkerGlobalScope>()?
+            .navigator()
+            .hardware_concurrency()
+    };
+    debug_log!("navigator.hardwareConcurrency = {count}");
+
+    // A JS number; browsers report a small positive integer
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (count >= 1.0).then_some(count as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_cpu_is_generic() {
+        let info = detect_cpu().unwrap();
+        assert_eq!(info.vendor, Vendor::Unknown);
+        assert_eq!(info.brand_string, WASM_BRAND);
+        assert_eq!(info.physical_cores, info.logical_cores);
+    }
+}
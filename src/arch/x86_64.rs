@@ -3,125 +3,298 @@
 //! This module provides functionality for detecting CPU information on `x86_64` systems
 //! using CPUID instructions through our CPUID wrapper.
 
-use crate::cpu::info::Frequency;
-use crate::cpu::uarch::detect_uarch;
-use crate::cpu::{CpuError, CpuInfo, CpuidWrapper, Vendor, Version};
+use crate::cpu::uarch::{Microarch, detect_uarch};
+use crate::cpu::{CacheTopology, CpuError, CpuInfo, CpuidReader, CpuidWrapper, Vendor, Version, X86Features};
 
 /// Detect CPU information for `x86_64` systems
 ///
+/// If CPUID hides the vendor and model leaves (as in SGX enclaves and some
+/// sandboxes), the CPU is reported as `Vendor::Unknown` / "Unknown CPU" with
+/// the OS-derived fields still filled in. On Linux, a blank CPUID brand
+/// string is replaced with the `model name` from `/proc/cpuinfo`.
+///
 /// # Errors
 ///
-/// Returns `CpuError` if CPUID access fails or CPU information cannot be read.
+/// Returns `CpuError` if CPU feature detection fails.
 pub fn detect_cpu() -> Result<CpuInfo, CpuError> {
-    let cpuid = CpuidWrapper::new();
-
-    // Basic CPU information via CPUID
-    let basic_info = cpuid
-        .get_basic_info()
-        .map_err(|e| CpuError::InfoRead(format!("Failed to get basic CPU info: {e}")))?;
-
-    // Vendor
-    let cpu_vendor = match basic_info.vendor_string.as_str() {
-        "GenuineIntel" => Vendor::Intel,
-        "AuthenticAMD" | "HygonGenuine" => Vendor::AMD,
-        _ => Vendor::Unknown,
-    };
+    detect_cpu_reporting(&mut Vec::new())
+}
+
+/// Like [`detect_cpu`], but records in `warnings` why optional fields were left empty.
+pub(crate) fn detect_cpu_reporting(warnings: &mut Vec<String>) -> Result<CpuInfo, CpuError> {
+    #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+    let mut info = detect_cpuid_reporting(warnings)?;
+
+    // Everything below needs the operating system rather than the CPUID instruction
+    #[cfg(feature = "std")]
+    {
+        // Core counts (WMI on Windows, num_cpus elsewhere)
+        let cores = crate::utils::platform::detect_core_counts();
+        info.physical_cores = cores.physical;
+        info.logical_cores = cores.logical;
+        debug_log!("Core counts: {} physical, {} logical", cores.physical, cores.logical);
+        if cores.logical == 0 {
+            warn(warnings, "core count detection failed, reporting 0 cores".to_string());
+        }
+        info.sockets = cores
+            .packages
+            .or_else(|| sockets_from_topology(cores.logical, CpuidWrapper::new().logical_processors_per_package()));
+
+        // On Apple Silicon an x86-64 binary runs under Rosetta 2, whose CPUID describes the emulated CPU
+        info.translated = detect_rosetta();
+        if info.translated {
+            warn(
+                warnings,
+                "running under Rosetta 2: features describe the emulated x86-64 CPU, not the Apple Silicon chip"
+                    .to_string(),
+            );
+        }
+
+        // Frequency — delegate to the platform-specific detection in `cpu::frequency`
+        info.frequency = super::detect_frequency_for_info(warnings);
+
+        // Hybrid parts whose core kinds differ report only the common features here
+        if let Some((p_core, e_core)) = crate::cpu::detect_hybrid_features(info.available_but_disabled) {
+            (info.features, info.p_core_features, info.e_core_features) =
+                crate::cpu::merge_core_features(p_core, e_core);
+        }
+
+        // Theoretical peak double-precision GFLOP/s
+        info.peak_flops = crate::cpu::perf::calculate_peak_flops(
+            info.physical_cores,
+            info.frequency.max,
+            info.frequency.base,
+            info.features,
+        );
+
+        info.numa_nodes = crate::cpu::detect_numa_nodes();
+        info.packages = crate::cpu::detect_packages();
+        info.microcode = crate::cpu::detect_microcode(&info.vendor);
+        if info.microcode.is_none() {
+            warn(warnings, "microcode revision unavailable (may need root)".to_string());
+        }
+        info.governor = crate::cpu::detect_governor();
+        info.epp = crate::cpu::detect_epp();
+        info.mitigations = crate::cpu::detect_mitigations();
+
+        // Hypervisors often hide the TSC leaves; the kernel calibrated the counter regardless
+        info.tsc_frequency_mhz = info.tsc_frequency_mhz.or_else(crate::cpu::detect_kernel_tsc_frequency);
+        info.invariant_tsc = info.invariant_tsc || crate::cpu::detect_kernel_invariant_tsc();
+
+        // Some hypervisors mask the CPUID cache leaves, leaving the sizes empty or guessed; the kernel still knows them
+        if !CpuidWrapper::new().has_cache_parameters()
+            && let Some(topology) = crate::cpu::detect_sysfs_caches()
+        {
+            debug_log!("CPUID cache leaves empty, using the sysfs cache topology");
+            info.cache_sizes = cache_sizes_from_topology(&topology);
+            info.cache_topology = Some(topology);
+        }
+
+        // CPUID only saw the current core's caches; add those of each core kind on hybrid parts
+        if let Some(topology) = &mut info.cache_topology {
+            topology.by_core_kind = crate::cpu::detect_hybrid_caches();
+        }
 
-    // Family/model/stepping with extended IDs folded in (Intel SDM Vol. 2A §3.2)
-    // Arithmetic is performed in u16 then truncated to u8, preventing overflow in debug mode.
-    #[allow(clippy::cast_possible_truncation)]
-    let version = Version {
-        family: if basic_info.family == 0xF {
-            ((u16::from(basic_info.extended_family) << 4) + u16::from(basic_info.family)) as u8
-        } else {
-            basic_info.family
+        // Some cloud VMs leave the CPUID brand leaves (0x80000002-4) blank
+        #[cfg(target_os = "linux")]
+        if info.brand_string.is_empty() || info.brand_string == "Unknown" {
+            match std::fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|cpuinfo| proc_cpuinfo_model_name(&cpuinfo).map(str::to_string))
+            {
+                Some(model_name) => {
+                    debug_log!("CPUID brand string missing, using /proc/cpuinfo model name: {model_name}");
+                    info.brand_string = model_name;
+                },
+                None => warn(
+                    warnings,
+                    "brand string unavailable from CPUID and /proc/cpuinfo".to_string(),
+                ),
+            }
+        }
+    }
+
+    // Always `None` without `thermal`, which itself requires `std`
+    info.temperature_celsius = super::detect_temperature_for_info(warnings);
+
+    Ok(info)
+}
+
+/// Detect CPU information using only the CPUID instruction.
+///
+/// Fields that require OS queries (core counts, frequency, peak FLOPS,
+/// temperature, NUMA layout) are left as `0` or `None`.
+///
+/// # Errors
+///
+/// Returns `CpuError` if CPUID access fails or CPU information cannot be read.
+pub fn detect_cpu_from_cpuid() -> Result<CpuInfo, CpuError> {
+    detect_cpuid_reporting(&mut Vec::new())
+}
+
+fn detect_cpuid_reporting(warnings: &mut Vec<String>) -> Result<CpuInfo, CpuError> {
+    detect_with_cpuid(&CpuidWrapper::new(), warnings)
+}
+
+fn detect_with_cpuid<R: CpuidReader>(cpuid: &CpuidWrapper<R>, warnings: &mut Vec<String>) -> Result<CpuInfo, CpuError> {
+    // Basic CPU information via CPUID. Sandboxes (SGX enclaves, some
+    // hypervisors) can hide the vendor and feature leaves; carry on with an
+    // unidentified CPU so the OS-derived fields are still reported.
+    let (cpu_vendor, brand_string, version) = match cpuid.get_basic_info() {
+        Ok(basic_info) => {
+            // Vendor (falls back to the brand string for unrecognised vendor IDs)
+            let cpu_vendor = Vendor::from_cpuid(&basic_info.vendor_string, &basic_info.brand_string);
+            debug_log!("Detected {cpu_vendor} CPU: {}", basic_info.brand_string);
+
+            // Family/model/stepping with extended IDs folded in (Intel SDM Vol. 2A §3.2)
+            // Arithmetic is performed in u16 then truncated to u8, preventing overflow in debug mode.
+            #[allow(clippy::cast_possible_truncation)]
+            let version = Version {
+                family: if basic_info.family == 0xF {
+                    ((u16::from(basic_info.extended_family) << 4) + u16::from(basic_info.family)) as u8
+                } else {
+                    basic_info.family
+                },
+                model: if basic_info.family == 0xF || basic_info.family == 0x6 {
+                    ((u16::from(basic_info.extended_model) << 4) + u16::from(basic_info.model)) as u8
+                } else {
+                    basic_info.model
+                },
+                stepping: basic_info.stepping,
+            };
+            (cpu_vendor, basic_info.brand_string, version)
         },
-        model: if basic_info.family == 0xF || basic_info.family == 0x6 {
-            ((u16::from(basic_info.extended_model) << 4) + u16::from(basic_info.model)) as u8
-        } else {
-            basic_info.model
+        Err(e) => {
+            warn(
+                warnings,
+                format!("CPUID vendor/model leaves unavailable, CPU unidentified: {e}"),
+            );
+            (Vendor::Unknown, String::from("Unknown CPU"), Version::default())
         },
-        stepping: basic_info.stepping,
     };
 
     // ISA feature flags
-    let features =
-        crate::cpu::detect_features().map_err(|e| CpuError::InfoRead(format!("Failed to detect CPU features: {e}")))?;
-
-    // Core counts
-    let logical_cores = u32::try_from(num_cpus::get()).unwrap_or(0);
-    let physical_cores = u32::try_from(num_cpus::get_physical()).unwrap_or(0);
-
-    // Frequency — delegate to the platform-specific detection in `cpu::frequency`
-    let frequency = detect_frequency_for_info();
+    let features = crate::cpu::detect_features()?;
+    let available_but_disabled = X86Features::disabled_by_os(cpuid.get_raw_features(), features);
+    if !available_but_disabled.is_empty() {
+        debug_log!("CPUID advertises features the OS has not enabled: {available_but_disabled:?}");
+    }
 
-    // Cache topology
-    let cache_sizes = detect_cache_sizes(&cpuid);
+    // Cache topology — keep the full per-cache detail and derive the flat size array from it
+    let cache_topology = cpuid
+        .get_cache_topology()
+        .map_err(|e| warn(warnings, format!("cache sizes unavailable: {e}")))
+        .ok();
+    let cache_sizes = cache_topology.as_ref().map_or([None; 4], cache_sizes_from_topology);
 
     // Microarchitecture lookup
     let microarch = detect_uarch(&cpu_vendor, version.family, version.model);
+    let process_node = microarch.as_ref().and_then(Microarch::process_node);
 
-    // Hypervisor detection (CPUID leaf 1 ECX bit 31)
-    let hypervisor = cpuid.detect_hypervisor();
+    // Chiplet topology — leaf 0x8000001E is reserved on Intel, so only query it on AMD and Hygon
+    let amd_topology = if matches!(cpu_vendor, Vendor::AMD | Vendor::Hygon) {
+        cpuid
+            .get_amd_topology()
+            .map_err(|e| warn(warnings, format!("chiplet topology unavailable: {e}")))
+            .ok()
+    } else {
+        None
+    };
+
+    // Physical and virtual address widths
+    let address_sizes = cpuid.get_address_sizes();
 
-    // Theoretical peak double-precision GFLOP/s
-    let peak_flops = crate::cpu::perf::calculate_peak_flops(physical_cores, frequency.max, frequency.base, features);
+    // Hypervisor detection (CPUID leaf 1 ECX bit 31, signature from leaf 0x40000000)
+    let hypervisor_info = cpuid.get_hypervisor_info();
+    let hypervisor = hypervisor_info
+        .present
+        .then(|| hypervisor_info.name().unwrap_or("Unknown").to_string());
 
+    // OS-derived fields (core counts, frequency, ...) are filled in by the caller
     Ok(CpuInfo {
         vendor: cpu_vendor,
-        brand_string: basic_info.brand_string,
+        brand_string,
         version,
-        physical_cores,
-        logical_cores,
-        frequency,
         cache_sizes,
+        cache_topology,
         features,
+        available_but_disabled,
         microarch,
+        process_node: process_node.map(str::to_string),
         hypervisor,
-        peak_flops,
-        p_cores: None,
-        e_cores: None,
+        is_virtualized: hypervisor_info.present,
+        hypervisor_vendor: hypervisor_info.signature,
+        amd_topology,
+        tsc_frequency_mhz: cpuid.get_tsc_frequency(),
+        invariant_tsc: cpuid.has_invariant_tsc(),
+        pmu: cpuid.get_pmu_info(),
+        physical_address_bits: address_sizes.map(|sizes| sizes.physical_bits),
+        virtual_address_bits: address_sizes.map(|sizes| sizes.virtual_bits),
+        ..CpuInfo::default()
     })
 }
 
-/// Resolve CPU frequency using the `frequency` feature when available,
-/// falling back to all-`None` when the feature is compiled out.
-fn detect_frequency_for_info() -> Frequency {
-    #[cfg(feature = "frequency")]
+/// Whether the process is translated by Rosetta 2, from the `sysctl.proc_translated` sysctl (macOS only).
+///
+/// The sysctl is 1 for a translated process and 0 for a native one; Intel Macs
+/// do not have it at all.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+fn detect_rosetta() -> bool {
+    #[cfg(all(target_os = "macos", feature = "macos"))]
     {
-        match crate::cpu::frequency::detect_frequency() {
-            Ok(f) => Frequency {
-                base: f.base,
-                current: f.current,
-                max: f.max,
-            },
-            Err(_) => Frequency::default(),
-        }
+        use sysctl::{Ctl, CtlValue, Sysctl};
+        let translated = Ctl::new("sysctl.proc_translated")
+            .and_then(|ctl| ctl.value())
+            .is_ok_and(|value| matches!(value, CtlValue::Int(1)));
+        debug_log!("sysctl.proc_translated: {translated}");
+        translated
     }
 
-    #[cfg(not(feature = "frequency"))]
-    {
-        Frequency::default()
-    }
+    #[cfg(not(all(target_os = "macos", feature = "macos")))]
+    false
+}
+
+/// Record a non-fatal detection problem and mirror it to the debug log.
+fn warn(warnings: &mut Vec<String>, message: String) {
+    debug_log!("{message}");
+    warnings.push(message);
+}
+
+/// Socket count implied by the OS logical CPU count and CPUID's per-package count.
+///
+/// Used when the OS does not report packages itself. A count that does not
+/// divide evenly (offline CPUs, container limits) gives `None` rather than a guess.
+#[cfg(feature = "std")]
+fn sockets_from_topology(logical: u32, per_package: Option<u32>) -> Option<u32> {
+    let per_package = per_package?;
+    (per_package > 0 && logical >= per_package && logical % per_package == 0).then(|| logical / per_package)
+}
+
+/// The first non-empty `model name` value in `/proc/cpuinfo` content.
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn proc_cpuinfo_model_name(cpuinfo: &str) -> Option<&str> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, value)| key.trim() == "model name" && !value.trim().is_empty())
+        .map(|(_, value)| value.trim())
 }
 
 /// Extract a simplified [L1i, L1d, L2, L3] cache size array from CPUID topology.
-fn detect_cache_sizes(cpuid: &CpuidWrapper) -> [Option<u32>; 4] {
+fn cache_sizes_from_topology(topology: &CacheTopology) -> [Option<u32>; 4] {
     let mut cache_sizes = [None; 4];
 
-    if let Ok(topology) = cpuid.get_cache_topology() {
-        for cache in topology.caches.iter().flatten() {
-            let index = match (cache.level, cache.cache_type) {
-                (1, crate::cpu::CacheType::Instruction) => Some(0),
-                (1, crate::cpu::CacheType::Data) => Some(1),
-                (2, _) => Some(2),
-                (3, _) => Some(3),
-                _ => None,
-            };
+    for cache in topology.iter_present() {
+        let index = match (cache.level, cache.cache_type) {
+            (1, crate::cpu::CacheType::Instruction) => Some(0),
+            (1, crate::cpu::CacheType::Data) => Some(1),
+            (2, _) => Some(2),
+            (3, _) => Some(3),
+            _ => None,
+        };
 
-            if let Some(idx) = index {
-                cache_sizes[idx] = Some(cache.size_kb);
-            }
+        if let Some(idx) = index {
+            cache_sizes[idx] = Some(cache.size_kb);
         }
     }
 
@@ -131,6 +304,7 @@ fn detect_cache_sizes(cpuid: &CpuidWrapper) -> [Option<u32>; 4] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cpu::CpuidRegisters;
 
     #[test]
     #[cfg_attr(not(target_arch = "x86_64"), ignore)]
@@ -146,6 +320,99 @@ mod tests {
         println!("Peak GFLOP/s: {:?}", info.peak_flops);
     }
 
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn test_not_translated_off_macos() {
+        assert!(!detect_rosetta());
+        assert!(!detect_cpu().unwrap().translated);
+        assert!(!CpuInfo::default().translated);
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "x86_64"), ignore)]
+    fn test_detect_cpu_from_cpuid_skips_os_queries() {
+        let info = detect_cpu_from_cpuid().unwrap();
+        assert!(!info.brand_string.is_empty());
+        assert_eq!(info.physical_cores, 0);
+        assert_eq!(info.logical_cores, 0);
+        assert!(info.frequency.max.is_none());
+        assert!(info.numa_nodes.is_none());
+    }
+
+    #[test]
+    #[cfg_attr(not(target_arch = "x86_64"), ignore)]
+    fn test_detect_without_cpuid_leaves() {
+        // A sandbox that reports no supported leaves at all
+        let cpuid = CpuidWrapper::with_reader(|_, _| CpuidRegisters::default());
+        let mut warnings = Vec::new();
+
+        let info = detect_with_cpuid(&cpuid, &mut warnings).unwrap();
+        assert_eq!(info.vendor, Vendor::Unknown);
+        assert_eq!(info.brand_string, "Unknown CPU");
+        assert!(info.microarch.is_none());
+        assert!(info.hypervisor.is_none());
+        assert!(warnings.iter().any(|w| w.contains("CPU unidentified")));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_sockets_from_topology() {
+        assert_eq!(sockets_from_topology(64, Some(32)), Some(2));
+        assert_eq!(sockets_from_topology(16, Some(16)), Some(1));
+        assert_eq!(sockets_from_topology(12, Some(32)), None);
+        assert_eq!(sockets_from_topology(48, Some(32)), None);
+        assert_eq!(sockets_from_topology(32, None), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    fn test_proc_cpuinfo_model_name() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz\nflags\t\t: fpu\n\nprocessor\t: 1\nmodel name\t: ignored\n";
+        assert_eq!(
+            proc_cpuinfo_model_name(cpuinfo),
+            Some("Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz")
+        );
+        assert_eq!(proc_cpuinfo_model_name("processor\t: 0\nmodel name\t:\n"), None);
+        assert_eq!(proc_cpuinfo_model_name(""), None);
+    }
+
+    #[test]
+    fn test_cache_sizes_from_topology() {
+        use crate::cpu::{CacheInfo, CacheType};
+
+        let mut topology = CacheTopology::default();
+        topology.caches[1] = Some(CacheInfo {
+            level: 1,
+            cache_type: CacheType::Data,
+            size_kb: 48,
+            ..CacheInfo::default()
+        });
+        topology.caches[3] = Some(CacheInfo {
+            level: 3,
+            cache_type: CacheType::Unified,
+            size_kb: 32768,
+            shared_by: 16,
+            ..CacheInfo::default()
+        });
+
+        assert_eq!(
+            cache_sizes_from_topology(&topology),
+            [None, Some(48), None, Some(32768)]
+        );
+
+        // A second, smaller L2 entry left over by a fallback path is dropped by normalisation
+        topology.outer_levels = vec![
+            CacheInfo::new(2, CacheType::Unified, 512),
+            CacheInfo::new(2, CacheType::Unified, 256),
+        ];
+        topology.normalize();
+        assert_eq!(
+            cache_sizes_from_topology(&topology),
+            [None, Some(48), Some(512), Some(32768)]
+        );
+        assert_eq!(topology.iter_present().count(), 3);
+    }
+
     #[test]
     #[cfg_attr(not(target_arch = "x86_64"), ignore)]
     fn test_frequency_populated() {
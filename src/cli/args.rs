@@ -1,4 +1,26 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+
+/// Machine-readable output formats selectable with `--format`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Toml,
+    /// The `--fields` keys as a CSV header and one row of values
+    Csv,
+    /// Prometheus text-format gauges, for the node exporter's textfile collector
+    Prometheus,
+}
+
+/// Which core count `--count` prints
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreCount {
+    /// Physical cores
+    Physical,
+    /// Logical processors (hardware threads)
+    Logical,
+}
+
 /// cpufetch - A fast, modern CPU detection tool
 #[derive(Parser, Debug, Default)]
 #[clap(author, version, about, long_about = None)]
@@ -8,26 +30,162 @@ pub struct Args {
     #[clap(short, long)]
     pub frequency: bool,
 
+    /// Estimate the clock by timing a ~10 ms busy loop, for when the OS reports none (`x86_64` cores of a known microarchitecture, requires the `frequency` feature)
+    #[clap(long)]
+    pub benchmark_freq: bool,
+
+    /// Show the current frequency of each core (requires the `frequency` feature)
+    #[clap(long)]
+    pub per_core: bool,
+
+    /// Show the clock of each frequency domain on big.LITTLE CPUs (Linux; implied by `--per-core`)
+    #[clap(long)]
+    pub clusters: bool,
+
+    /// Draw the current frequency as a bar against the maximum
+    #[clap(long)]
+    pub bars: bool,
+
     /// Show CPU cache information
     #[clap(short, long)]
     pub cache: bool,
 
+    /// With `--cache`, also show associativity, line size, sharing and inclusivity per cache level
+    #[clap(long, requires = "cache")]
+    pub cache_detail: bool,
+
+    /// Show the caches as a Level / Type / Size / Assoc / Line / Shared table
+    #[clap(long)]
+    pub table: bool,
+
     /// Show CPU feature flags
     #[clap(short = 'F', long)]
     pub features: bool,
 
-    /// Output in JSON format instead of ASCII art
-    #[clap(short, long)]
+    /// Show the CPU family, model and stepping behind the microarchitecture name, and its process node
+    #[clap(long)]
+    pub uarch: bool,
+
+    /// Show the CPU family, model and stepping identifiers
+    #[clap(long)]
+    pub version_info: bool,
+
+    /// Show the loaded microcode revision
+    #[clap(long)]
+    pub microcode: bool,
+
+    /// Show the cpufreq governor and energy-performance preference (Linux)
+    #[clap(long)]
+    pub governor: bool,
+
+    /// Show the TSC frequency and whether the TSC is invariant (x86)
+    #[clap(long)]
+    pub tsc: bool,
+
+    /// Show the performance monitoring unit's version and counter counts, for profilers (Intel x86)
+    #[clap(long)]
+    pub pmu: bool,
+
+    /// Show physical and virtual address sizes and the byte order
+    #[clap(long)]
+    pub address_sizes: bool,
+
+    /// Show whether CPUID reports a hypervisor, and its signature
+    #[clap(long)]
+    pub vm: bool,
+
+    /// Show the current CPU temperature (requires the `thermal` feature)
+    #[clap(long)]
+    pub temp: bool,
+
+    /// Show the average package power (requires the `power` feature)
+    #[clap(long)]
+    pub power: bool,
+
+    /// Sampling interval for `--power`, in milliseconds
+    #[clap(long, value_name = "MS", default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..), requires = "power")]
+    pub power_interval: u64,
+
+    /// Show the NUMA node to CPU mapping
+    #[clap(long)]
+    pub numa: bool,
+
+    /// Show the speculative-execution vulnerabilities the kernel knows of and their mitigation status (Linux only)
+    #[clap(long)]
+    pub mitigations: bool,
+
+    /// Show the number of CPU sockets (always shown when there is more than one)
+    #[clap(long)]
+    pub sockets: bool,
+
+    /// Show each processor package (socket) separately instead of a merged view
+    #[clap(long)]
+    pub all_packages: bool,
+
+    /// Output in JSON format instead of ASCII art (deprecated: use `--format json`)
+    #[clap(short, long, conflicts_with = "format")]
     pub json: bool,
 
+    /// Output in a machine-readable format instead of ASCII art
+    #[clap(long, value_enum)]
+    pub format: Option<OutputFormat>,
+
+    /// Compare this machine against a snapshot saved with `--json`, listing the fields that differ
+    #[clap(long, value_name = "JSON_FILE", conflicts_with_all = ["json", "format", "watch", "brief"])]
+    pub compare: Option<std::path::PathBuf>,
+
+    /// Exit 0 if the CPU has every feature in LIST (e.g. `avx2,fma`), 1 if any is absent, 2 if a name is unknown
+    #[clap(long, value_name = "LIST", conflicts_with_all = ["watch", "compare", "brief"])]
+    pub require: Option<String>,
+
+    /// Print only the fields in LIST (e.g. `vendor,cores,l3,avx2`) as `key=value` lines, or as a JSON object with `--format json`
+    #[clap(long, value_name = "LIST", conflicts_with_all = ["watch", "compare", "brief", "require"])]
+    pub fields: Option<String>,
+
+    /// Write the report to FILE instead of stdout (uncoloured unless `CLICOLOR_FORCE` is set)
+    #[clap(short, long, value_name = "FILE", conflicts_with = "watch")]
+    pub output: Option<std::path::PathBuf>,
+
+    /// Refresh the frequency readout every SECONDS until Ctrl-C is pressed
+    #[clap(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["json", "format"])]
+    pub watch: Option<u64>,
+
+    /// Stream one compact JSON object every MS milliseconds (JSON Lines) until interrupted
+    #[clap(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["watch", "compare", "brief", "require", "fields"])]
+    pub refresh_rate: Option<u64>,
+
+    /// Print a single summary line and nothing else (implies `--no-logo`)
+    #[clap(long, conflicts_with_all = ["json", "format", "watch", "logo_only"])]
+    pub brief: bool,
+
+    /// Print only the number of logical (default) or physical cores, e.g. to size a thread pool
+    #[clap(long, value_name = "KIND", num_args = 0..=1, default_missing_value = "logical", conflicts_with_all = ["json", "format", "watch", "compare", "brief", "require", "fields", "refresh_rate"])]
+    pub count: Option<CoreCount>,
+
     /// Don't show the CPU logo
     #[clap(long)]
     pub no_logo: bool,
 
+    /// Print only the ASCII art logo
+    #[clap(long, conflicts_with_all = ["info_only", "no_logo"])]
+    pub logo_only: bool,
+
+    /// Print only the CPU information, without the logo
+    #[clap(long, conflicts_with = "logo_only")]
+    pub info_only: bool,
+
     /// Don't use colour in the output
     #[clap(long)]
     pub no_color: bool,
 
+    /// Draw with ASCII characters only, for terminals without UTF-8 (assumed when the locale is not UTF-8)
+    #[clap(long)]
+    pub ascii: bool,
+
+    /// Link the model name to its spec page in terminals that support hyperlinks
+    #[clap(long)]
+    pub links: bool,
+
     /// Force the short (compact) logo variant
     #[clap(long, conflicts_with = "logo_long")]
     pub logo_short: bool,
@@ -40,7 +198,101 @@ pub struct Args {
     #[clap(short, long, value_parser = ["default", "fancy", "retro"])]
     pub style: Option<String>,
 
-    /// Show debug information
+    /// Colour theme, overriding the config file's `theme` (`--no-color` still wins)
+    #[clap(long, value_name = "NAME", value_parser = ["default", "mono", "solarized", "nord"])]
+    pub theme: Option<String>,
+
+    /// Explain on stderr why any field could not be detected
+    #[clap(short, long)]
+    pub verbose: bool,
+
+    /// Log detection steps (CPUID leaves read, fallbacks taken) to stderr
     #[clap(long)]
     pub debug: bool,
+
+    /// Print the raw registers of every CPUID leaf and subleaf instead of the report (x86 only)
+    #[clap(long, conflicts_with_all = ["json", "format", "watch", "compare", "brief", "require", "fields", "refresh_rate", "count"])]
+    pub cpuid_dump: bool,
+}
+
+impl Args {
+    /// Parse `argv` with the config file's `[defaults]` and `theme` as the flags' defaults.
+    ///
+    /// Explicit arguments win: `--format`, `--theme` replace the configured
+    /// values, and a flag the config turns on stays on.
+    ///
+    /// # Errors
+    ///
+    /// Returns the clap error for invalid arguments, `--help` or `--version`,
+    /// or for a configured format or theme that is not a valid value.
+    #[cfg(feature = "config")]
+    pub fn try_parse_with_config<I, T>(config: &crate::config::Config, argv: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        use clap::{CommandFactory, FromArgMatches};
+
+        let defaults = &config.defaults;
+        let mut command = Self::command();
+        for (flag, on) in [
+            ("frequency", defaults.frequency),
+            ("cache", defaults.cache),
+            ("features", defaults.features),
+        ] {
+            if on {
+                command = command.mut_arg(flag, |arg| arg.default_value("true"));
+            }
+        }
+        if let Some(format) = &defaults.format {
+            command = command.mut_arg("format", |arg| arg.default_value(format.clone()));
+        }
+        if let Some(theme) = &config.theme {
+            command = command.mut_arg("theme", |arg| arg.default_value(theme.clone()));
+        }
+
+        Self::from_arg_matches_mut(&mut command.try_get_matches_from(argv)?)
+    }
+
+    /// The requested machine-readable format, folding the legacy `--json` flag into `--format json`.
+    #[must_use]
+    pub fn output_format(&self) -> Option<OutputFormat> {
+        self.format.or(self.json.then_some(OutputFormat::Json))
+    }
+}
+
+#[cfg(all(test, feature = "config"))]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_config_defaults_under_cli() {
+        let config =
+            Config::from_toml_str("theme = \"nord\"\n\n[defaults]\ncache = true\nformat = \"yaml\"\n").unwrap();
+
+        let args = Args::try_parse_with_config(&config, ["cpufetch"]).unwrap();
+        assert!(args.cache);
+        assert!(!args.frequency);
+        assert_eq!(args.format, Some(OutputFormat::Yaml));
+        assert_eq!(args.theme.as_deref(), Some("nord"));
+
+        // The command line wins over the config file
+        let args =
+            Args::try_parse_with_config(&config, ["cpufetch", "--format", "json", "--theme", "mono", "-f"]).unwrap();
+        assert_eq!(args.format, Some(OutputFormat::Json));
+        assert_eq!(args.theme.as_deref(), Some("mono"));
+        assert!(args.frequency && args.cache);
+
+        // No config leaves clap's own defaults
+        let args = Args::try_parse_with_config(&Config::default(), ["cpufetch"]).unwrap();
+        assert!(!args.cache);
+        assert_eq!(args.format, None);
+    }
+
+    #[test]
+    fn test_invalid_config_format_is_rejected() {
+        let config = Config::from_toml_str("[defaults]\nformat = \"xml\"\n").unwrap();
+        assert!(Args::try_parse_with_config(&config, ["cpufetch"]).is_err());
+    }
 }
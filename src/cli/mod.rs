@@ -1,2 +1,4 @@
 pub mod args;
-pub use args::Args;
+pub mod run;
+pub use args::{Args, CoreCount, OutputFormat};
+pub use run::run;
@@ -0,0 +1,211 @@
+//! The CLI's behaviour after argument parsing, as a library function.
+//!
+//! `main` only parses [`Args`], installs logging and calls [`run`] with the
+//! [`NativeProvider`](crate::NativeProvider); tests call it with a mock
+//! [`CpuInfoProvider`] to check CLI behaviour without touching the hardware.
+
+use std::io::Write;
+
+use anyhow::Context;
+
+use super::Args;
+use crate::cpu::CpuInfo;
+use crate::provider::CpuInfoProvider;
+
+/// Report the provider's CPU as `args` asks and return the process exit code.
+///
+/// Output goes to `--output` when given and to stdout otherwise. The exit
+/// code is 0 except for `--require`: 1 when a listed feature is missing and
+/// 2 when a name is unknown or the architecture has no feature flags.
+///
+/// # Errors
+///
+/// Returns an error if the provider fails, or if the report cannot be written.
+pub fn run(provider: &dyn CpuInfoProvider, args: &Args) -> anyhow::Result<i32> {
+    // Get CPU information, keeping any partial-failure warnings for --verbose
+    #[cfg_attr(not(any(feature = "power", feature = "frequency")), allow(unused_mut))]
+    let crate::Detection {
+        info: mut cpu_info,
+        mut warnings,
+    } = provider.detect()?;
+
+    // Power sampling blocks for the interval, so it only runs on request
+    #[cfg(feature = "power")]
+    if args.power {
+        cpu_info.power_watts = crate::cpu::detect_power(std::time::Duration::from_millis(args.power_interval));
+        if cpu_info.power_watts.is_none() {
+            warnings.push("package power unavailable: no readable RAPL energy counter".to_string());
+        }
+    }
+
+    // The busy loop saturates a core while it runs, so it is also opt-in
+    #[cfg(feature = "frequency")]
+    if args.benchmark_freq {
+        cpu_info.frequency.estimated = crate::cpu::estimate_frequency_by_benchmark(cpu_info.microarch.as_ref());
+        if cpu_info.frequency.estimated.is_none() {
+            warnings.push("frequency benchmark unavailable: multiply latency unknown for this CPU".to_string());
+        }
+    }
+
+    if args.verbose {
+        for warning in &warnings {
+            eprintln!("warning: {warning}");
+        }
+    }
+
+    // The report goes to --output when given, stdout otherwise
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(std::io::BufWriter::new(
+            std::fs::File::create(path).with_context(|| format!("cannot create {}", path.display()))?,
+        )),
+        None => Box::new(std::io::stdout().lock()),
+    };
+
+    // CI gating: the exit code alone answers whether the CPU qualifies
+    if let Some(list) = &args.require {
+        let code = check_required(list, &cpu_info, &mut out)?;
+        out.flush()?;
+        return Ok(code);
+    }
+
+    // Raw registers to attach to misdetection reports, read from this machine's CPU
+    if args.cpuid_dump {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            for leaf in crate::cpu::CpuidWrapper::new().dump_leaves() {
+                writeln!(out, "{leaf}")?;
+            }
+            out.flush()?;
+            return Ok(0);
+        }
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        return Err(anyhow::anyhow!("--cpuid-dump is only available on x86 CPUs"));
+    }
+
+    // Selected fields for scripts, without the rest of the report
+    if let Some(list) = &args.fields {
+        let fields = cpu_info.select_fields(list)?;
+        crate::printer::print_fields(&mut out, &fields, args.output_format())?;
+        out.flush()?;
+        return Ok(0);
+    }
+
+    // JSON Lines telemetry, re-reading the volatile fields each tick
+    if let Some(ms) = args.refresh_rate {
+        #[cfg(all(feature = "json", feature = "frequency"))]
+        {
+            crate::printer::stream_json(&mut out, &cpu_info, args, std::time::Duration::from_millis(ms))?;
+            return Ok(0);
+        }
+        #[cfg(not(all(feature = "json", feature = "frequency")))]
+        {
+            let _ = ms;
+            return Err(anyhow::anyhow!(
+                "--refresh-rate requires the 'json' and 'frequency' features"
+            ));
+        }
+    }
+
+    // A bare number for scripts, independent of the display feature
+    if let Some(kind) = args.count {
+        let count = match kind {
+            super::CoreCount::Physical => cpu_info.physical_cores,
+            super::CoreCount::Logical => cpu_info.logical_cores,
+        };
+        writeln!(out, "{count}")?;
+        out.flush()?;
+        return Ok(0);
+    }
+
+    // One line for shell prompts, independent of the display feature
+    if args.brief {
+        writeln!(out, "{}", cpu_info.summary())?;
+        out.flush()?;
+        return Ok(0);
+    }
+
+    // Enhanced CLI with arguments when the display feature is enabled
+    #[cfg(feature = "display")]
+    {
+        // Display CPU information based on the selected format; watch mode goes
+        // before the format so a configured default format does not override it
+        if let Some(path) = &args.compare {
+            let snapshot = load_snapshot(path)?;
+            crate::printer::print_comparison(&mut out, &snapshot, &cpu_info, args)?;
+        } else if let Some(secs) = args.watch {
+            #[cfg(feature = "frequency")]
+            {
+                let interval = std::time::Duration::from_secs(secs);
+                crate::printer::watch_loop(&cpu_info, args, interval)?;
+            }
+            #[cfg(not(feature = "frequency"))]
+            {
+                let _ = secs;
+                eprintln!("Error: watch mode was requested but the 'frequency' feature is not enabled");
+                eprintln!("Recompile with --feature=frequency to enable watch mode");
+                return Err(anyhow::anyhow!("Frequency feature not enabled"));
+            }
+        } else {
+            crate::printer::renderer_for(args.output_format()).render(&cpu_info, args, &mut out)?;
+        }
+    }
+
+    // CLI feature enabled but display feature disabled: plain text or a machine-readable format
+    #[cfg(not(feature = "display"))]
+    crate::printer::renderer_for(args.output_format()).render(&cpu_info, args, &mut out)?;
+
+    out.flush()?;
+    Ok(0)
+}
+
+/// Write a one-line verdict for `--require` to `out` and return the exit code:
+/// 0 when every listed feature is present, 1 when any is absent, 2 for an unknown name.
+///
+/// An unknown name is a usage error, so it goes to stderr instead.
+fn check_required(list: &str, cpu_info: &CpuInfo, out: &mut dyn Write) -> std::io::Result<i32> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+    {
+        let required = match crate::cpu::parse_features(list) {
+            Ok(required) => required,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                return Ok(2);
+            },
+        };
+        let missing = cpu_info.missing_features(required);
+        if missing.is_empty() {
+            let present: Vec<&str> = required.iter_names().map(|(name, _)| name).collect();
+            writeln!(out, "PASS: {}", present.join(", "))?;
+            Ok(0)
+        } else {
+            let absent: Vec<&str> = missing.iter_names().map(|(name, _)| name).collect();
+            writeln!(out, "FAIL: missing {}", absent.join(", "))?;
+            Ok(1)
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        let _ = (list, cpu_info, out);
+        eprintln!("Error: {}", crate::cpu::FeatureError::UnsupportedArch);
+        Ok(2)
+    }
+}
+
+/// Read a `--compare` snapshot written by `cpufetch --json`.
+#[cfg(feature = "display")]
+fn load_snapshot(path: &std::path::Path) -> anyhow::Result<CpuInfo> {
+    #[cfg(feature = "json")]
+    {
+        let json = std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        CpuInfo::from_json(&json).with_context(|| format!("{} is not a cpufetch JSON snapshot", path.display()))
+    }
+
+    #[cfg(not(feature = "json"))]
+    {
+        let _ = path;
+        Err(anyhow::anyhow!(
+            "--compare was requested but the 'json' feature is not enabled (recompile with --features=json)"
+        ))
+    }
+}
@@ -0,0 +1,219 @@
+//! User configuration file.
+//!
+//! Settings are read from `$XDG_CONFIG_HOME/cpufetch-rs/config.toml`, falling
+//! back to `~/.config/cpufetch-rs/config.toml`; on Windows the file is
+//! `%APPDATA%\cpufetch-rs\config.toml`. A missing file is not an error:
+//! every setting has a built-in default.
+//!
+//! ```toml
+//! # Colour theme: default, mono, solarized or nord (`--theme` overrides it)
+//! theme = "nord"
+//!
+//! [defaults]
+//! # Flags turned on as if given on the command line
+//! frequency = true
+//! cache = true
+//! features = true
+//! # Machine-readable format used when none is given: json, yaml, toml, csv or prometheus
+//! format = "json"
+//!
+//! [logo]
+//! # Vendor name (as shown in the "Vendor" line, or a CPUID vendor string) = ASCII art file
+//! AMD = "~/.config/cpufetch-rs/amd.txt"
+//! Intel = "/usr/share/cpufetch-rs/intel-retro.txt"
+//! ```
+//!
+//! Custom art may use the same `$C1`–`$C4` / `$CR` colour markers as the
+//! built-in logos.
+
+use crate::Error;
+use crate::cpu::Vendor;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Parsed contents of the configuration file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Name of the colour theme used when `--theme` is not given
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Default values for command-line flags
+    #[serde(default)]
+    pub defaults: Defaults,
+    /// Custom ASCII art files keyed by vendor name
+    #[serde(default)]
+    pub logo: HashMap<String, PathBuf>,
+}
+
+/// The `[defaults]` table: flags on by default, which the command line can add to but not turn off
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Defaults {
+    /// Show frequency information, as `--frequency`
+    #[serde(default)]
+    pub frequency: bool,
+    /// Show cache information, as `--cache`
+    #[serde(default)]
+    pub cache: bool,
+    /// Show feature flags, as `--features`
+    #[serde(default)]
+    pub features: bool,
+    /// Output format used when `--format` is not given (`json`, `yaml`, `toml`, `csv` or `prometheus`)
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+impl Config {
+    /// Location of the configuration file for the current user, if a home directory is known.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        #[cfg(windows)]
+        if let Some(app_data) = std::env::var_os("APPDATA").filter(|dir| !dir.is_empty()) {
+            return Some(PathBuf::from(app_data).join("cpufetch-rs").join("config.toml"));
+        }
+
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|home| home.join(".config")))?;
+        Some(config_home.join("cpufetch-rs").join("config.toml"))
+    }
+
+    /// Load the configuration from [`Config::default_path`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the file exists but cannot be read or parsed.
+    pub fn load() -> Result<Self, Error> {
+        Self::default_path().map_or_else(|| Ok(Self::default()), |path| Self::load_from(&path))
+    }
+
+    /// Load the configuration from `path`, returning the defaults if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if the file exists but cannot be read or parsed.
+    pub fn load_from(path: &Path) -> Result<Self, Error> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                Self::from_toml_str(&contents).map_err(|e| Error::Config(format!("{}: {e}", path.display())))
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::Config(format!("cannot read {}: {e}", path.display()))),
+        }
+    }
+
+    /// Parse configuration from TOML source.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` describing the first syntax or schema error.
+    pub fn from_toml_str(source: &str) -> Result<Self, Error> {
+        toml::from_str(source).map_err(|e| Error::Config(e.to_string()))
+    }
+
+    /// Path of the custom logo configured for `vendor`, if any.
+    ///
+    /// Keys are matched with [`Vendor`]'s lenient `FromStr`, so "amd",
+    /// "AMD" and `AuthenticAMD` all select the same entry.
+    #[must_use]
+    pub fn logo_path(&self, vendor: &Vendor) -> Option<PathBuf> {
+        self.logo
+            .iter()
+            .find(|(key, _)| key.parse::<Vendor>().is_ok_and(|v| v == *vendor))
+            .map(|(_, path)| expand_home(path))
+    }
+
+    /// Read the custom logo configured for `vendor`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Config` if a logo is configured but its file cannot be read.
+    pub fn custom_logo(&self, vendor: &Vendor) -> Result<Option<String>, Error> {
+        let Some(path) = self.logo_path(vendor) else {
+            return Ok(None);
+        };
+        std::fs::read_to_string(&path)
+            .map(|art| Some(art.trim_end_matches('\n').to_string()))
+            .map_err(|e| Error::Config(format!("cannot read logo {}: {e}", path.display())))
+    }
+}
+
+/// The current user's home directory, from `HOME` (or `USERPROFILE` on Windows).
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Expand a leading `~/` to the home directory.
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_logo_section() {
+        let config = Config::from_toml_str("[logo]\namd = \"/tmp/amd.txt\"\n").unwrap();
+        assert_eq!(config.logo_path(&Vendor::AMD), Some(PathBuf::from("/tmp/amd.txt")));
+        assert_eq!(config.logo_path(&Vendor::Intel), None);
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config = Config::from_toml_str("theme = \"solarized\"\n\n[logo]\namd = \"/tmp/amd.txt\"\n").unwrap();
+        assert_eq!(config.theme.as_deref(), Some("solarized"));
+        assert_eq!(Config::default().theme, None);
+    }
+
+    #[test]
+    fn test_parse_defaults() {
+        let config = Config::from_toml_str("[defaults]\ncache = true\nformat = \"yaml\"\n").unwrap();
+        assert!(config.defaults.cache);
+        assert!(!config.defaults.frequency);
+        assert_eq!(config.defaults.format.as_deref(), Some("yaml"));
+        assert_eq!(Config::default().defaults, Defaults::default());
+
+        assert!(Config::from_toml_str("[defaults]\ncolour = true\n").is_err());
+    }
+
+    #[test]
+    fn test_malformed_config_is_an_error() {
+        let err = Config::from_toml_str("[logo\nAMD = ").unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+
+        let err = Config::from_toml_str("[colours]\nvendor = \"red\"\n").unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+    }
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::load_from(&dir.path().join("config.toml")).unwrap();
+        assert!(config.logo.is_empty());
+    }
+
+    #[test]
+    fn test_custom_logo_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let art = dir.path().join("intel.txt");
+        std::fs::write(&art, "$C1 my intel art\n").unwrap();
+
+        let mut config = Config::default();
+        config.logo.insert("GenuineIntel".to_string(), art);
+        assert_eq!(
+            config.custom_logo(&Vendor::Intel).unwrap().as_deref(),
+            Some("$C1 my intel art")
+        );
+        assert_eq!(config.custom_logo(&Vendor::AMD).unwrap(), None);
+    }
+}
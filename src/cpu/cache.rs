@@ -0,0 +1,232 @@
+//! Cache topology from Linux sysfs.
+//!
+//! The kernel lists every cache of a CPU under
+//! `/sys/devices/system/cpu/cpuN/cache/index*`, filled from firmware tables
+//! or its own CPUID parsing at boot. Hypervisors that mask the CPUID cache
+//! leaves from guests often leave these populated, so they stand in when
+//! CPUID describes no caches. Other platforms report nothing.
+
+use crate::cpu::CacheTopology;
+
+/// Detect the caches of CPU 0 from `/sys/devices/system/cpu/cpu0/cache`.
+///
+/// Caches are placed in the [`CacheTopology`] slots by level and type (L1i,
+/// L1d, L2, L3), with deeper levels such as an L4 after them. `None` when
+/// sysfs lists no caches, on non-Linux platforms, or without the `std` feature.
+#[must_use]
+pub fn detect_sysfs_caches() -> Option<CacheTopology> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        topology_of_cpu(std::path::Path::new("/sys/devices/system/cpu/cpu0"))
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        None
+    }
+}
+
+/// The caches of `cpu_dir` arranged into topology slots, or `None` if it lists none.
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn topology_of_cpu(cpu_dir: &std::path::Path) -> Option<CacheTopology> {
+    let mut topology = CacheTopology::default();
+    let mut found = false;
+    for cache in caches_of_cpu(cpu_dir, None) {
+        debug_log!(
+            "sysfs cache: L{} {}, {} KB",
+            cache.level,
+            cache.cache_type,
+            cache.size_kb
+        );
+        topology.insert(cache);
+        found = true;
+    }
+    found.then_some(topology)
+}
+
+/// The caches listed under `cpu_dir/cache/index*`, in index order, tagged with `kind`.
+#[cfg(all(target_os = "linux", feature = "std"))]
+pub(crate) fn caches_of_cpu(
+    cpu_dir: &std::path::Path,
+    kind: Option<crate::cpu::CoreKind>,
+) -> Vec<crate::cpu::CacheInfo> {
+    use crate::cpu::numa::parse_cpulist;
+    use crate::cpu::{CacheInfo, CacheType};
+
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).ok();
+    let number = |dir: &std::path::Path, file: &str| read(dir.join(file))?.trim().parse::<u32>().ok();
+
+    // SMT siblings share every cache of their core, so divide them out of the sharing count
+    let threads =
+        read(cpu_dir.join("topology").join("thread_siblings_list")).map_or(1, |list| parse_cpulist(&list).len().max(1));
+
+    let Ok(entries) = std::fs::read_dir(cpu_dir.join("cache")) else {
+        return Vec::new();
+    };
+    let mut indexed: Vec<(u32, CacheInfo)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let index = name.to_str()?.strip_prefix("index")?.parse::<u32>().ok()?;
+            let dir = entry.path();
+
+            let cache_type = match read(dir.join("type"))?.trim() {
+                "Data" => CacheType::Data,
+                "Instruction" => CacheType::Instruction,
+                "Unified" => CacheType::Unified,
+                _ => CacheType::Unknown,
+            };
+            let shared = read(dir.join("shared_cpu_list")).map_or(0, |list| parse_cpulist(&list).len());
+            let cache = CacheInfo {
+                line_size: number(&dir, "coherency_line_size").and_then(|n| u16::try_from(n).ok())?,
+                associativity: number(&dir, "ways_of_associativity").and_then(|n| u16::try_from(n).ok())?,
+                sets: number(&dir, "number_of_sets").unwrap_or(0),
+                shared_by: u16::try_from(shared / threads).unwrap_or(u16::MAX),
+                core_kind: kind,
+                ..CacheInfo::new(
+                    u8::try_from(number(&dir, "level")?).ok()?,
+                    cache_type,
+                    parse_size_kb(&read(dir.join("size"))?)?,
+                )
+            };
+            Some((index, cache))
+        })
+        .collect();
+
+    indexed.sort_by_key(|(index, _)| *index);
+    indexed.into_iter().map(|(_, cache)| cache).collect()
+}
+
+/// Parse a sysfs cache size such as `48K` or `30M` into KB.
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn parse_size_kb(size: &str) -> Option<u32> {
+    let size = size.trim();
+    if let Some(kb) = size.strip_suffix('K') {
+        kb.parse().ok()
+    } else if let Some(mb) = size.strip_suffix('M') {
+        mb.parse::<u32>().ok()?.checked_mul(1024)
+    } else {
+        size.parse::<u32>().ok().map(|bytes| bytes / 1024)
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(target_os = "linux", feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::cpu::CacheType;
+    use std::path::Path;
+
+    /// Write one `cache/indexM` directory of a mock sysfs CPU.
+    fn write_cache(cpu: &Path, index: u32, files: &[(&str, &str)]) {
+        let dir = cpu.join("cache").join(format!("index{index}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, value) in files {
+            std::fs::write(dir.join(file), format!("{value}\n")).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_size_kb() {
+        assert_eq!(parse_size_kb("48K\n"), Some(48));
+        assert_eq!(parse_size_kb("30M"), Some(30720));
+        assert_eq!(parse_size_kb("2048"), Some(2));
+        assert_eq!(parse_size_kb("big"), None);
+    }
+
+    #[test]
+    fn test_topology_of_cpu() {
+        // A VM guest whose CPUID cache leaves are masked: 32K L1s, 1M L2, 32M L3
+        let cpu = tempfile::tempdir().unwrap();
+        let cpu = cpu.path();
+        for (index, level, kind, size, ways) in [
+            (0, "1", "Data", "32K", "8"),
+            (1, "1", "Instruction", "32K", "8"),
+            (2, "2", "Unified", "1024K", "16"),
+            (3, "3", "Unified", "32M", "16"),
+        ] {
+            write_cache(
+                cpu,
+                index,
+                &[
+                    ("level", level),
+                    ("type", kind),
+                    ("size", size),
+                    ("ways_of_associativity", ways),
+                    ("coherency_line_size", "64"),
+                ],
+            );
+        }
+
+        let topology = topology_of_cpu(cpu).unwrap();
+        let [l1i, l1d, l2, l3] = topology.caches.map(Option::unwrap);
+        assert_eq!((l1i.cache_type, l1i.size_kb), (CacheType::Instruction, 32));
+        assert_eq!((l1d.cache_type, l1d.associativity), (CacheType::Data, 8));
+        assert_eq!((l2.size_kb, l2.line_size), (1024, 64));
+        assert_eq!((l3.size_kb, l3.sets, l3.core_kind), (32768, 0, None));
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_sysfs_and_cpuid_agree_on_smt_sharing() {
+        use crate::cpu::{CpuidRegisters, CpuidWrapper};
+
+        // A Zen 3 CCD with SMT: the L2 is private, the L3 spans 8 cores (16 threads)
+        let cpu = tempfile::tempdir().unwrap();
+        let cpu = cpu.path();
+        std::fs::create_dir_all(cpu.join("topology")).unwrap();
+        std::fs::write(cpu.join("topology").join("thread_siblings_list"), "0,16\n").unwrap();
+        for (index, level, size, shared) in [(2, "2", "512K", "0,16"), (3, "3", "32M", "0-7,16-23")] {
+            write_cache(
+                cpu,
+                index,
+                &[
+                    ("level", level),
+                    ("type", "Unified"),
+                    ("size", size),
+                    ("ways_of_associativity", "8"),
+                    ("coherency_line_size", "64"),
+                    ("shared_cpu_list", shared),
+                ],
+            );
+        }
+        let sysfs = topology_of_cpu(cpu).unwrap();
+
+        // The same CCD through CPUID: leaf 0x8000001D counts logical processors,
+        // leaf 0x8000001E gives 2 threads per core
+        let reader = |leaf: u32, subleaf: u32| {
+            let regs = |eax, ebx, ecx| CpuidRegisters { eax, ebx, ecx, edx: 0 };
+            match (leaf, subleaf) {
+                // "AuthenticAMD"
+                (0x0, _) => CpuidRegisters {
+                    edx: 0x6974_6E65,
+                    ..regs(0xD, 0x6874_7541, 0x444D_4163)
+                },
+                (0x1, _) => regs(0x00A2_0F10, 0, 0),
+                (0x8000_0000, _) => regs(0x8000_0020, 0, 0),
+                (0x8000_001D, 0) => regs(0x4143, 0x01C0_003F, 1023),
+                (0x8000_001D, 1) => regs(0x3_C163, 0x03C0_003F, 32767),
+                (0x8000_001E, _) => regs(0, 0x100, 0),
+                _ => CpuidRegisters::default(),
+            }
+        };
+        let cpuid = CpuidWrapper::with_reader(reader).get_cache_topology().unwrap();
+
+        for slot in [2, 3] {
+            let (sysfs, cpuid) = (sysfs.caches[slot].unwrap(), cpuid.caches[slot].unwrap());
+            assert_eq!(sysfs.shared_by, cpuid.shared_by, "L{}", sysfs.level);
+        }
+        assert_eq!(sysfs.caches[2].unwrap().shared_by, 1);
+        assert_eq!(sysfs.caches[3].unwrap().shared_by, 8);
+    }
+
+    #[test]
+    fn test_topology_of_cpu_without_caches() {
+        let cpu = tempfile::tempdir().unwrap();
+        assert!(topology_of_cpu(cpu.path()).is_none());
+
+        // An entry missing its geometry is skipped rather than guessed
+        write_cache(cpu.path(), 0, &[("level", "1"), ("type", "Data"), ("size", "32K")]);
+        assert!(topology_of_cpu(cpu.path()).is_none());
+    }
+}
@@ -10,12 +10,28 @@
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use raw_cpuid::CpuId;
 
+use crate::cpu::X86Features;
+use crate::cpu::hybrid::CoreKind;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// Maximum number of cache levels typically found in processors
+/// Number of fixed cache slots in [`CacheTopology`]: L1i, L1d, L2 and L3
 const MAX_CACHE_LEVELS: usize = 4;
 
+/// Size in KB of a cache described by CPUID leaf 4 / 0x8000001D parameters.
+///
+/// The product is formed in `u64` because large server L3 caches overflow
+/// 32-bit arithmetic before the division; the result saturates at `u32::MAX`.
+#[cfg_attr(not(any(target_arch = "x86", target_arch = "x86_64")), allow(dead_code))]
+fn cache_size_kb(ways: u64, partitions: u64, line_size: u64, sets: u64) -> u32 {
+    let bytes = ways
+        .saturating_mul(partitions)
+        .saturating_mul(line_size)
+        .saturating_mul(sets);
+    u32::try_from(bytes / 1024).unwrap_or(u32::MAX)
+}
+
 /// Error types specific to CPUID operations
 #[derive(Debug, thiserror::Error)]
 pub enum CpuidError {
@@ -34,7 +50,12 @@ pub enum CpuidError {
 }
 
 /// Represents a CPU cache
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+///
+/// Marked `#[non_exhaustive]`; outside this crate, build one with
+/// [`CacheInfo::new`] and assign the remaining fields.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub struct CacheInfo {
     /// Cache level (1=L1, 2=L2, 3=L3, etc.)
     pub level: u8,
@@ -48,12 +69,38 @@ pub struct CacheInfo {
     pub associativity: u16,
     /// Number of sets
     pub sets: u32,
-    /// Shared by how many cores
+    /// Number of physical cores sharing this cache (1 for a private cache), or 0 when unknown
+    ///
+    /// CPUID reports the maximum number of logical processors; it is divided
+    /// by the threads per core so that both CPUID and sysfs count cores.
     pub shared_by: u16,
+    /// Whether the cache is inclusive of lower levels; `None` when CPUID does not say
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inclusive: Option<bool>,
+    /// Whether the cache initialises itself without software intervention
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub self_initializing: bool,
+    /// Which kind of core this cache belongs to, for the per-kind caches of a hybrid CPU
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub core_kind: Option<CoreKind>,
+}
+
+impl CacheInfo {
+    /// Create a cache description with the given level, type and size; geometry fields start at zero.
+    #[must_use]
+    pub fn new(level: u8, cache_type: CacheType, size_kb: u32) -> Self {
+        Self {
+            level,
+            cache_type,
+            size_kb,
+            ..Self::default()
+        }
+    }
 }
 
 /// Types of CPU caches
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CacheType {
     Data,
     Instruction,
@@ -62,6 +109,30 @@ pub enum CacheType {
     Unknown,
 }
 
+impl CacheType {
+    /// Sort rank within a level: instruction before data, matching the L1i/L1d slot order.
+    const fn rank(self) -> u8 {
+        match self {
+            CacheType::Instruction => 0,
+            CacheType::Data => 1,
+            CacheType::Unified => 2,
+            CacheType::Unknown => 3,
+        }
+    }
+}
+
+impl PartialOrd for CacheType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CacheType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
 impl fmt::Display for CacheType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -74,7 +145,8 @@ impl fmt::Display for CacheType {
 }
 
 /// Basic CPU information extracted from CPUID
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BasicInfo {
     /// CPU vendor identification string
     pub vendor_string: String,
@@ -99,28 +171,426 @@ pub struct BasicInfo {
 }
 
 /// Collection of cache information for all cache levels
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CacheTopology {
     /// Array of cache information for each detected cache
     /// Index 0 = L1 Instruction, 1 = L1 Data, 2 = L2, 3 = L3
+    ///
+    /// On a hybrid CPU these are the caches of whichever core ran the detection.
     pub caches: [Option<CacheInfo>; MAX_CACHE_LEVELS],
+    /// Caches that fit none of the slots, in detection order, such as the
+    /// L4 eDRAM of Crystal Well and Broadwell-C parts
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub outer_levels: Vec<CacheInfo>,
+    /// Caches of each core kind on a hybrid CPU (see [`crate::cpu::detect_hybrid_caches`]); empty otherwise
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub by_core_kind: Vec<CacheInfo>,
+}
+
+impl CacheTopology {
+    /// The detected caches in slot order (L1i, L1d, L2, L3), skipping absent
+    /// levels, followed by the outer levels.
+    pub fn iter_present(&self) -> impl Iterator<Item = &CacheInfo> {
+        self.caches.iter().flatten().chain(&self.outer_levels)
+    }
+
+    /// Add `cache` to its slot, or to the outer levels if it fits none.
+    ///
+    /// When a cache of the same slot, or the same level and type, is already
+    /// present, the more detailed of the two is kept (geometry known, then
+    /// the larger size).
+    pub fn insert(&mut self, cache: CacheInfo) {
+        let slot = match (cache.level, cache.cache_type) {
+            (1, CacheType::Instruction) => Some(0),
+            (1, CacheType::Data) => Some(1),
+            (2, _) => Some(2),
+            (3, _) => Some(3),
+            _ => None,
+        };
+        let existing = match slot {
+            Some(slot) => self.caches[slot].as_mut(),
+            None => self
+                .outer_levels
+                .iter_mut()
+                .find(|other| (other.level, other.cache_type) == (cache.level, cache.cache_type)),
+        };
+        match existing {
+            Some(existing) if detail_rank(existing) >= detail_rank(&cache) => {},
+            Some(existing) => *existing = cache,
+            None => match slot {
+                Some(slot) => self.caches[slot] = Some(cache),
+                None => self.outer_levels.push(cache),
+            },
+        }
+    }
+
+    /// Rebuild the topology with every cache in its slot, one cache per
+    /// level and type, and the outer levels in ascending order.
+    ///
+    /// Detection fills the topology through [`CacheTopology::insert`]; this
+    /// repairs one assembled by hand or deserialised from an older snapshot.
+    pub fn normalize(&mut self) {
+        let mut caches: Vec<CacheInfo> = self.iter_present().copied().collect();
+        caches.sort_by_key(|cache| (cache.level, cache.cache_type));
+        self.caches = [None; MAX_CACHE_LEVELS];
+        self.outer_levels.clear();
+        for cache in caches {
+            self.insert(cache);
+        }
+    }
+
+    /// The L4 cache (e.g. eDRAM), if one was detected.
+    #[must_use]
+    pub fn l4(&self) -> Option<&CacheInfo> {
+        self.outer_levels.iter().find(|cache| cache.level == 4)
+    }
+
+    /// The detected caches sorted by level, then type (instruction before data).
+    #[must_use]
+    pub fn ordered(&self) -> Vec<&CacheInfo> {
+        let mut caches: Vec<_> = self.iter_present().collect();
+        caches.sort_by_key(|cache| (cache.level, cache.cache_type));
+        caches
+    }
+}
+
+/// Sort key for choosing between two descriptions of the same cache: known geometry first, then size.
+fn detail_rank(cache: &CacheInfo) -> (bool, u32) {
+    (cache.associativity > 0 && cache.line_size > 0, cache.size_kb)
+}
+
+/// Hypervisor identification from CPUID leaves 0x1 and 0x40000000
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HypervisorInfo {
+    /// Hypervisor-present bit (leaf 0x1 ECX bit 31)
+    pub present: bool,
+    /// 12-byte signature from leaf 0x40000000 EBX/ECX/EDX, with NUL padding removed
+    pub signature: Option<String>,
+}
+
+impl HypervisorInfo {
+    /// Readable name for a known signature, e.g. "KVM" for `KVMKVMKVM`
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        self.signature.as_deref().and_then(crate::cpu::info::hypervisor_name)
+    }
+}
+
+/// Rebuild the leaf 0x40000000 signature string from `raw_cpuid`'s identification.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn hypervisor_signature(hypervisor: &raw_cpuid::Hypervisor) -> String {
+    use raw_cpuid::Hypervisor;
+
+    let signature = match *hypervisor {
+        Hypervisor::Xen => "XenVMMXenVMM",
+        Hypervisor::VMware => "VMwareVMware",
+        Hypervisor::HyperV => "Microsoft Hv",
+        Hypervisor::KVM => "KVMKVMKVM",
+        Hypervisor::QEMU => "TCGTCGTCGTCG",
+        Hypervisor::Bhyve => "bhyve bhyve ",
+        Hypervisor::QNX => "QNXQVMBSQG",
+        Hypervisor::ACRN => "ACRNACRNACRN",
+        Hypervisor::Unknown(ebx, ecx, edx) => {
+            let bytes: Vec<u8> = [ebx, ecx, edx].iter().flat_map(|reg| reg.to_le_bytes()).collect();
+            return String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string();
+        },
+    };
+    signature.to_string()
+}
+
+/// Nominal frequencies reported by CPUID leaf 0x16 (Intel Skylake and later)
+///
+/// Each value is in MHz; a field is `None` when the CPU reports zero for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuidFrequencyInfo {
+    /// Processor base (nominal) frequency
+    pub base_mhz: Option<u16>,
+    /// Maximum (turbo) frequency
+    pub max_mhz: Option<u16>,
+    /// Bus (reference) frequency
+    pub bus_mhz: Option<u16>,
+}
+
+impl CpuidFrequencyInfo {
+    /// Build from raw leaf 0x16 register values, treating zero as "not reported".
+    ///
+    /// Returns `None` when all three values are zero, which is common on
+    /// older and virtualised CPUs that expose the leaf without filling it in.
+    #[must_use]
+    pub fn from_raw(base_mhz: u16, max_mhz: u16, bus_mhz: u16) -> Option<Self> {
+        let non_zero = |mhz: u16| (mhz != 0).then_some(mhz);
+        let info = Self {
+            base_mhz: non_zero(base_mhz),
+            max_mhz: non_zero(max_mhz),
+            bus_mhz: non_zero(bus_mhz),
+        };
+        (info != Self::default()).then_some(info)
+    }
+}
+
+/// Address widths reported by CPUID leaf 0x80000008
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddressSizes {
+    /// Physical address bits (`EAX[7:0]`)
+    pub physical_bits: u8,
+    /// Linear (virtual) address bits (`EAX[15:8]`)
+    pub virtual_bits: u8,
+}
+
+/// Architectural performance monitoring capabilities from CPUID leaf 0xA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PmuInfo {
+    /// Architectural PMU version (`EAX[7:0]`)
+    pub version: u8,
+    /// General-purpose counters per logical processor (`EAX[15:8]`)
+    pub general_counters: u8,
+    /// Bit width of the general-purpose counters (`EAX[23:16]`)
+    pub counter_bits: u8,
+    /// Fixed-function counters (`EDX[4:0]`); always 0 before version 2
+    pub fixed_counters: u8,
+}
+
+impl PmuInfo {
+    /// Build from the leaf 0xA fields, treating version 0 as "no architectural PMU".
+    ///
+    /// Versions before 2 do not enumerate fixed counters, so whatever EDX
+    /// holds is ignored for them.
+    #[must_use]
+    pub fn from_raw(version: u8, general_counters: u8, counter_bits: u8, fixed_counters: u8) -> Option<Self> {
+        (version != 0).then_some(Self {
+            version,
+            general_counters,
+            counter_bits,
+            fixed_counters: if version >= 2 { fixed_counters } else { 0 },
+        })
+    }
+}
+
+impl fmt::Display for PmuInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "v{}, {} general + {} fixed counters",
+            self.version, self.general_counters, self.fixed_counters
+        )?;
+        if self.counter_bits > 0 {
+            write!(f, ", {}-bit", self.counter_bits)?;
+        }
+        Ok(())
+    }
+}
+
+/// AMD chiplet topology derived from CPUID leaves 0x8000001E and 0x8000001D
+///
+/// Zen processors group cores into core complexes (CCX) sharing an L3 slice,
+/// and one or more CCXs into a core complex die (CCD).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AmdTopology {
+    /// Hardware threads per physical core (2 with SMT enabled)
+    pub threads_per_core: u8,
+    /// Physical cores sharing one L3 slice (one CCX)
+    pub cores_per_ccx: u32,
+    /// Core complexes per die (2 on Zen/Zen+/Zen 2 and Hygon Dhyana, 1 from Zen 3 onwards)
+    pub ccx_per_ccd: u32,
+    /// NUMA nodes per processor package as reported by the CPU
+    pub nodes_per_processor: u8,
+}
+
+impl AmdTopology {
+    /// Derive the chiplet topology from raw CPUID values.
+    ///
+    /// `l3_sharing_threads` is the number of logical processors sharing the L3
+    /// (leaf 0x8000001D, L3 subleaf), and `family` is the display family
+    /// including the extended family. Returns `None` for pre-Zen families,
+    /// which have no CCX/CCD structure.
+    #[must_use]
+    pub fn from_raw(
+        threads_per_core: u8,
+        l3_sharing_threads: u32,
+        family: u8,
+        nodes_per_processor: u8,
+    ) -> Option<Self> {
+        let ccx_per_ccd = match family {
+            // Zen, Zen+, Zen 2 and Hygon Dhyana (a Zen 1 derivative): two 4-core CCXs per CCD
+            0x17 | 0x18 => 2,
+            // Zen 3 onwards: a single unified CCX per CCD
+            0x19.. => 1,
+            _ => return None,
+        };
+        let threads_per_core = threads_per_core.max(1);
+
+        Some(Self {
+            threads_per_core,
+            cores_per_ccx: l3_sharing_threads / u32::from(threads_per_core),
+            ccx_per_ccd,
+            nodes_per_processor,
+        })
+    }
+}
+
+/// Raw register values returned by one CPUID query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuidRegisters {
+    /// EAX after the query
+    pub eax: u32,
+    /// EBX after the query
+    pub ebx: u32,
+    /// ECX after the query
+    pub ecx: u32,
+    /// EDX after the query
+    pub edx: u32,
+}
+
+/// Registers of one CPUID leaf/subleaf, as listed by [`CpuidWrapper::dump_leaves`]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuidLeafDump {
+    /// Leaf (input EAX)
+    pub leaf: u32,
+    /// Subleaf (input ECX)
+    pub subleaf: u32,
+    /// Registers returned for this leaf and subleaf
+    pub registers: CpuidRegisters,
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl fmt::Display for CpuidLeafDump {
+    /// One row in the `cpuid -r` layout, e.g. `0x00000007 0x00: eax=0x00000002 ebx=...`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let CpuidRegisters { eax, ebx, ecx, edx } = self.registers;
+        write!(
+            f,
+            "0x{:08x} 0x{:02x}: eax=0x{eax:08x} ebx=0x{ebx:08x} ecx=0x{ecx:08x} edx=0x{edx:08x}",
+            self.leaf, self.subleaf
+        )
+    }
+}
+
+/// Upper bound on the subleaves listed for any one leaf
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+const MAX_DUMP_SUBLEAVES: u32 = 64;
+
+/// Subleaves of `leaf` worth listing, given its subleaf 0 registers.
+///
+/// Most leaves ignore ECX. Those that do not either report their last
+/// subleaf in EAX, or are enumerated until an entry comes back invalid.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn dump_subleaves(leaf: u32, first: CpuidRegisters, read: impl Fn(u32) -> CpuidRegisters) -> Vec<u32> {
+    let valid_until = |valid: fn(CpuidRegisters) -> bool| {
+        (0..MAX_DUMP_SUBLEAVES)
+            .take_while(|&subleaf| subleaf == 0 || valid(read(subleaf)))
+            .collect()
+    };
+    match leaf {
+        // Deterministic cache parameters: until the cache type field is null
+        0x4 | 0x8000_001D => valid_until(|regs| regs.eax & 0x1F != 0),
+        // Extended topology: until the level type field is invalid
+        0xB | 0x1F => valid_until(|regs| regs.ecx & 0xFF00 != 0),
+        // XSAVE: subleaves 0 and 1, then one per state component in XCR0/IA32_XSS
+        0xD => {
+            let second = read(1);
+            let components =
+                u64::from(first.eax) | u64::from(first.edx) << 32 | u64::from(second.ecx) | u64::from(second.edx) << 32;
+            (0..MAX_DUMP_SUBLEAVES)
+                .filter(|&subleaf| subleaf < 2 || components & (1 << subleaf) != 0)
+                .collect()
+        },
+        // Leaves whose subleaf 0 EAX holds the last valid subleaf
+        0x7 | 0x14 | 0x17 | 0x18 | 0x1D | 0x20 | 0x23 => (0..=first.eax.min(MAX_DUMP_SUBLEAVES - 1)).collect(),
+        _ => vec![0],
+    }
+}
+
+/// Source of raw CPUID results.
+///
+/// Every query the wrapper makes (vendor, feature flags, cache parameters,
+/// topology) reduces to a leaf/subleaf lookup, so implementing this one method
+/// is enough to replay a captured CPU. Closures of the form
+/// `Fn(leaf, subleaf) -> CpuidRegisters` implement it directly.
+pub trait CpuidReader: Clone {
+    /// Execute CPUID for `leaf` (EAX) and `subleaf` (ECX).
+    fn cpuid(&self, leaf: u32, subleaf: u32) -> CpuidRegisters;
+}
+
+impl<F> CpuidReader for F
+where
+    F: Fn(u32, u32) -> CpuidRegisters + Clone,
+{
+    fn cpuid(&self, leaf: u32, subleaf: u32) -> CpuidRegisters {
+        self(leaf, subleaf)
+    }
+}
+
+/// Reader that executes the CPUID instruction on the current processor
+///
+/// On non-x86 targets there is no CPUID instruction and every query returns zeros.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NativeCpuidReader;
+
+impl CpuidReader for NativeCpuidReader {
+    fn cpuid(&self, leaf: u32, subleaf: u32) -> CpuidRegisters {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let result = raw_cpuid::native_cpuid::cpuid_count(leaf, subleaf);
+            CpuidRegisters {
+                eax: result.eax,
+                ebx: result.ebx,
+                ecx: result.ecx,
+                edx: result.edx,
+            }
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            let _ = (leaf, subleaf);
+            CpuidRegisters::default()
+        }
+    }
+}
+
+/// Bridges a [`CpuidReader`] to the reader interface `raw_cpuid` parses from.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Clone)]
+struct RawReader<R>(R);
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl<R: CpuidReader> raw_cpuid::CpuIdReader for RawReader<R> {
+    fn cpuid2(&self, eax: u32, ecx: u32) -> raw_cpuid::CpuIdResult {
+        let regs = self.0.cpuid(eax, ecx);
+        raw_cpuid::CpuIdResult {
+            eax: regs.eax,
+            ebx: regs.ebx,
+            ecx: regs.ecx,
+            edx: regs.edx,
+        }
+    }
 }
 
 /// Wrapper around raw-cpuid functionality providing higher-level abstractions
-#[derive(Debug)]
-pub struct CpuidWrapper {
+///
+/// Generic over the [`CpuidReader`] so tests can substitute canned results for
+/// the real instruction; [`CpuidWrapper::new`] uses [`NativeCpuidReader`].
+pub struct CpuidWrapper<R: CpuidReader = NativeCpuidReader> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    cpuid: CpuId<RawReader<R>>,
+    /// The same reader, for register-level access `raw_cpuid` does not offer
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-    cpuid: CpuId<raw_cpuid::CpuIdReaderNative>,
+    reader: R,
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    reader: std::marker::PhantomData<R>,
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
-impl Default for CpuidWrapper {
-    fn default() -> Self {
-        Self::new()
+impl<R: CpuidReader> fmt::Debug for CpuidWrapper<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CpuidWrapper").finish_non_exhaustive()
     }
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 impl Default for CpuidWrapper {
     fn default() -> Self {
         Self::new()
@@ -129,16 +599,35 @@ impl Default for CpuidWrapper {
 
 impl CpuidWrapper {
     /// Create a new `CpuidWrapper` instance
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[must_use]
     pub fn new() -> Self {
-        Self { cpuid: CpuId::new() }
+        Self::with_reader(NativeCpuidReader)
     }
+}
 
-    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+impl<R: CpuidReader> CpuidWrapper<R> {
+    /// Create a wrapper that answers CPUID queries from `reader`.
     #[must_use]
-    pub fn new() -> Self {
-        Self {}
+    #[cfg_attr(
+        not(any(target_arch = "x86", target_arch = "x86_64")),
+        allow(clippy::needless_pass_by_value)
+    )]
+    pub fn with_reader(reader: R) -> Self {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            Self {
+                cpuid: CpuId::with_cpuid_reader(RawReader(reader.clone())),
+                reader,
+            }
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            let _ = reader;
+            Self {
+                reader: std::marker::PhantomData,
+            }
+        }
     }
 
     /// Get basic CPU information
@@ -163,6 +652,13 @@ impl CpuidWrapper {
 
             // Get basic feature information
             let feature_info = self.cpuid.get_feature_info().ok_or(CpuidError::UnsupportedLeaf(1))?;
+            trace_log!(
+                "CPUID leaf 0x0: vendor {:?}; leaf 0x1: family {:#x}, model {:#x}, stepping {}",
+                vendor.as_str(),
+                feature_info.family_id(),
+                feature_info.model_id(),
+                feature_info.stepping_id()
+            );
 
             // Extract family, model, stepping details
             let family_id = feature_info.family_id();
@@ -208,15 +704,9 @@ impl CpuidWrapper {
 
             // Try Intel/AMD deterministic cache parameters first (preferred method)
             if let Some(deterministic_cache) = self.cpuid.get_cache_parameters() {
-                let cache_iter = deterministic_cache;
-                let mut index = 0;
-
+                let threads_per_core = self.threads_per_core();
                 // Iterate through all available cache levels
-                for cache in cache_iter {
-                    if index >= MAX_CACHE_LEVELS {
-                        break;
-                    }
-
+                for cache in deterministic_cache {
                     // Map cache type
                     let cache_type = match cache.cache_type() {
                         raw_cpuid::CacheType::Data => CacheType::Data,
@@ -226,44 +716,39 @@ impl CpuidWrapper {
                     };
 
                     // Calculate cache size
-                    let size_kb = cache.associativity()
-                        * cache.physical_line_partitions()
-                        * cache.coherency_line_size()
-                        * cache.sets()
-                        / 1024;
-
-                    // Add to our topology at the appropriate index
-                    let target_index = match (cache.level(), cache_type) {
-                        (1, CacheType::Instruction) => 0,
-                        (1, CacheType::Data) => 1,
-                        (2, _) => 2,
-                        (3, _) => 3,
-                        _ => {
-                            // For other levels, just use the index as is
-                            // but ensure we don't exceed our array bounds
-                            if index < MAX_CACHE_LEVELS {
-                                index
-                            } else {
-                                continue;
-                            }
-                        },
-                    };
+                    let size_kb = cache_size_kb(
+                        cache.associativity() as u64,
+                        cache.physical_line_partitions() as u64,
+                        cache.coherency_line_size() as u64,
+                        cache.sets() as u64,
+                    );
+
+                    trace_log!(
+                        "CPUID deterministic cache leaf: L{} {:?}, {} KB, shared by {} logical processors",
+                        cache.level(),
+                        cache_type,
+                        size_kb,
+                        cache.max_cores_for_cache()
+                    );
 
                     // Cache fields are bounded by CPU hardware limits; truncation is intentional.
                     #[allow(clippy::cast_possible_truncation)]
                     let cache_entry = CacheInfo {
                         level: cache.level(),
                         cache_type,
-                        size_kb: size_kb as u32,
+                        size_kb,
                         line_size: cache.coherency_line_size() as u16,
                         associativity: cache.associativity() as u16,
                         sets: cache.sets() as u32,
-                        shared_by: cache.max_cores_for_cache() as u16,
+                        shared_by: (cache.max_cores_for_cache() / threads_per_core).max(1) as u16,
+                        inclusive: Some(cache.is_inclusive()),
+                        self_initializing: cache.is_self_initializing(),
+                        core_kind: None,
                     };
-                    topology.caches[target_index] = Some(cache_entry);
+                    // L1i, L1d, L2 and L3 have fixed slots; an L4 (eDRAM) or anything odd goes after them
+                    topology.insert(cache_entry);
 
                     cache_found = true;
-                    index += 1;
                 }
 
                 if cache_found {
@@ -272,6 +757,7 @@ impl CpuidWrapper {
             }
 
             // Last resort: use legacy cache descriptors
+            debug_log!("CPUID leaf 0x4/0x8000001D yielded no caches, falling back to leaf 0x2 descriptors");
             if self.cpuid.get_cache_info().is_some() {
                 // We'll check for cache descriptors, but they're not well supported in newer CPUs
                 // So this is primarily a fallback method
@@ -293,6 +779,7 @@ impl CpuidWrapper {
                             associativity: 8, // Common associativity
                             sets: 0,
                             shared_by: 1,
+                            ..CacheInfo::default()
                         });
 
                         topology.caches[1] = Some(CacheInfo {
@@ -303,6 +790,7 @@ impl CpuidWrapper {
                             associativity: 8, // Common associativity
                             sets: 0,
                             shared_by: 1,
+                            ..CacheInfo::default()
                         });
 
                         // Note: this is only a fallback with reasonable defaults
@@ -317,6 +805,7 @@ impl CpuidWrapper {
                             associativity: 8, // Common associativity
                             sets: 0,
                             shared_by: 1,
+                            ..CacheInfo::default()
                         });
 
                         topology.caches[1] = Some(CacheInfo {
@@ -327,6 +816,7 @@ impl CpuidWrapper {
                             associativity: 8, // Common associativity
                             sets: 0,
                             shared_by: 1,
+                            ..CacheInfo::default()
                         });
                     }
                 }
@@ -341,40 +831,385 @@ impl CpuidWrapper {
         }
     }
 
+    /// Hardware threads per physical core, for turning the logical processor
+    /// counts of the cache leaves into cores.
+    ///
+    /// Read from the SMT level of leaf 0xB, or leaf 0x8000001E on Zen parts
+    /// without it; assumes no SMT when neither is available.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn threads_per_core(&self) -> usize {
+        let smt_level = self.cpuid.get_extended_topology_info().and_then(|mut levels| {
+            levels
+                .find(|level| level.level_type() == raw_cpuid::TopologyType::SMT)
+                .map(|level| usize::from(level.processors()))
+        });
+        // Before Zen (family 0x17) the same field counts the cores of a Bulldozer module
+        let zen = self
+            .cpuid
+            .get_feature_info()
+            .is_some_and(|info| info.family_id() >= 0x17);
+        let threads = smt_level.or_else(|| {
+            self.cpuid
+                .get_processor_topology_info()
+                .filter(|_| zen)
+                .map(|topology| usize::from(topology.threads_per_core()))
+        });
+        threads.unwrap_or(1).max(1)
+    }
+
+    /// Whether the deterministic cache parameter leaves (0x4, or 0x8000001D on
+    /// AMD) describe at least one cache.
+    ///
+    /// When they do not, [`CpuidWrapper::get_cache_topology`] has only the
+    /// legacy leaf 0x2 or typical sizes to go on. Always `false` on non-x86 platforms.
+    #[must_use]
+    pub fn has_cache_parameters(&self) -> bool {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            self.cpuid
+                .get_cache_parameters()
+                .is_some_and(|mut caches| caches.next().is_some())
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        false
+    }
+
     /// Detect whether the CPU is running inside a hypervisor.
     ///
-    /// Checks CPUID leaf 0x1 ECX bit 31 (hypervisor present bit).  If set,
-    /// attempts to identify the hypervisor from leaf 0x40000000.
-    /// Returns `None` on bare metal or non-x86 platforms.
+    /// Returns the readable hypervisor name (see [`CpuidWrapper::get_hypervisor_info`]),
+    /// `"Unknown"` for an unrecognised signature, or `None` on bare metal or
+    /// non-x86 platforms.
     #[must_use]
     pub fn detect_hypervisor(&self) -> Option<String> {
+        let info = self.get_hypervisor_info();
+        info.present.then(|| info.name().unwrap_or("Unknown").to_string())
+    }
+
+    /// Read the hypervisor-present bit (leaf 0x1 ECX bit 31) and, when set,
+    /// the hypervisor signature from leaf 0x40000000.
+    ///
+    /// On bare metal and non-x86 platforms `present` is `false` and
+    /// `signature` is `None`.
+    #[must_use]
+    pub fn get_hypervisor_info(&self) -> HypervisorInfo {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
-            let feature_info = self.cpuid.get_feature_info()?;
-            if !feature_info.has_hypervisor() {
+            let present = self.cpuid.get_feature_info().is_some_and(|info| info.has_hypervisor());
+            if !present {
+                return HypervisorInfo::default();
+            }
+            debug_log!("CPUID leaf 0x1 ECX[31] set: running under a hypervisor");
+
+            let signature = self
+                .cpuid
+                .get_hypervisor_info()
+                .map(|info| hypervisor_signature(&info.identify()));
+            debug_log!("Hypervisor signature (leaf 0x40000000): {signature:?}");
+            HypervisorInfo { present, signature }
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        HypervisorInfo::default()
+    }
+
+    /// Get AMD CCX/CCD chiplet topology.
+    ///
+    /// Combines the threads-per-core count from leaf 0x8000001E with the L3
+    /// sharing count from leaf 0x8000001D. Only meaningful on AMD Zen and
+    /// later; callers should check the vendor first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CpuidError` if the topology leaves are unavailable or the CPU
+    /// family predates the chiplet design.
+    pub fn get_amd_topology(&self) -> Result<AmdTopology, CpuidError> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let topology = self
+                .cpuid
+                .get_processor_topology_info()
+                .ok_or(CpuidError::UnsupportedLeaf(0x8000_001E))?;
+
+            let l3_sharing = self
+                .cpuid
+                .get_cache_parameters()
+                .and_then(|mut caches| caches.find(|cache| cache.level() == 3))
+                .map(|l3| l3.max_cores_for_cache())
+                .ok_or(CpuidError::CacheInfoNotAvailable(3))?;
+
+            // raw-cpuid already folds the extended family into `family_id()`
+            let family = self
+                .cpuid
+                .get_feature_info()
+                .ok_or(CpuidError::UnsupportedLeaf(1))?
+                .family_id();
+
+            trace_log!(
+                "CPUID leaf 0x8000001E: {} threads/core, {} nodes/processor; L3 shared by {l3_sharing}",
+                topology.threads_per_core(),
+                topology.nodes_per_processor()
+            );
+
+            AmdTopology::from_raw(
+                topology.threads_per_core(),
+                u32::try_from(l3_sharing).map_err(|_| CpuidError::UnexpectedResult)?,
+                family,
+                topology.nodes_per_processor(),
+            )
+            .ok_or(CpuidError::UnexpectedResult)
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            Err(CpuidError::UnsupportedArchitecture)
+        }
+    }
+
+    /// Logical processors per physical package, from the extended topology leaf 0xB.
+    ///
+    /// The outermost topology level counts every logical processor sharing
+    /// the package. Returns `None` when the leaf is unsupported or reports zero.
+    #[must_use]
+    pub fn logical_processors_per_package(&self) -> Option<u32> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let outermost = self.cpuid.get_extended_topology_info()?.last()?;
+            trace_log!(
+                "CPUID leaf 0xB: {} logical processors per package",
+                outermost.processors()
+            );
+            Some(u32::from(outermost.processors())).filter(|&count| count > 0)
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        None
+    }
+
+    /// Get the base, maximum, and bus frequencies from CPUID leaf 0x16.
+    ///
+    /// Returns `None` when the leaf is unsupported (pre-Skylake Intel, AMD) or
+    /// reports all zeros (common under hypervisors), so callers can fall
+    /// through to OS-provided frequency sources.
+    #[must_use]
+    pub fn get_frequency_info(&self) -> Option<CpuidFrequencyInfo> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let Some(info) = self.cpuid.get_processor_frequency_info() else {
+                debug_log!("CPUID leaf 0x16 not supported");
+                return None;
+            };
+            trace_log!(
+                "CPUID leaf 0x16: base {} MHz, max {} MHz, bus {} MHz",
+                info.processor_base_frequency(),
+                info.processor_max_frequency(),
+                info.bus_frequency()
+            );
+            CpuidFrequencyInfo::from_raw(
+                info.processor_base_frequency(),
+                info.processor_max_frequency(),
+                info.bus_frequency(),
+            )
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        None
+    }
+
+    /// Get the TSC frequency in MHz from CPUID leaf 0x15.
+    ///
+    /// The leaf gives the ratio of the TSC to the core crystal clock and, on
+    /// newer parts, the crystal frequency. Where the crystal is not enumerated
+    /// (Skylake client), the base frequency of leaf 0x16 stands in, as the
+    /// Linux kernel does. Returns `None` when the leaf is unsupported (AMD,
+    /// pre-Skylake Intel) or zeroed, as under most hypervisors.
+    #[must_use]
+    pub fn get_tsc_frequency(&self) -> Option<f64> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let Some(info) = self.cpuid.get_tsc_info() else {
+                debug_log!("CPUID leaf 0x15 not supported");
+                return None;
+            };
+            trace_log!(
+                "CPUID leaf 0x15: TSC/crystal ratio {}/{}, crystal {} Hz",
+                info.numerator(),
+                info.denominator(),
+                info.nominal_frequency()
+            );
+            if info.denominator() == 0 || info.numerator() == 0 {
                 return None;
             }
-            // Try to name the hypervisor from leaf 0x40000000
-            if let Some(hv_info) = self.cpuid.get_hypervisor_info() {
-                let name = match hv_info.identify() {
-                    raw_cpuid::Hypervisor::Xen => "Xen",
-                    raw_cpuid::Hypervisor::VMware => "VMware",
-                    raw_cpuid::Hypervisor::HyperV => "Hyper-V",
-                    raw_cpuid::Hypervisor::KVM => "KVM",
-                    raw_cpuid::Hypervisor::Bhyve => "bhyve",
-                    raw_cpuid::Hypervisor::QNX => "QNX",
-                    raw_cpuid::Hypervisor::ACRN => "ACRN",
-                    _ => "Unknown",
-                };
-                return Some(name.to_string());
+            if info.nominal_frequency() != 0 {
+                let crystal_mhz = f64::from(info.nominal_frequency()) / 1_000_000.0;
+                return Some(crystal_mhz * f64::from(info.numerator()) / f64::from(info.denominator()));
             }
-            Some("Unknown".to_string())
+            // The crystal derived from the base clock cancels out: the TSC runs at the base frequency
+            self.get_frequency_info()?.base_mhz.map(f64::from)
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        None
+    }
+
+    /// Whether the TSC ticks at a constant rate in every P-, C- and T-state
+    /// (CPUID leaf 0x80000007 EDX bit 8), so it can time code across frequency changes.
+    ///
+    /// False when the leaf is unsupported, as under some hypervisors, and on non-x86 platforms.
+    #[must_use]
+    pub fn has_invariant_tsc(&self) -> bool {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            self.cpuid
+                .get_advanced_power_mgmt_info()
+                .is_some_and(|info| info.has_invariant_tsc())
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        false
+    }
+
+    /// Get the performance monitoring unit's version and counters from CPUID leaf 0xA.
+    ///
+    /// Returns `None` when the leaf is unsupported or reports version 0, as
+    /// on AMD (which enumerates its counters elsewhere) and under hypervisors
+    /// that hide the PMU from guests.
+    #[must_use]
+    pub fn get_pmu_info(&self) -> Option<PmuInfo> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let Some(info) = self.cpuid.get_performance_monitoring_info() else {
+                debug_log!("CPUID leaf 0xA not supported");
+                return None;
+            };
+            trace_log!(
+                "CPUID leaf 0xA: version {}, {} x {}-bit general counters, {} fixed",
+                info.version_id(),
+                info.number_of_counters(),
+                info.counter_bit_width(),
+                info.fixed_function_counters()
+            );
+            PmuInfo::from_raw(
+                info.version_id(),
+                info.number_of_counters(),
+                info.counter_bit_width(),
+                info.fixed_function_counters(),
+            )
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        None
+    }
+
+    /// Get the physical and virtual address widths from CPUID leaf 0x80000008.
+    ///
+    /// Returns `None` when the leaf is unsupported or reports zero widths, as
+    /// some hypervisors do.
+    #[must_use]
+    pub fn get_address_sizes(&self) -> Option<AddressSizes> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let Some(info) = self.cpuid.get_processor_capacity_feature_info() else {
+                debug_log!("CPUID leaf 0x80000008 not supported");
+                return None;
+            };
+            let sizes = AddressSizes {
+                physical_bits: info.physical_address_bits(),
+                virtual_bits: info.linear_address_bits(),
+            };
+            trace_log!(
+                "CPUID leaf 0x80000008: {} physical, {} virtual address bits",
+                sizes.physical_bits,
+                sizes.virtual_bits
+            );
+            (sizes.physical_bits != 0 && sizes.virtual_bits != 0).then_some(sizes)
         }
 
         #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
         None
     }
 
+    /// Feature flags advertised by CPUID leaves 0x1 and 0x7, whether or not
+    /// the operating system has enabled them.
+    ///
+    /// Unlike [`crate::cpu::detect_features`], this does not consult
+    /// OSXSAVE/XCR0, so AVX and AVX-512 are reported even when a kernel or
+    /// hypervisor leaves their register state disabled. Empty on non-x86 platforms.
+    #[must_use]
+    pub fn get_raw_features(&self) -> X86Features {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let mut features = X86Features::empty();
+            if let Some(info) = self.cpuid.get_feature_info() {
+                features.set(X86Features::SSE, info.has_sse());
+                features.set(X86Features::SSE2, info.has_sse2());
+                features.set(X86Features::SSE3, info.has_sse3());
+                features.set(X86Features::SSSE3, info.has_ssse3());
+                features.set(X86Features::SSE4_1, info.has_sse41());
+                features.set(X86Features::SSE4_2, info.has_sse42());
+                features.set(X86Features::AVX, info.has_avx());
+                features.set(X86Features::FMA, info.has_fma());
+                features.set(X86Features::F16C, info.has_f16c());
+                features.set(X86Features::POPCNT, info.has_popcnt());
+                features.set(X86Features::AES, info.has_aesni());
+            }
+            if let Some(info) = self.cpuid.get_extended_feature_info() {
+                features.set(X86Features::AVX2, info.has_avx2());
+                features.set(X86Features::BMI1, info.has_bmi1());
+                features.set(X86Features::BMI2, info.has_bmi2());
+                features.set(X86Features::AVX512F, info.has_avx512f());
+                features.set(X86Features::AVX512BW, info.has_avx512bw());
+                features.set(X86Features::AVX512CD, info.has_avx512cd());
+                features.set(X86Features::AVX512DQ, info.has_avx512dq());
+                features.set(X86Features::AVX512VL, info.has_avx512vl());
+                features.set(X86Features::SHA, info.has_sha());
+                features.set(X86Features::GFNI, info.has_gfni());
+            }
+            trace_log!("CPUID leaves 0x1/0x7 advertise: {features:?}");
+            features
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        X86Features::empty()
+    }
+
+    /// Raw registers of every standard (`0..=max`) and extended
+    /// (`0x80000000..=max`) leaf, with the subleaves each one defines.
+    ///
+    /// Meant for bug reports about misdetection: the dump lets a captured CPU
+    /// be replayed through [`CpuidWrapper::with_reader`]. Maximum leaves are
+    /// capped at 0xFF above their range base in case a part reports garbage.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[must_use]
+    pub fn dump_leaves(&self) -> Vec<CpuidLeafDump> {
+        let max_standard = self.reader.cpuid(0, 0).eax.min(0xFF);
+        let max_extended = self.reader.cpuid(0x8000_0000, 0).eax;
+        // Parts without the extended range echo some other leaf back here
+        let extended = (max_extended & 0xFFFF_0000 == 0x8000_0000)
+            .then(|| 0x8000_0000..=max_extended.min(0x8000_00FF))
+            .into_iter()
+            .flatten();
+
+        let mut dump = Vec::new();
+        for leaf in (0..=max_standard).chain(extended) {
+            let first = self.reader.cpuid(leaf, 0);
+            for subleaf in dump_subleaves(leaf, first, |subleaf| self.reader.cpuid(leaf, subleaf)) {
+                let registers = if subleaf == 0 {
+                    first
+                } else {
+                    self.reader.cpuid(leaf, subleaf)
+                };
+                dump.push(CpuidLeafDump {
+                    leaf,
+                    subleaf,
+                    registers,
+                });
+            }
+        }
+        trace_log!("CPUID dump: {} leaf/subleaf entries", dump.len());
+        dump
+    }
+
     /// Check if a specific CPUID feature is supported.
     /// Raw bit-level access was removed in raw-cpuid 11.x; always returns false.
     #[must_use]
@@ -401,6 +1236,162 @@ pub enum CpuidRegister {
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn test_cache_topology_iteration_order() {
+        use super::{CacheInfo, CacheTopology, CacheType};
+
+        let mut topology = CacheTopology::default();
+        topology.caches[3] = Some(CacheInfo::new(3, CacheType::Unified, 32768));
+        topology.caches[0] = Some(CacheInfo::new(1, CacheType::Instruction, 32));
+        topology.caches[1] = Some(CacheInfo::new(1, CacheType::Data, 48));
+
+        let slots: Vec<_> = topology.iter_present().map(|c| (c.level, c.cache_type)).collect();
+        assert_eq!(
+            slots,
+            [
+                (1, CacheType::Instruction),
+                (1, CacheType::Data),
+                (3, CacheType::Unified)
+            ]
+        );
+
+        // Misplaced slots still come out in level order
+        topology.caches.swap(0, 3);
+        let ordered: Vec<_> = topology.ordered().iter().map(|c| (c.level, c.cache_type)).collect();
+        assert_eq!(ordered, slots);
+        assert!(CacheTopology::default().ordered().is_empty());
+    }
+
+    #[test]
+    fn test_cache_topology_normalize() {
+        use super::{CacheInfo, CacheTopology, CacheType};
+
+        let detailed = |level, cache_type, size_kb| CacheInfo {
+            associativity: 8,
+            line_size: 64,
+            ..CacheInfo::new(level, cache_type, size_kb)
+        };
+
+        // A messy fallback result: L3 in the L1i slot, a bare L1d beside a
+        // detailed one, and the L2 and L4 listed twice among the outer levels
+        let mut topology = CacheTopology::default();
+        topology.caches[0] = Some(detailed(3, CacheType::Unified, 16384));
+        topology.caches[1] = Some(CacheInfo::new(1, CacheType::Data, 64));
+        topology.outer_levels = vec![
+            CacheInfo::new(4, CacheType::Unified, 65536),
+            detailed(1, CacheType::Data, 32),
+            detailed(2, CacheType::Unified, 512),
+            CacheInfo::new(2, CacheType::Unified, 1024),
+            detailed(4, CacheType::Unified, 131_072),
+            detailed(1, CacheType::Instruction, 32),
+        ];
+
+        topology.normalize();
+        let caches: Vec<_> = topology
+            .iter_present()
+            .map(|c| (c.level, c.cache_type, c.size_kb))
+            .collect();
+        assert_eq!(
+            caches,
+            [
+                (1, CacheType::Instruction, 32),
+                (1, CacheType::Data, 32),
+                (2, CacheType::Unified, 512),
+                (3, CacheType::Unified, 16384),
+                (4, CacheType::Unified, 131_072),
+            ]
+        );
+
+        // Without geometry on either side, the larger size wins
+        let mut topology = CacheTopology::default();
+        topology.insert(CacheInfo::new(2, CacheType::Unified, 256));
+        topology.insert(CacheInfo::new(2, CacheType::Unified, 1024));
+        topology.insert(CacheInfo::new(2, CacheType::Unified, 512));
+        assert_eq!(topology.caches[2].map(|c| c.size_kb), Some(1024));
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_hypervisor_signature() {
+        use raw_cpuid::Hypervisor;
+
+        assert_eq!(super::hypervisor_signature(&Hypervisor::KVM), "KVMKVMKVM");
+        // "Parallels" signature arrives as an unrecognised register triple
+        let ebx = u32::from_le_bytes(*b" lrp");
+        let ecx = u32::from_le_bytes(*b"epyh");
+        let edx = u32::from_le_bytes(*b"  vr");
+        assert_eq!(
+            super::hypervisor_signature(&Hypervisor::Unknown(ebx, ecx, edx)),
+            " lrpepyh  vr"
+        );
+    }
+
+    #[test]
+    fn test_hypervisor_info_name() {
+        let known = |signature: &str| super::HypervisorInfo {
+            present: true,
+            signature: Some(signature.to_string()),
+        };
+        assert_eq!(known("VMwareVMware").name(), Some("VMware"));
+        assert_eq!(known("KVMKVMKVM").name(), Some("KVM"));
+        assert_eq!(known("Microsoft Hv").name(), Some("Hyper-V"));
+        assert_eq!(known("XenVMMXenVMM").name(), Some("Xen"));
+        assert_eq!(known("NotAHypervsr").name(), None);
+
+        let bare_metal = super::HypervisorInfo::default();
+        assert!(!bare_metal.present);
+        assert_eq!(bare_metal.name(), None);
+    }
+
+    #[test]
+    fn test_frequency_info_from_raw() {
+        let info = super::CpuidFrequencyInfo::from_raw(2100, 4800, 100).unwrap();
+        assert_eq!(info.base_mhz, Some(2100));
+        assert_eq!(info.max_mhz, Some(4800));
+        assert_eq!(info.bus_mhz, Some(100));
+
+        // Partially filled leaves keep the values that are present
+        let partial = super::CpuidFrequencyInfo::from_raw(2100, 0, 0).unwrap();
+        assert_eq!(partial.max_mhz, None);
+
+        // All-zero leaves (common under hypervisors) are treated as absent
+        assert!(super::CpuidFrequencyInfo::from_raw(0, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_cache_size_kb_large_l3() {
+        // Synthetic 8 GiB cache: 64 ways × 4 partitions × 64 B × 524288 sets.
+        // The byte count is 2^33, which wraps to zero in 32-bit arithmetic.
+        assert_eq!(super::cache_size_kb(64, 4, 64, 524_288), 8_388_608);
+        // 48 KB L1d for comparison
+        assert_eq!(super::cache_size_kb(12, 1, 64, 64), 48);
+        // Absurd values saturate rather than wrap
+        assert_eq!(super::cache_size_kb(u64::MAX, 2, 64, 64), u32::MAX);
+    }
+
+    #[test]
+    fn test_amd_topology_zen2() {
+        // Zen 2: 4 cores × 2 threads share each L3 slice, two CCXs per CCD
+        let topo = super::AmdTopology::from_raw(2, 8, 0x17, 1).unwrap();
+        assert_eq!(topo.cores_per_ccx, 4);
+        assert_eq!(topo.ccx_per_ccd, 2);
+
+        // Hygon Dhyana is Zen 1 underneath, with the same two CCXs per CCD
+        let topo = super::AmdTopology::from_raw(2, 8, 0x18, 1).unwrap();
+        assert_eq!(topo.ccx_per_ccd, 2);
+    }
+
+    #[test]
+    fn test_amd_topology_zen3_and_pre_zen() {
+        // Zen 3: 8 cores × 2 threads share a unified L3, one CCX per CCD
+        let topo = super::AmdTopology::from_raw(2, 16, 0x19, 1).unwrap();
+        assert_eq!(topo.cores_per_ccx, 8);
+        assert_eq!(topo.ccx_per_ccd, 1);
+
+        // Bulldozer has no chiplets
+        assert!(super::AmdTopology::from_raw(2, 8, 0x15, 1).is_none());
+    }
+
     #[cfg(any(
         all(target_arch = "x86", not(target_env = "sgx"), target_feature = "sse"),
         all(target_arch = "x86_64", not(target_env = "sgx"))
@@ -434,7 +1425,7 @@ mod tests {
         let topology = wrapper.get_cache_topology().expect("Failed to get cache topology");
 
         // Most CPUs should have at least one cache
-        let has_at_least_one_cache = topology.caches.iter().any(Option::is_some);
+        let has_at_least_one_cache = topology.iter_present().next().is_some();
         assert!(has_at_least_one_cache, "No caches detected on this CPU");
     }
 }
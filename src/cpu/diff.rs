@@ -0,0 +1,236 @@
+//! Field-by-field comparison of two [`CpuInfo`] snapshots.
+//!
+//! Used by `cpufetch --compare` to set a saved JSON report against the
+//! current machine, and by `==` to tell whether two detections describe the
+//! same CPU. Only stable properties are compared; the current clock,
+//! temperature and power change from one run to the next.
+
+use crate::cpu::CpuInfo;
+
+/// One difference between two [`CpuInfo`] snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldDiff {
+    /// A scalar field whose displayed value differs, e.g. "L3 Cache": "16384 KB" vs "32768 KB"
+    Value {
+        /// Display label of the field
+        field: &'static str,
+        /// Value in the snapshot `diff` was called on
+        left: String,
+        /// Value in the snapshot passed to `diff`
+        right: String,
+    },
+    /// Feature flags present in only one of the two snapshots
+    Features {
+        /// Flags the right-hand snapshot has and the left-hand one lacks
+        gained: Vec<&'static str>,
+        /// Flags the left-hand snapshot has and the right-hand one lacks
+        lost: Vec<&'static str>,
+    },
+}
+
+impl CpuInfo {
+    /// Fields that differ between `self` (left) and `other` (right), in display order.
+    ///
+    /// Compares the vendor, model, microarchitecture, core counts, cache sizes,
+    /// base and maximum frequency, and feature flags; an empty result means the
+    /// snapshots describe the same CPU. Missing values are shown as "Unknown".
+    #[must_use]
+    pub fn diff(&self, other: &CpuInfo) -> Vec<FieldDiff> {
+        let mut diffs = Vec::new();
+        let mut compare = |field: &'static str, left: String, right: String| {
+            if left != right {
+                diffs.push(FieldDiff::Value { field, left, right });
+            }
+        };
+
+        compare("Vendor", self.vendor.to_string(), other.vendor.to_string());
+        compare("Model", self.brand_string.clone(), other.brand_string.clone());
+        compare(
+            "Microarchitecture",
+            display_or_unknown(self.microarch.as_ref()),
+            display_or_unknown(other.microarch.as_ref()),
+        );
+        compare(
+            "Physical Cores",
+            self.physical_cores.to_string(),
+            other.physical_cores.to_string(),
+        );
+        compare(
+            "Logical Cores",
+            self.logical_cores.to_string(),
+            other.logical_cores.to_string(),
+        );
+
+        let cache_labels = ["L1i Cache", "L1d Cache", "L2 Cache", "L3 Cache"];
+        let sizes = |info: &CpuInfo| [info.l1i(), info.l1d(), info.l2(), info.l3()];
+        for ((label, before), after) in cache_labels.into_iter().zip(sizes(self)).zip(sizes(other)) {
+            let kb = |size: Option<u32>| display_or_unknown(size.map(|kb| format!("{kb} KB")).as_ref());
+            compare(label, kb(before), kb(after));
+        }
+
+        let mhz = |value: Option<f64>| display_or_unknown(value.map(|mhz| format!("{mhz:.0} MHz")).as_ref());
+        compare("Base Frequency", mhz(self.frequency.base), mhz(other.frequency.base));
+        compare("Max Frequency", mhz(self.frequency.max), mhz(other.frequency.max));
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+        {
+            let changed = self.features.symmetric_difference(other.features);
+            if !changed.is_empty() {
+                let names = |flags: &_| {
+                    changed
+                        .intersection(*flags)
+                        .iter_names()
+                        .map(|(name, _)| name)
+                        .collect()
+                };
+                diffs.push(FieldDiff::Features {
+                    gained: names(&other.features),
+                    lost: names(&self.features),
+                });
+            }
+        }
+
+        diffs
+    }
+}
+
+/// Two detections are equal when they describe the same CPU.
+///
+/// Compared: the vendor, brand string, family/model/stepping, physical and
+/// logical core counts, cache sizes, base and maximum frequency, and feature
+/// flags. Everything else is ignored, notably the current and average clocks,
+/// temperature and power, which differ between two runs on one machine, and
+/// fields derived from the compared ones, such as the microarchitecture.
+impl PartialEq for CpuInfo {
+    fn eq(&self, other: &Self) -> bool {
+        let version = |info: &CpuInfo| (info.version.family, info.version.model, info.version.stepping);
+
+        #[cfg_attr(
+            not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")),
+            allow(unused_mut)
+        )]
+        let mut same = self.vendor == other.vendor
+            && self.brand_string == other.brand_string
+            && version(self) == version(other)
+            && self.physical_cores == other.physical_cores
+            && self.logical_cores == other.logical_cores
+            && self.cache_sizes == other.cache_sizes
+            && self.frequency.base == other.frequency.base
+            && self.frequency.max == other.frequency.max;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+        {
+            same &= self.features == other.features;
+        }
+        same
+    }
+}
+
+fn display_or_unknown<T: std::fmt::Display>(value: Option<&T>) -> String {
+    value.map_or_else(|| "Unknown".to_string(), ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Vendor;
+
+    #[test]
+    fn test_diff_identical_is_empty() {
+        let info = CpuInfo {
+            vendor: Vendor::AMD,
+            physical_cores: 16,
+            ..CpuInfo::default()
+        };
+        assert!(info.diff(&info.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_eq_ignores_current_frequency() {
+        use crate::cpu::Frequency;
+
+        let snapshot = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD Ryzen 9 5950X 16-Core Processor".to_string(),
+            physical_cores: 16,
+            logical_cores: 32,
+            cache_sizes: [Some(32), Some(32), Some(512), Some(32768)],
+            frequency: Frequency::new(Some(3400.0), Some(4900.0), Some(3400.0)),
+            ..CpuInfo::default()
+        };
+        let later = CpuInfo {
+            frequency: Frequency {
+                average: Some(2875.0),
+                ..Frequency::new(Some(3400.0), Some(4900.0), Some(4650.0))
+            },
+            temperature_celsius: Some(61.5),
+            ..snapshot.clone()
+        };
+        assert_eq!(snapshot, later);
+
+        let other_part = CpuInfo {
+            logical_cores: 16,
+            ..later.clone()
+        };
+        assert_ne!(snapshot, other_part);
+        let other_max = CpuInfo {
+            frequency: Frequency::new(Some(3400.0), Some(5000.0), Some(4650.0)),
+            ..later
+        };
+        assert_ne!(snapshot, other_max);
+    }
+
+    #[test]
+    fn test_diff_reports_changed_fields() {
+        let left = CpuInfo {
+            vendor: Vendor::Intel,
+            physical_cores: 8,
+            cache_sizes: [Some(32), Some(48), Some(1280), None],
+            ..CpuInfo::default()
+        };
+        let right = CpuInfo {
+            vendor: Vendor::Intel,
+            physical_cores: 16,
+            cache_sizes: [Some(32), Some(48), Some(1280), Some(30720)],
+            ..CpuInfo::default()
+        };
+
+        assert_eq!(
+            left.diff(&right),
+            [
+                FieldDiff::Value {
+                    field: "Physical Cores",
+                    left: "8".to_string(),
+                    right: "16".to_string(),
+                },
+                FieldDiff::Value {
+                    field: "L3 Cache",
+                    left: "Unknown".to_string(),
+                    right: "30720 KB".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_diff_features_gained_and_lost() {
+        use crate::cpu::X86Features;
+
+        let left = CpuInfo {
+            features: X86Features::SSE2 | X86Features::AVX2 | X86Features::AVX512F,
+            ..CpuInfo::default()
+        };
+        let right = CpuInfo {
+            features: X86Features::SSE2 | X86Features::AVX2 | X86Features::SHA | X86Features::GFNI,
+            ..CpuInfo::default()
+        };
+
+        assert_eq!(
+            left.diff(&right),
+            [FieldDiff::Features {
+                gained: vec!["SHA", "GFNI"],
+                lost: vec!["AVX512F"],
+            }]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Named fields of [`CpuInfo`] for `cpufetch --fields`.
+//!
+//! Scripts and monitoring integrations ask for fields by a stable short key
+//! (`vendor`, `cores`, `l3`, ...) instead of parsing the human-readable
+//! output. Any feature flag name (`avx2`, `neon`) is also a key, whose value
+//! says whether the CPU has that feature.
+
+use crate::Error;
+use crate::cpu::CpuInfo;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::fmt;
+
+/// The value of one selected field
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum FieldValue {
+    /// A textual value such as the vendor or brand string
+    Text(String),
+    /// A count, ID or size in KB
+    Integer(u64),
+    /// A measurement such as a frequency in MHz
+    Number(f64),
+    /// A yes/no value such as whether a feature is present
+    Bool(bool),
+    /// The value was not detected; `null` in JSON, empty in `key=value` output
+    Missing,
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Text(text) => write!(f, "{text}"),
+            FieldValue::Integer(number) => write!(f, "{number}"),
+            FieldValue::Number(number) => write!(f, "{number}"),
+            FieldValue::Bool(value) => write!(f, "{value}"),
+            FieldValue::Missing => Ok(()),
+        }
+    }
+}
+
+impl From<Option<f64>> for FieldValue {
+    fn from(value: Option<f64>) -> Self {
+        value.map_or(FieldValue::Missing, FieldValue::Number)
+    }
+}
+
+impl From<Option<u32>> for FieldValue {
+    fn from(value: Option<u32>) -> Self {
+        value.map_or(FieldValue::Missing, |number| FieldValue::Integer(u64::from(number)))
+    }
+}
+
+impl From<Option<String>> for FieldValue {
+    fn from(value: Option<String>) -> Self {
+        value.map_or(FieldValue::Missing, FieldValue::Text)
+    }
+}
+
+/// Reads one field out of a [`CpuInfo`]
+type Extractor = fn(&CpuInfo) -> FieldValue;
+
+/// Fields selectable by key, in the order error messages list them
+const FIELDS: &[(&str, Extractor)] = &[
+    ("vendor", |info| FieldValue::Text(info.vendor.to_string())),
+    ("brand", |info| FieldValue::Text(info.brand_string.clone())),
+    ("microarch", |info| {
+        info.microarch.as_ref().map(ToString::to_string).into()
+    }),
+    ("process_node", |info| info.process_node.clone().into()),
+    ("family", |info| Some(u32::from(info.version.family)).into()),
+    ("model", |info| Some(u32::from(info.version.model)).into()),
+    ("stepping", |info| Some(u32::from(info.version.stepping)).into()),
+    ("microcode", |info| {
+        info.microcode.map_or(FieldValue::Missing, FieldValue::Integer)
+    }),
+    ("governor", |info| info.governor.clone().into()),
+    ("epp", |info| info.epp.clone().into()),
+    ("cores", |info| Some(info.physical_cores).into()),
+    ("threads", |info| Some(info.logical_cores).into()),
+    ("sockets", |info| info.sockets.into()),
+    ("base_mhz", |info| info.frequency.base.into()),
+    ("max_mhz", |info| info.frequency.max.into()),
+    ("current_mhz", |info| info.frequency.current.into()),
+    ("tsc_mhz", |info| info.tsc_frequency_mhz.into()),
+    ("invariant_tsc", |info| FieldValue::Bool(info.invariant_tsc)),
+    ("l1i", |info| info.l1i().into()),
+    ("l1d", |info| info.l1d().into()),
+    ("l2", |info| info.l2().into()),
+    ("l3", |info| info.l3().into()),
+    ("l4", |info| info.l4().into()),
+    ("cache_line", |info| info.cache_line_size().map(u32::from).into()),
+    ("physical_address_bits", |info| {
+        info.physical_address_bits.map(u32::from).into()
+    }),
+    ("virtual_address_bits", |info| {
+        info.virtual_address_bits.map(u32::from).into()
+    }),
+    ("endianness", |info| FieldValue::Text(info.endianness.to_string())),
+    ("hypervisor", |info| info.hypervisor.clone().into()),
+    ("virtualized", |info| FieldValue::Bool(info.is_virtualized)),
+    ("peak_gflops", |info| info.peak_flops.into()),
+    ("temperature", |info| info.temperature_celsius.map(f64::from).into()),
+    ("power_watts", |info| info.power_watts.map(f64::from).into()),
+];
+
+/// Keys accepted by [`CpuInfo::select_fields`] besides feature flag names.
+#[must_use]
+pub fn field_names() -> Vec<&'static str> {
+    FIELDS.iter().map(|(name, _)| *name).collect()
+}
+
+impl CpuInfo {
+    /// Values of the comma-separated field keys in `list`, in the order requested.
+    ///
+    /// Keys are case-insensitive. A key that is not a named field is looked up
+    /// as a feature flag, e.g. `avx2`, and yields whether the CPU has it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnknownField` for the first key that is neither a field
+    /// nor a feature of this architecture.
+    pub fn select_fields(&self, list: &str) -> Result<Vec<(String, FieldValue)>, Error> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| {
+                let key = key.to_ascii_lowercase();
+                let value = FIELDS
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, extract)| extract(self))
+                    .or_else(|| self.feature_field(&key))
+                    .ok_or_else(|| Error::UnknownField(key.clone(), field_names().join(", ")))?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+
+    /// Every named field (see [`field_names`]) with its value, in registry order.
+    #[must_use]
+    pub fn named_fields(&self) -> Vec<(&'static str, FieldValue)> {
+        FIELDS.iter().map(|(name, extract)| (*name, extract(self))).collect()
+    }
+
+    /// Whether the CPU has the feature named `name`, or `None` if no feature has that name.
+    fn feature_field(&self, name: &str) -> Option<FieldValue> {
+        self.has_feature_named(name).map(FieldValue::Bool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Vendor;
+
+    fn ryzen() -> CpuInfo {
+        CpuInfo {
+            vendor: Vendor::AMD,
+            physical_cores: 16,
+            logical_cores: 32,
+            cache_sizes: [Some(32), Some(32), Some(512), Some(32768)],
+            ..CpuInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_select_fields_in_requested_order() {
+        let fields = ryzen().select_fields("l3, Vendor,cores,sockets").unwrap();
+        assert_eq!(
+            fields,
+            [
+                ("l3".to_string(), FieldValue::Integer(32768)),
+                ("vendor".to_string(), FieldValue::Text("AMD".to_string())),
+                ("cores".to_string(), FieldValue::Integer(16)),
+                ("sockets".to_string(), FieldValue::Missing),
+            ]
+        );
+        assert_eq!(fields[2].1.to_string(), "16");
+        assert_eq!(fields[3].1.to_string(), "");
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_select_feature_fields() {
+        use crate::cpu::X86Features;
+
+        let info = CpuInfo {
+            features: X86Features::AVX2,
+            ..ryzen()
+        };
+        let fields = info.select_fields("avx2,avx512f").unwrap();
+        assert_eq!(fields[0].1, FieldValue::Bool(true));
+        assert_eq!(fields[1].1, FieldValue::Bool(false));
+    }
+
+    #[test]
+    fn test_unknown_field_lists_valid_keys() {
+        let err = ryzen().select_fields("vendor,warp_drive").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("warp_drive"), "{message}");
+        assert!(message.contains("vendor, brand, microarch"), "{message}");
+    }
+}
@@ -5,7 +5,14 @@
 //! and falls back to static detection where necessary.
 
 use bitflags::bitflags;
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde::de::{self, SeqAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::marker::PhantomData;
 
 /// Error types specific to CPU feature detection
 #[derive(Debug, thiserror::Error)]
@@ -14,11 +21,15 @@ pub enum FeatureError {
     UnsupportedArch,
     #[error("Failed to detect feature {0}")]
     DetectionFailed(String),
+    #[error("Unknown feature name '{0}'")]
+    UnknownFeature(String),
 }
 
 bitflags! {
     /// CPU features for x86/x86_64 architectures
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    ///
+    /// Serialised as an array of flag names, e.g. `["SSE2", "AVX2"]`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct X86Features: u64 {
         const SSE = 1 << 0;
         const SSE2 = 1 << 1;
@@ -39,12 +50,16 @@ bitflags! {
         const AVX512CD = 1 << 16;
         const AVX512DQ = 1 << 17;
         const AVX512VL = 1 << 18;
+        const SHA = 1 << 19;
+        const GFNI = 1 << 20;
     }
 }
 
 bitflags! {
     /// CPU features for ARM architectures
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    ///
+    /// Serialised as an array of flag names, e.g. `["NEON", "AES"]`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct ArmFeatures: u64 {
         const NEON = 1 << 0;
         const AES = 1 << 1;
@@ -59,6 +74,210 @@ bitflags! {
         const ASIMDHP = 1 << 10;
         const ASIMDDP = 1 << 11;
         const ASIMDFHM = 1 << 12;
+        const SVE = 1 << 13;
+        const SVE2 = 1 << 14;
+    }
+}
+
+impl X86Features {
+    /// Flags named in `names`, matched case-insensitively with punctuation ignored,
+    /// so `sse4.1`, `SSE4_1` and `avx-512f` all resolve.
+    ///
+    /// Unknown names are skipped; use [`parse_features`] to reject them instead.
+    #[must_use]
+    pub fn from_names(names: &[&str]) -> Self {
+        names.iter().filter_map(|name| flag_named(name)).collect()
+    }
+
+    /// Flags the CPU advertises in `raw` that runtime detection did not find usable.
+    ///
+    /// `raw` comes from [`crate::cpu::CpuidWrapper::get_raw_features`] and `usable`
+    /// from [`detect_features`]; a non-empty result usually means the OS or a
+    /// hypervisor has masked AVX or AVX-512 state in XCR0.
+    #[must_use]
+    pub fn disabled_by_os(raw: Self, usable: Self) -> Self {
+        raw.difference(usable)
+    }
+}
+
+impl ArmFeatures {
+    /// Flags named in `names`, matched like [`X86Features::from_names`].
+    #[must_use]
+    pub fn from_names(names: &[&str]) -> Self {
+        names.iter().filter_map(|name| flag_named(name)).collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for X86Features {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_names(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for X86Features {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_names(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ArmFeatures {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_names(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ArmFeatures {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_names(deserializer)
+    }
+}
+
+/// Serialise flags as the array of their names, in declaration order.
+#[cfg(feature = "serde")]
+fn serialize_names<F: bitflags::Flags, S: Serializer>(flags: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(flags.iter_names().map(|(name, _)| name))
+}
+
+/// Read flags from an array of names (matched like [`parse_features`]), or from
+/// the `"SSE2 | AVX2"` text that schema version 1 snapshots contain.
+#[cfg(feature = "serde")]
+fn deserialize_names<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+where
+    F: bitflags::Flags + Copy,
+    F::Bits: bitflags::parser::ParseHex,
+    D: Deserializer<'de>,
+{
+    struct NamesVisitor<F>(PhantomData<F>);
+
+    impl<'de, F> Visitor<'de> for NamesVisitor<F>
+    where
+        F: bitflags::Flags + Copy,
+        F::Bits: bitflags::parser::ParseHex,
+    {
+        type Value = F;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an array of feature names")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<F, A::Error> {
+            let mut flags = F::empty();
+            while let Some(name) = seq.next_element::<String>()? {
+                let flag =
+                    flag_named::<F>(&name).ok_or_else(|| de::Error::custom(FeatureError::UnknownFeature(name)))?;
+                flags = flags.union(flag);
+            }
+            Ok(flags)
+        }
+
+        fn visit_str<E: de::Error>(self, text: &str) -> Result<F, E> {
+            bitflags::parser::from_str(text).map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(NamesVisitor(PhantomData))
+}
+
+/// The flag whose name matches `name` ignoring case and non-alphanumeric characters.
+fn flag_named<F: bitflags::Flags + Copy>(name: &str) -> Option<F> {
+    let normalise = |name: &str| -> String {
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_uppercase())
+            .collect()
+    };
+    let wanted = normalise(name);
+    F::FLAGS
+        .iter()
+        .find(|flag| normalise(flag.name()) == wanted)
+        .map(|flag| *flag.value())
+}
+
+/// Split a comma-separated list such as "SSE2,AVX2,FMA" into flags, in any case.
+#[cfg_attr(
+    not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")),
+    allow(dead_code)
+)]
+fn parse_flag_list<F: bitflags::Flags + Copy>(list: &str) -> Result<F, FeatureError> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .try_fold(F::empty(), |flags, name| {
+            let flag = flag_named::<F>(name).ok_or_else(|| FeatureError::UnknownFeature(name.to_string()))?;
+            Ok(flags.union(flag))
+        })
+}
+
+/// Parse a comma-separated feature list such as "SSE2,AVX2,FMA" for this architecture.
+///
+/// # Errors
+///
+/// Returns `FeatureError::UnknownFeature` for the first name that is not an x86 feature.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn parse_features(list: &str) -> Result<X86Features, FeatureError> {
+    parse_flag_list(list)
+}
+
+/// Parse a comma-separated feature list such as "NEON,AES" for this architecture.
+///
+/// # Errors
+///
+/// Returns `FeatureError::UnknownFeature` for the first name that is not an ARM feature.
+#[cfg(target_arch = "aarch64")]
+pub fn parse_features(list: &str) -> Result<ArmFeatures, FeatureError> {
+    parse_flag_list(list)
+}
+
+/// Feature lists cannot be parsed on architectures without a feature set.
+///
+/// # Errors
+///
+/// Always returns `FeatureError::UnsupportedArch`.
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+pub fn parse_features(_list: &str) -> Result<(), FeatureError> {
+    Err(FeatureError::UnsupportedArch)
+}
+
+/// The single flag named `name` for this architecture, matched like [`parse_features`].
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) fn feature_named(name: &str) -> Option<X86Features> {
+    flag_named(name)
+}
+
+/// The single flag named `name` for this architecture, matched like [`parse_features`].
+#[cfg(target_arch = "aarch64")]
+pub(crate) fn feature_named(name: &str) -> Option<ArmFeatures> {
+    flag_named(name)
+}
+
+/// Names of the feature flags known on this architecture, as accepted by [`parse_features`].
+///
+/// Empty on architectures without a feature set.
+#[must_use]
+pub fn feature_names() -> Vec<&'static str> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        <X86Features as bitflags::Flags>::FLAGS
+            .iter()
+            .map(bitflags::Flag::name)
+            .collect()
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        <ArmFeatures as bitflags::Flags>::FLAGS
+            .iter()
+            .map(bitflags::Flag::name)
+            .collect()
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        Vec::new()
     }
 }
 
@@ -130,6 +349,12 @@ pub fn detect_features() -> Result<X86Features, FeatureError> {
         if std::is_x86_feature_detected!("avx512vl") {
             features |= X86Features::AVX512VL;
         }
+        if std::is_x86_feature_detected!("sha") {
+            features |= X86Features::SHA;
+        }
+        if std::is_x86_feature_detected!("gfni") {
+            features |= X86Features::GFNI;
+        }
     }
 
     Ok(features)
@@ -170,6 +395,12 @@ pub fn detect_features() -> Result<ArmFeatures, FeatureError> {
         if std::arch::is_aarch64_feature_detected!("asimd") {
             features |= ArmFeatures::ASIMD;
         }
+        if std::arch::is_aarch64_feature_detected!("sve") {
+            features |= ArmFeatures::SVE;
+        }
+        if std::arch::is_aarch64_feature_detected!("sve2") {
+            features |= ArmFeatures::SVE2;
+        }
         // Note: Some features might not be available for detection in all environments
     }
 
@@ -194,6 +425,93 @@ mod tests {
         assert!(!features.contains(X86Features::AVX));
     }
 
+    #[test]
+    fn test_x86_sha_gfni_flags() {
+        let features = X86Features::SHA | X86Features::AES;
+        assert!(features.contains(X86Features::SHA));
+        assert!(!features.contains(X86Features::GFNI));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_x86_sha_gfni_serde_roundtrip() {
+        let features = X86Features::SHA | X86Features::GFNI | X86Features::SSE2;
+        let json = serde_json::to_string(&features).unwrap();
+        assert_eq!(json, r#"["SSE2","SHA","GFNI"]"#);
+        assert_eq!(serde_json::from_str::<X86Features>(&json).unwrap(), features);
+
+        let empty = serde_json::to_string(&X86Features::empty()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<X86Features>(&empty).unwrap(),
+            X86Features::empty()
+        );
+
+        // Schema version 1 wrote the flags as text
+        assert_eq!(
+            serde_json::from_str::<X86Features>(r#""SSE2 | SHA | GFNI""#).unwrap(),
+            features
+        );
+        assert!(serde_json::from_str::<X86Features>(r#"["SSE2","WARP"]"#).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_arm_serde_roundtrip() {
+        let features = ArmFeatures::NEON | ArmFeatures::AES | ArmFeatures::SVE2;
+        let json = serde_json::to_string(&features).unwrap();
+        assert_eq!(json, r#"["NEON","AES","SVE2"]"#);
+        assert_eq!(serde_json::from_str::<ArmFeatures>(&json).unwrap(), features);
+        assert_eq!(
+            serde_json::from_str::<ArmFeatures>(r#"["neon"]"#).unwrap(),
+            ArmFeatures::NEON
+        );
+    }
+
+    #[test]
+    fn test_x86_from_names() {
+        assert_eq!(
+            X86Features::from_names(&["sse4.1", "SSE4_2", "avx-512f", "Fma", "bogus"]),
+            X86Features::SSE4_1 | X86Features::SSE4_2 | X86Features::AVX512F | X86Features::FMA
+        );
+        assert_eq!(X86Features::from_names(&[]), X86Features::empty());
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_parse_features() {
+        assert_eq!(
+            parse_features("SSE2,avx2, fma").unwrap(),
+            X86Features::SSE2 | X86Features::AVX2 | X86Features::FMA
+        );
+        assert_eq!(parse_features("").unwrap(), X86Features::empty());
+        assert!(matches!(
+            parse_features("avx2,avx1024"),
+            Err(FeatureError::UnknownFeature(name)) if name == "avx1024"
+        ));
+    }
+
+    #[test]
+    fn test_x86_disabled_by_os() {
+        // A VM that exposes the AVX-512 CPUID bits but leaves ZMM state off in XCR0
+        let raw = X86Features::SSE2 | X86Features::AVX2 | X86Features::AVX512F | X86Features::AVX512BW;
+        let usable = X86Features::SSE2 | X86Features::AVX2;
+        assert_eq!(
+            X86Features::disabled_by_os(raw, usable),
+            X86Features::AVX512F | X86Features::AVX512BW
+        );
+        assert!(X86Features::disabled_by_os(usable, usable).is_empty());
+        // Runtime-only detections are not reported as disabled
+        assert!(X86Features::disabled_by_os(X86Features::SSE2, usable).is_empty());
+    }
+
+    #[test]
+    fn test_arm_from_names() {
+        assert_eq!(
+            ArmFeatures::from_names(&["neon", "sha2"]),
+            ArmFeatures::NEON | ArmFeatures::SHA2
+        );
+    }
+
     #[test]
     fn test_arm_features_flags() {
         let features = ArmFeatures::NEON | ArmFeatures::AES;
@@ -202,6 +520,19 @@ mod tests {
         assert!(!features.contains(ArmFeatures::SHA2));
     }
 
+    #[test]
+    fn test_arm_sve_flags() {
+        // A Graviton3-like feature set, then the same part with SVE masked
+        let mut features = ArmFeatures::NEON | ArmFeatures::SVE | ArmFeatures::SVE2;
+        assert!(features.contains(ArmFeatures::SVE | ArmFeatures::SVE2));
+        features.remove(ArmFeatures::SVE | ArmFeatures::SVE2);
+        assert!(!features.intersects(ArmFeatures::SVE | ArmFeatures::SVE2));
+        assert_eq!(
+            ArmFeatures::from_names(&["sve", "SVE2"]),
+            ArmFeatures::SVE | ArmFeatures::SVE2
+        );
+    }
+
     // Note: We can't reliably test actual feature detection in unit tests
     // as it depends on the CPU capabilities of the test machine
 }
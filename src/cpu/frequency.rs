@@ -1,38 +1,136 @@
+//! CPU frequency detection.
+//!
+//! Platform-specific backends populate the shared [`Frequency`] type from
+//! `cpu::info`, so detection results can be stored in `CpuInfo` without any
+//! conversion step.
+
 use crate::Error;
-use std::fmt;
-/// CPU frequency information
-#[derive(Debug, Clone, PartialEq, Default)]
-pub struct Frequency {
-    /// Base frequency in MHz
-    pub base: Option<f64>,
-    /// Current frequency in MHz
-    pub current: Option<f64>,
-    /// Maximum frequency in MHz (Turbo/Boost)
-    pub max: Option<f64>,
-}
-
-impl fmt::Display for Frequency {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let base = self
-            .base
-            .map_or_else(|| "Unknown".to_string(), |v| format!("{v:.2} MHz"));
-        let current = self
-            .current
-            .map_or_else(|| "Unknown".to_string(), |v| format!("{v:.2} MHz"));
-        let max = self
-            .max
-            .map_or_else(|| "Unknown".to_string(), |v| format!("{v:.2} MHz"));
-
-        write!(f, "Base: {base}, Current: {current}, Max: {max}")
-    }
-}
+use crate::cpu::info::ClusterFrequency;
+pub use crate::cpu::info::Frequency;
+use crate::cpu::uarch::Microarch;
+
+/// Longest time to wait for the WMI processor query on Windows.
+///
+/// COM and WMI start-up can take several seconds under some group policies;
+/// past this deadline detection falls back to the registry and `sysinfo`.
+pub const WMI_QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
 
 /// Detects CPU frequency using platform-specific methods
 ///
+/// On x86, the base and maximum frequencies from CPUID leaf 0x16 take
+/// precedence because they come straight from the processor; the OS sources
+/// supply the current frequency and fill in whatever the leaf leaves out.
+/// On Windows those sources are, in order: the registry's `~MHz` value, WMI,
+/// then `sysinfo`. WMI is abandoned after [`WMI_QUERY_TIMEOUT`], in which case
+/// the current clock comes from `sysinfo` and the maximum may be missing or
+/// estimated, so the result is less precise than a completed query.
+///
 /// # Errors
 ///
 /// Returns an error if frequency detection fails on the current platform.
 pub fn detect_frequency() -> Result<Frequency, Error> {
+    #[cfg_attr(not(any(target_arch = "x86", target_arch = "x86_64")), allow(unused_mut))]
+    let mut frequency = detect_frequency_platform()?;
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if let Some(info) = crate::cpu::CpuidWrapper::new().get_frequency_info() {
+        debug_log!("Using CPUID leaf 0x16 for base/max frequency");
+        frequency.base = info.base_mhz.map(f64::from).or(frequency.base);
+        frequency.max = info.max_mhz.map(f64::from).or(frequency.max);
+    }
+
+    Ok(frequency)
+}
+
+/// Repeatedly samples the current CPU frequency without redoing full detection
+///
+/// Base and maximum frequencies are fixed properties of the part, so they are
+/// detected once by [`FrequencyMonitor::new`]. Each [`FrequencyMonitor::refresh`]
+/// then only re-reads the core clocks, through cpufreq sysfs files kept open
+/// on Linux or a persistent `sysinfo::System` elsewhere.
+#[derive(Debug)]
+pub struct FrequencyMonitor {
+    base: Option<f64>,
+    max: Option<f64>,
+    #[cfg(target_os = "linux")]
+    scaling_cur_freq: Vec<std::fs::File>,
+    system: sysinfo::System,
+}
+
+impl FrequencyMonitor {
+    /// Detect the fixed frequencies and open the handles used by [`FrequencyMonitor::refresh`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if frequency detection fails on the current platform.
+    pub fn new() -> Result<Self, Error> {
+        let initial = detect_frequency()?;
+        Ok(Self {
+            base: initial.base,
+            max: initial.max,
+            #[cfg(target_os = "linux")]
+            scaling_cur_freq: cpufreq_dirs(std::path::Path::new(SYSFS_CPU))
+                .into_iter()
+                .filter_map(|(_, dir)| std::fs::File::open(dir.join("scaling_cur_freq")).ok())
+                .collect(),
+            system: sysinfo::System::new(),
+        })
+    }
+
+    /// Sample the core clocks, returning the highest and average alongside the cached base and maximum.
+    pub fn refresh(&mut self) -> Frequency {
+        let (current, average) = core_clock_summary(&self.core_clocks()).unzip();
+        Frequency {
+            average,
+            ..Frequency::new(self.base, self.max, current)
+        }
+    }
+
+    fn core_clocks(&mut self) -> Vec<f64> {
+        #[cfg(target_os = "linux")]
+        {
+            use std::io::{Read, Seek};
+
+            // sysfs regenerates the value on every read from offset zero
+            let clocks: Vec<f64> = self
+                .scaling_cur_freq
+                .iter_mut()
+                .filter_map(|file| {
+                    let mut content = String::new();
+                    file.rewind().ok()?;
+                    file.read_to_string(&mut content).ok()?;
+                    parse_khz(&content)
+                })
+                .collect();
+            if !clocks.is_empty() {
+                return clocks;
+            }
+        }
+
+        self.system
+            .refresh_cpu_specifics(sysinfo::CpuRefreshKind::nothing().with_frequency());
+        #[allow(clippy::cast_precision_loss)]
+        self.system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.frequency() as f64)
+            .filter(|mhz| *mhz > 0.0)
+            .collect()
+    }
+}
+
+/// The highest and the average of a set of per-core clocks, or `None` when there are none.
+fn core_clock_summary(clocks: &[f64]) -> Option<(f64, f64)> {
+    let highest = clocks.iter().copied().reduce(f64::max)?;
+    #[allow(clippy::cast_precision_loss)]
+    let average = clocks.iter().sum::<f64>() / clocks.len() as f64;
+    Some((highest, average))
+}
+
+/// Dispatch to the OS-specific frequency backend.
+// Only the Windows backend can fail; the Result keeps the dispatch uniform.
+#[allow(clippy::unnecessary_wraps)]
+fn detect_frequency_platform() -> Result<Frequency, Error> {
     #[cfg(feature = "frequency")]
     {
         // Platform-specific implementations
@@ -45,8 +143,20 @@ pub fn detect_frequency() -> Result<Frequency, Error> {
         #[cfg(target_os = "macos")]
         return detect_frequency_macos();
 
+        #[cfg(target_os = "freebsd")]
+        return Ok(detect_frequency_freebsd());
+
+        #[cfg(target_os = "openbsd")]
+        return Ok(detect_frequency_openbsd());
+
         // Generic fallback using sysinfo
-        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "windows",
+            target_os = "macos",
+            target_os = "freebsd",
+            target_os = "openbsd"
+        )))]
         return Ok(detect_frequency_generic());
     }
 
@@ -57,6 +167,257 @@ pub fn detect_frequency() -> Result<Frequency, Error> {
     }
 }
 
+/// Dependent multiplies timed per batch by [`estimate_frequency_by_benchmark`]
+#[cfg(target_arch = "x86_64")]
+const BENCHMARK_BATCH: u32 = 250_000;
+
+/// Latency in cycles of a dependent 64-bit `imul` on `microarch`, or `None` when it is not known here
+///
+/// Three cycles on Intel cores since Nehalem and on Zen, but four on K8 and
+/// K10 and six on the Bulldozer family; Atom-class and Pentium 4 cores differ again.
+#[cfg(target_arch = "x86_64")]
+fn imul_latency_cycles(microarch: &Microarch) -> Option<f64> {
+    match microarch {
+        Microarch::Nehalem
+        | Microarch::Westmere
+        | Microarch::SandyBridge
+        | Microarch::IvyBridge
+        | Microarch::Haswell
+        | Microarch::Broadwell
+        | Microarch::Skylake
+        | Microarch::KabyLake
+        | Microarch::CometLake
+        | Microarch::CannonLake
+        | Microarch::IceLake
+        | Microarch::TigerLake
+        | Microarch::AlderLake
+        | Microarch::RaptorLake
+        | Microarch::MeteorLake
+        | Microarch::SapphireRapids
+        | Microarch::GraniteRapids
+        | Microarch::Zen
+        | Microarch::ZenPlus
+        | Microarch::Zen2
+        | Microarch::Hygon
+        | Microarch::Zen3
+        | Microarch::Zen3Plus
+        | Microarch::Zen4
+        | Microarch::Zen5 => Some(3.0),
+        Microarch::K8 | Microarch::K10 => Some(4.0),
+        Microarch::Bulldozer | Microarch::Piledriver | Microarch::Steamroller | Microarch::Excavator => Some(6.0),
+        _ => None,
+    }
+}
+
+/// How long [`estimate_frequency_by_benchmark`] keeps the core busy
+#[cfg(target_arch = "x86_64")]
+const BENCHMARK_DURATION: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// Estimates the current core clock in MHz by timing a busy loop
+///
+/// For when no OS frequency source is available, as in some containers. A
+/// chain of dependent 64-bit multiplies, whose latency is a fixed number of
+/// cycles, runs in batches for about 10 ms against the wall clock; the
+/// fastest batch gives the estimate, so brief preemptions are discarded.
+///
+/// This is an estimate, not a reading. It measures the clock of whichever
+/// core the thread ran on while that core was fully loaded, so it tends
+/// toward the turbo clock and ignores other cores. An SMT sibling competing
+/// for the multiplier, a hypervisor stealing time for the whole run, or an
+/// unoptimised build read low; expect a few percent of error at best. The
+/// loop keeps one core saturated while it runs, so it is never part of
+/// normal detection.
+///
+/// The cycle count per multiply depends on the core, so `microarch` picks
+/// it. Returns `None` when the microarchitecture is unknown or its multiply
+/// latency is not listed, and on architectures other than `x86_64`.
+#[must_use]
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+pub fn estimate_frequency_by_benchmark(microarch: Option<&Microarch>) -> Option<f64> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::hint::black_box;
+        use std::time::{Duration, Instant};
+
+        let latency = imul_latency_cycles(microarch?)?;
+
+        let started = Instant::now();
+        let mut fastest = Duration::MAX;
+        while started.elapsed() < BENCHMARK_DURATION {
+            let batch = Instant::now();
+            black_box(multiply_chain(black_box(3), black_box(BENCHMARK_BATCH)));
+            fastest = fastest.min(batch.elapsed());
+        }
+
+        let cycles = f64::from(BENCHMARK_BATCH) * latency;
+        let mhz = cycles / fastest.as_secs_f64() / 1_000_000.0;
+        debug_log!("Busy-loop estimate: {mhz:.0} MHz (fastest batch {fastest:?})");
+        Some(mhz)
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        None
+    }
+}
+
+/// `count` multiplies, each waiting on the previous result so they cannot overlap.
+///
+/// Repeated squaring, unlike multiplying by a fixed factor, cannot be
+/// reassociated by the compiler into a shorter chain. An odd seed stays odd,
+/// so the value never collapses to zero.
+#[cfg(target_arch = "x86_64")]
+fn multiply_chain(seed: u64, count: u32) -> u64 {
+    (0..count).fold(seed, |square, _| square.wrapping_mul(square))
+}
+
+/// Detects the current frequency of every logical CPU, in MHz
+///
+/// Each entry pairs the logical CPU id with its clock, ordered by id. Offline
+/// CPUs are absent rather than shifting the ids after them. On Linux the
+/// per-CPU cpufreq `scaling_cur_freq` files are read; elsewhere, or when
+/// cpufreq is absent, `sysinfo`'s per-CPU data is used.
+///
+/// # Errors
+///
+/// Returns an error if per-core frequency detection fails on the current platform.
+pub fn detect_per_core_frequencies() -> Result<Vec<(u32, f64)>, Error> {
+    #[cfg(target_os = "linux")]
+    {
+        let frequencies = detect_per_core_frequencies_linux();
+        if !frequencies.is_empty() {
+            return Ok(frequencies);
+        }
+        debug_log!("Per-CPU cpufreq sysfs unavailable, falling back to sysinfo");
+    }
+
+    Ok(detect_per_core_frequencies_generic())
+}
+
+/// Detects the clock of each cpufreq policy (frequency domain), ordered by policy number
+///
+/// On Arm chips each cluster of cores, such as the little and big cores of a
+/// big.LITTLE design, shares one policy under
+/// `/sys/devices/system/cpu/cpufreq/policyN`; x86 usually has one per CPU.
+/// Empty off Linux or when cpufreq is absent.
+#[must_use]
+pub fn detect_cluster_frequencies() -> Vec<ClusterFrequency> {
+    #[cfg(target_os = "linux")]
+    {
+        cluster_frequencies_in(&std::path::Path::new(SYSFS_CPU).join("cpufreq"))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Vec::new()
+    }
+}
+
+/// The `policyN` directories under `cpufreq` with their CPUs and clocks.
+///
+/// A policy lists its CPUs in `related_cpus` (or `affected_cpus` on older
+/// kernels); the maximum is `cpuinfo_max_freq`, falling back to the
+/// governor's `scaling_max_freq`. Policies without CPUs are skipped.
+#[cfg(target_os = "linux")]
+fn cluster_frequencies_in(cpufreq: &std::path::Path) -> Vec<ClusterFrequency> {
+    let Ok(entries) = std::fs::read_dir(cpufreq) else {
+        return Vec::new();
+    };
+    let read = |dir: &std::path::Path, file: &str| std::fs::read_to_string(dir.join(file)).ok();
+    let khz = |dir: &std::path::Path, file: &str| parse_khz(&read(dir, file)?);
+
+    let mut clusters: Vec<ClusterFrequency> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let policy = name.to_str()?.strip_prefix("policy")?.parse::<u32>().ok()?;
+            let dir = entry.path();
+
+            let list = read(&dir, "related_cpus").or_else(|| read(&dir, "affected_cpus"))?;
+            let mut cpus: Vec<u32> = list.split_whitespace().filter_map(|cpu| cpu.parse().ok()).collect();
+            cpus.sort_unstable();
+            if cpus.is_empty() {
+                return None;
+            }
+
+            Some(ClusterFrequency {
+                policy,
+                cpus,
+                current: khz(&dir, "scaling_cur_freq"),
+                max: khz(&dir, "cpuinfo_max_freq").or_else(|| khz(&dir, "scaling_max_freq")),
+            })
+        })
+        .collect();
+
+    clusters.sort_by_key(|cluster| cluster.policy);
+    clusters
+}
+
+/// Root of the per-CPU sysfs directories.
+#[cfg(target_os = "linux")]
+const SYSFS_CPU: &str = "/sys/devices/system/cpu";
+
+#[cfg(target_os = "linux")]
+fn detect_per_core_frequencies_linux() -> Vec<(u32, f64)> {
+    per_core_frequencies_in(std::path::Path::new(SYSFS_CPU))
+}
+
+/// CPU id and current clock in MHz of every CPU under `root` that has a readable `scaling_cur_freq`.
+///
+/// Offline CPUs have no cpufreq directory and are skipped, as are unreadable files.
+#[cfg(target_os = "linux")]
+fn per_core_frequencies_in(root: &std::path::Path) -> Vec<(u32, f64)> {
+    cpufreq_dirs(root)
+        .into_iter()
+        .filter_map(|(cpu, dir)| {
+            let mhz = parse_khz(&std::fs::read_to_string(dir.join("scaling_cur_freq")).ok()?)?;
+            Some((cpu, mhz))
+        })
+        .collect()
+}
+
+/// `cpuN/cpufreq` directories under `root`, ordered by CPU index.
+#[cfg(target_os = "linux")]
+fn cpufreq_dirs(root: &std::path::Path) -> Vec<(u32, std::path::PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<(u32, std::path::PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let index = name.to_str()?.strip_prefix("cpu")?.parse::<u32>().ok()?;
+            let dir = entry.path().join("cpufreq");
+            dir.is_dir().then_some((index, dir))
+        })
+        .collect();
+
+    // read_dir order is arbitrary (and "cpu10" sorts before "cpu2" anyway)
+    dirs.sort_by_key(|(index, _)| *index);
+    dirs
+}
+
+/// Parse a cpufreq value in kHz into MHz.
+#[cfg(target_os = "linux")]
+fn parse_khz(content: &str) -> Option<f64> {
+    content.trim().parse::<f64>().ok().map(|khz| khz / 1000.0)
+}
+
+/// `sysinfo` lists only the CPUs it can see, so their position stands in for the id.
+fn detect_per_core_frequencies_generic() -> Vec<(u32, f64)> {
+    use sysinfo::{CpuRefreshKind, System};
+
+    let mut system = System::new();
+    system.refresh_cpu_specifics(CpuRefreshKind::nothing().with_frequency());
+
+    #[allow(clippy::cast_precision_loss)]
+    (0..)
+        .zip(system.cpus())
+        .map(|(cpu, info)| (cpu, info.frequency() as f64))
+        .collect()
+}
+
 // Platform-specific implementations
 #[cfg(all(feature = "frequency", target_os = "linux"))]
 fn detect_frequency_linux() -> Frequency {
@@ -64,11 +425,14 @@ fn detect_frequency_linux() -> Frequency {
 
     let mut frequency = Frequency::default();
 
-    // Read current frequency from cpufreq sysfs
-    if let Ok(content) = read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/scaling_cur_freq")
-        && let Ok(khz) = content.trim().parse::<f64>()
-    {
-        frequency.current = Some(khz / 1000.0);
+    // Sample every online core: cpu0 alone may be idling while another runs at turbo
+    let clocks: Vec<f64> = detect_per_core_frequencies_linux()
+        .into_iter()
+        .map(|(_, mhz)| mhz)
+        .collect();
+    if let Some((highest, average)) = core_clock_summary(&clocks) {
+        frequency.current = Some(highest);
+        frequency.average = Some(average);
     }
 
     // Read max frequency from cpufreq sysfs
@@ -78,6 +442,16 @@ fn detect_frequency_linux() -> Frequency {
         frequency.max = Some(khz / 1000.0);
     }
 
+    // cpu0 is usually a little core on big.LITTLE parts; the maximum is that of the fastest cluster
+    #[cfg(target_arch = "aarch64")]
+    if let Some(max) = detect_cluster_frequencies()
+        .iter()
+        .filter_map(|cluster| cluster.max)
+        .reduce(f64::max)
+    {
+        frequency.max = Some(max);
+    }
+
     // Read base frequency from cpufreq sysfs (not always present)
     if let Ok(content) = read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/base_frequency")
         && let Ok(khz) = content.trim().parse::<f64>()
@@ -87,6 +461,7 @@ fn detect_frequency_linux() -> Frequency {
 
     // Fallback to sysinfo if sysfs yielded nothing
     if frequency.current.is_none() && frequency.max.is_none() && frequency.base.is_none() {
+        debug_log!("cpufreq sysfs unavailable, falling back to sysinfo");
         return detect_frequency_generic();
     }
 
@@ -108,39 +483,38 @@ fn detect_frequency_windows() -> Result<Frequency, Error> {
 
     let mut frequency = Frequency::default();
 
-    // Try WMI access first for most accurate data
-    match COMLibrary::new() {
-        Ok(com_lib) => {
-            if let Ok(wmi_con) = WMIConnection::new(com_lib) {
-                // Query WMI for processor information
-                if let Ok(processors) = wmi_con.query::<Win32_Processor>() {
-                    if let Some(processor) = processors.first() {
-                        // Current frequency
-                        if let Some(current_speed) = processor.CurrentClockSpeed {
-                            frequency.current = Some(current_speed as f64);
-                        }
-
-                        // Max frequency
-                        if let Some(max_speed) = processor.MaxClockSpeed {
-                            frequency.max = Some(max_speed as f64);
-
-                            // If max is available but base isn't, estimate base as 80% of max
-                            // This is a common rule of thumb for modern processors
-                            if frequency.base.is_none() {
-                                frequency.base = Some(max_speed as f64 * 0.8);
-                            }
-                        }
-                    }
-                }
-            }
-        },
-        Err(e) => {
-            // Log the error but continue with fallback
-            eprintln!("Failed to initialize COM library for WMI: {}", e);
+    // The registry gives the nominal clock quickly and without COM
+    #[cfg(feature = "windows")]
+    {
+        frequency.base = detect_base_frequency_registry();
+    }
+
+    // WMI provides the current and maximum clocks. COM objects cannot leave
+    // the thread that created them, so the whole query runs on its own thread.
+    let wmi_clocks = run_with_timeout(WMI_QUERY_TIMEOUT, || {
+        first_processor_clocks(
+            COMLibrary::new()
+                .and_then(WMIConnection::new)
+                .and_then(|connection| connection.query::<Win32_Processor>())
+                .map(|processors| {
+                    processors
+                        .into_iter()
+                        .map(|processor| (processor.CurrentClockSpeed, processor.MaxClockSpeed))
+                        .collect()
+                }),
+        )
+    });
+    match wmi_clocks {
+        Some(Some((current, max))) => {
+            frequency.current = current;
+            frequency.max = max;
         },
+        Some(None) => {},
+        None => debug_log!("WMI query did not finish within {WMI_QUERY_TIMEOUT:?}, falling back to sysinfo"),
     }
 
-    // Use sysinfo as a fallback if WMI failed to provide frequency information
+    // sysinfo fills in the current clock whenever WMI gave none (failed, timed
+    // out, or left it empty), whether or not it reported a maximum
     if frequency.current.is_none() {
         let mut system = System::new();
         system.refresh_cpu_specifics(CpuRefreshKind::everything());
@@ -155,9 +529,67 @@ fn detect_frequency_windows() -> Result<Frequency, Error> {
         return Ok(detect_frequency_generic());
     }
 
+    // Last resort: an *estimate* of base as 80% of max, a common ratio on
+    // modern parts. Reached whenever the registry had no base clock but WMI
+    // reported a maximum.
+    if frequency.base.is_none()
+        && let Some(max) = frequency.max
+    {
+        debug_log!("Estimating base frequency as 80% of max {max} MHz");
+        frequency.base = Some(max * 0.8);
+    }
+
     Ok(frequency)
 }
 
+/// Current and maximum clock in MHz of one `Win32_Processor`, as WMI reports them
+type ProcessorClocks = (Option<u32>, Option<u32>);
+
+/// The current and maximum clock of the first processor a WMI query returned.
+///
+/// `None` when the query failed or returned no processor. Failures are only
+/// logged: the caller's fallbacks cover them, and a library must not write to
+/// the embedding program's stderr.
+#[cfg_attr(not(all(feature = "frequency", target_os = "windows")), allow(dead_code))]
+fn first_processor_clocks<E: std::fmt::Display>(
+    processors: Result<Vec<ProcessorClocks>, E>,
+) -> Option<(Option<f64>, Option<f64>)> {
+    let processors = processors
+        .map_err(|e| debug_log!("WMI processor query failed, falling back to sysinfo: {e}"))
+        .ok()?;
+    let (current, max) = processors.first()?;
+    Some((current.map(f64::from), max.map(f64::from)))
+}
+
+/// Run `f` on a separate thread and return its result, or `None` if it is
+/// still running after `timeout`.
+///
+/// A timed-out thread is left to finish in the background; its result is discarded.
+#[cfg(any(all(feature = "frequency", target_os = "windows"), test))]
+fn run_with_timeout<T, F>(timeout: std::time::Duration, f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        // The receiver is gone once the caller has given up
+        let _ = sender.send(f());
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+/// Nominal clock from `HKLM\HARDWARE\DESCRIPTION\System\CentralProcessor\0\~MHz`.
+#[cfg(all(feature = "frequency", feature = "windows", target_os = "windows"))]
+fn detect_base_frequency_registry() -> Option<f64> {
+    let key = windows_registry::LOCAL_MACHINE
+        .open(r"HARDWARE\DESCRIPTION\System\CentralProcessor\0")
+        .ok()?;
+    let mhz = key.get_u32("~MHz").ok()?;
+    debug_log!("Registry reports ~MHz = {mhz}");
+    (mhz > 0).then(|| f64::from(mhz))
+}
+
 // Result is needed for uniformity with other platform detect_ fns called from detect_frequency.
 #[cfg(all(feature = "frequency", target_os = "macos"))]
 #[allow(clippy::unnecessary_wraps)]
@@ -202,6 +634,60 @@ fn detect_frequency_macos_inner() -> Frequency {
     frequency
 }
 
+/// FreeBSD: `dev.cpu.0.freq` is the current clock, `hw.clockrate` the clock
+/// at boot (the nominal one) and `dev.cpu.0.freq_levels` the P-states cpufreq
+/// can select, all in MHz.
+#[cfg(all(feature = "frequency", target_os = "freebsd"))]
+fn detect_frequency_freebsd() -> Frequency {
+    #[cfg(feature = "bsd")]
+    {
+        use sysctl::Sysctl;
+
+        let read = |name: &str| sysctl::Ctl::new(name).and_then(|ctl| ctl.value_string()).ok();
+        let mhz = |name: &str| read(name)?.trim().parse::<f64>().ok().filter(|mhz| *mhz > 0.0);
+
+        let frequency = Frequency::new(
+            mhz("hw.clockrate"),
+            read("dev.cpu.0.freq_levels").as_deref().and_then(highest_freq_level),
+            mhz("dev.cpu.0.freq"),
+        );
+        if frequency.base.is_some() || frequency.max.is_some() || frequency.current.is_some() {
+            return frequency;
+        }
+        debug_log!("No frequency sysctls, falling back to sysinfo");
+    }
+
+    detect_frequency_generic()
+}
+
+/// The highest clock in a FreeBSD `dev.cpu.N.freq_levels` list such as
+/// `2601/35000 2600/35000 1800/22000` (MHz/milliwatts pairs).
+#[cfg(any(target_os = "freebsd", test))]
+fn highest_freq_level(levels: &str) -> Option<f64> {
+    levels
+        .split_whitespace()
+        .filter_map(|level| level.split('/').next()?.parse::<f64>().ok())
+        .reduce(f64::max)
+}
+
+/// OpenBSD: `hw.cpuspeed` is the current clock in MHz. The `sysctl` crate does
+/// not support OpenBSD, so this asks the `sysctl` utility.
+#[cfg(all(feature = "frequency", target_os = "openbsd"))]
+fn detect_frequency_openbsd() -> Frequency {
+    let current = std::process::Command::new("sysctl")
+        .args(["-n", "hw.cpuspeed"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok()?.trim().parse::<f64>().ok())
+        .filter(|mhz| *mhz > 0.0);
+
+    match current {
+        Some(current) => Frequency::new(None, None, Some(current)),
+        None => detect_frequency_generic(),
+    }
+}
+
 #[cfg(feature = "frequency")]
 fn detect_frequency_generic() -> Frequency {
     use sysinfo::{CpuRefreshKind, System};
@@ -218,6 +704,7 @@ fn detect_frequency_generic() -> Frequency {
 
     // Try to estimate base/max if we have current frequency
     if let Some(current) = frequency.current {
+        debug_log!("Estimating base/max frequency from current {current} MHz");
         // A very simplistic estimation - not accurate but provides something
         frequency.base = Some(current * 0.9);
         frequency.max = Some(current * 1.1);
@@ -225,3 +712,182 @@ fn detect_frequency_generic() -> Frequency {
 
     frequency
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_processor_clocks() {
+        assert_eq!(
+            first_processor_clocks::<&str>(Ok(vec![(Some(3400), Some(4700)), (Some(800), None)])),
+            Some((Some(3400.0), Some(4700.0)))
+        );
+        assert_eq!(first_processor_clocks::<&str>(Ok(Vec::new())), None);
+    }
+
+    /// A failed WMI query is logged, never printed: stderr stays empty in the library path.
+    ///
+    /// libtest cannot capture a test's own stderr, so the test reruns itself
+    /// in a child process and inspects the child's. The child takes the WMI
+    /// failure branch directly (it is reachable on every platform) and then
+    /// the full detection, which on Windows queries WMI for real.
+    #[test]
+    fn test_wmi_failure_is_silent_on_stderr() {
+        const CHILD: &str = "CPUFETCH_TEST_STDERR_CHILD";
+        if std::env::var_os(CHILD).is_some() {
+            assert_eq!(
+                first_processor_clocks(Err("0x800401F0: CoInitialize has not been called")),
+                None
+            );
+            let _ = detect_frequency();
+            return;
+        }
+
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "cpu::frequency::tests::test_wmi_failure_is_silent_on_stderr",
+                "--nocapture",
+            ])
+            .env(CHILD, "1")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "child failed: {output:?}");
+        assert!(
+            output.stderr.is_empty(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_benchmark_estimate_is_plausible() {
+        let mhz = estimate_frequency_by_benchmark(Some(&Microarch::Zen3)).expect("Zen 3 latency is known");
+        assert!((100.0..=10_000.0).contains(&mhz), "{mhz} MHz");
+
+        // No guess for a core whose multiply latency is not listed
+        assert_eq!(estimate_frequency_by_benchmark(None), None);
+        assert_eq!(estimate_frequency_by_benchmark(Some(&Microarch::Prescott)), None);
+        assert_eq!(imul_latency_cycles(&Microarch::K10), Some(4.0));
+        assert_eq!(imul_latency_cycles(&Microarch::Piledriver), Some(6.0));
+    }
+
+    #[test]
+    fn test_highest_freq_level() {
+        assert_eq!(highest_freq_level("2601/35000 2600/35000 1800/22000\n"), Some(2601.0));
+        assert_eq!(highest_freq_level("800/-1"), Some(800.0));
+        assert_eq!(highest_freq_level(""), None);
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        use std::time::Duration;
+
+        assert_eq!(run_with_timeout(Duration::from_secs(5), || 42), Some(42));
+        let slow = run_with_timeout(Duration::from_millis(10), || {
+            std::thread::sleep(Duration::from_secs(1));
+            42
+        });
+        assert_eq!(slow, None);
+    }
+
+    #[test]
+    fn test_core_clock_summary() {
+        assert_eq!(core_clock_summary(&[]), None);
+        assert_eq!(
+            core_clock_summary(&[800.0, 4800.0, 2400.0, 2000.0]),
+            Some((4800.0, 2500.0))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_per_core_frequencies_from_mock_sysfs() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let root = sysfs.path();
+        let cpufreq = |cpu: &str, khz: Option<&str>| {
+            let dir = root.join(cpu).join("cpufreq");
+            std::fs::create_dir_all(&dir).unwrap();
+            if let Some(khz) = khz {
+                std::fs::write(dir.join("scaling_cur_freq"), khz).unwrap();
+            }
+        };
+        cpufreq("cpu0", Some("800000\n"));
+        cpufreq("cpu2", Some("4800000\n"));
+        cpufreq("cpu10", Some("2400000\n"));
+        // Present but without a readable value, as with a hot-unplugged core
+        cpufreq("cpu3", None);
+        // Offline cores have no cpufreq directory at all
+        std::fs::create_dir_all(root.join("cpu4")).unwrap();
+        std::fs::create_dir_all(root.join("cpuidle")).unwrap();
+
+        let cores = per_core_frequencies_in(root);
+
+        assert_eq!(cores, [(0, 800.0), (2, 4800.0), (10, 2400.0)]);
+        let clocks: Vec<f64> = cores.iter().map(|(_, mhz)| *mhz).collect();
+        assert_eq!(core_clock_summary(&clocks), Some((4800.0, 8000.0 / 3.0)));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cluster_frequencies_from_mock_sysfs() {
+        let cpufreq = tempfile::tempdir().unwrap();
+        let policy = |name: &str, files: &[(&str, &str)]| {
+            let dir = cpufreq.path().join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            for (file, value) in files {
+                std::fs::write(dir.join(file), format!("{value}\n")).unwrap();
+            }
+        };
+        // Snapdragon-style tri-cluster: four little, three big and one prime core
+        policy(
+            "policy0",
+            &[
+                ("related_cpus", "0 1 2 3"),
+                ("scaling_cur_freq", "1017600"),
+                ("cpuinfo_max_freq", "1804800"),
+            ],
+        );
+        policy(
+            "policy4",
+            &[
+                ("related_cpus", "4 5 6"),
+                ("scaling_cur_freq", "2419200"),
+                ("cpuinfo_max_freq", "2419200"),
+            ],
+        );
+        // Older kernels only have affected_cpus; no cpuinfo_max_freq either
+        policy("policy7", &[("affected_cpus", "7"), ("scaling_max_freq", "2841600")]);
+        policy("policy9", &[("related_cpus", "")]);
+        std::fs::create_dir_all(cpufreq.path().join("ondemand")).unwrap();
+
+        let clusters = cluster_frequencies_in(cpufreq.path());
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[0].cpus, [0, 1, 2, 3]);
+        assert_eq!(clusters[0].current, Some(1017.6));
+        assert_eq!(clusters[1].policy, 4);
+        assert_eq!(clusters[1].max, Some(2419.2));
+        assert_eq!((clusters[2].current, clusters[2].max), (None, Some(2841.6)));
+        assert_eq!(clusters[0].to_string(), "CPUs 0-3: 1.018 GHz (max 1.805 GHz)");
+        assert_eq!(clusters[2].to_string(), "CPUs 7: max 2.842 GHz");
+    }
+
+    #[test]
+    fn test_monitor_refresh_is_plausible() {
+        let mut monitor = FrequencyMonitor::new().expect("frequency detection should succeed");
+        let first = monitor.refresh();
+        let second = monitor.refresh();
+
+        // Base and max are cached, so they cannot drift between refreshes
+        assert_eq!(first.base, second.base);
+        assert_eq!(first.max, second.max);
+        for mhz in [first.current, second.current, second.base, second.max]
+            .into_iter()
+            .flatten()
+        {
+            assert!((1.0..20_000.0).contains(&mhz), "implausible frequency {mhz} MHz");
+        }
+    }
+}
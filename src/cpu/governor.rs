@@ -0,0 +1,72 @@
+//! Frequency scaling policy detection.
+//!
+//! Linux cpufreq exposes the active scaling governor (e.g. `performance`,
+//! `powersave`, `schedutil`) and, with the `intel_pstate` and `amd-pstate`
+//! drivers, the energy-performance preference (EPP) hint. Both are read for
+//! CPU 0; other platforms report neither.
+
+/// Directory of the cpufreq policy of the first CPU
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+const CPUFREQ_DIR: &str = "/sys/devices/system/cpu/cpu0/cpufreq";
+
+/// Detect the active cpufreq governor of the first CPU.
+///
+/// Read from `/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor`. Returns
+/// `None` on non-Linux platforms, without cpufreq (as in many VMs), or
+/// without the `std` feature.
+#[must_use]
+pub fn detect_governor() -> Option<String> {
+    read_cpufreq("scaling_governor")
+}
+
+/// Detect the energy-performance preference of the first CPU.
+///
+/// Read from `/sys/devices/system/cpu/cpu0/cpufreq/energy_performance_preference`,
+/// which only drivers with hardware-managed P-states provide. Returns `None`
+/// otherwise, on non-Linux platforms, or without the `std` feature.
+#[must_use]
+pub fn detect_epp() -> Option<String> {
+    read_cpufreq("energy_performance_preference")
+}
+
+/// Read and clean one attribute of the cpufreq policy directory.
+fn read_cpufreq(attribute: &str) -> Option<String> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        let value = std::fs::read_to_string(format!("{CPUFREQ_DIR}/{attribute}"))
+            .ok()
+            .and_then(|text| parse_sysfs_value(&text));
+        debug_log!("cpufreq {attribute}: {value:?}");
+        value
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        let _ = attribute;
+        None
+    }
+}
+
+/// A sysfs string attribute without its trailing newline, or `None` when blank.
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+fn parse_sysfs_value(text: &str) -> Option<String> {
+    let value = text.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sysfs_value() {
+        assert_eq!(parse_sysfs_value("performance\n").as_deref(), Some("performance"));
+        assert_eq!(
+            parse_sysfs_value("balance_performance\n").as_deref(),
+            Some("balance_performance")
+        );
+        assert_eq!(parse_sysfs_value("schedutil").as_deref(), Some("schedutil"));
+        assert_eq!(parse_sysfs_value("\n"), None);
+        assert_eq!(parse_sysfs_value(""), None);
+    }
+}
@@ -0,0 +1,225 @@
+//! Per-core-type caches and features on hybrid CPUs.
+//!
+//! CPUID only describes the core it happens to run on, so on a hybrid part
+//! such as Alder Lake the P-cores' private L2 and the E-cores'
+//! cluster-shared L2 cannot both be seen from one call, nor can features that
+//! only one kind of core implements. Linux sysfs lists the caches of every
+//! CPU, and the `cpu_core` / `cpu_atom` PMU devices say which CPUs are which
+//! kind; features are read by running CPUID on one core of each kind. Other
+//! platforms report nothing.
+
+use crate::cpu::{CacheInfo, X86Features};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The kind of core on a hybrid CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CoreKind {
+    /// Performance core (Intel "Core", the `cpu_core` PMU)
+    Performance,
+    /// Efficiency core (Intel "Atom", the `cpu_atom` PMU)
+    Efficiency,
+}
+
+impl fmt::Display for CoreKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreKind::Performance => write!(f, "P-core"),
+            CoreKind::Efficiency => write!(f, "E-core"),
+        }
+    }
+}
+
+/// Caches of one representative CPU of each core kind, tagged with [`CacheInfo::core_kind`].
+///
+/// P-core caches come first, each kind ordered as the kernel lists them.
+/// `shared_by` counts cores, not hardware threads, so a P-core's L2 is 1
+/// even with SMT. Empty unless the system has both kinds of core, and on
+/// non-Linux platforms or without the `std` feature.
+#[must_use]
+pub fn detect_hybrid_caches() -> Vec<CacheInfo> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        hybrid_caches_in(std::path::Path::new("/sys/devices"))
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        Vec::new()
+    }
+}
+
+/// Features of a P-core and of an E-core, as `(p_core, e_core)`.
+///
+/// Each set is the CPUID-advertised features of that core minus
+/// `disabled_by_os`, the features the OS has not enabled (see
+/// [`X86Features::disabled_by_os`]). CPUID is run on a short-lived helper
+/// thread pinned to the core with `sched_setaffinity`; the calling thread's
+/// affinity is never changed. `None` unless the system has both kinds of
+/// core, if pinning fails (e.g. the core is outside this process's cpuset),
+/// and off Linux x86 or without the `linux` feature.
+#[must_use]
+pub fn detect_hybrid_features(disabled_by_os: X86Features) -> Option<(X86Features, X86Features)> {
+    #[cfg(all(
+        target_os = "linux",
+        feature = "linux",
+        any(target_arch = "x86", target_arch = "x86_64")
+    ))]
+    {
+        let (p_cpu, e_cpu) = hybrid_cpus(std::path::Path::new("/sys/devices"))?;
+        let p_core = raw_features_on(p_cpu)?.difference(disabled_by_os);
+        let e_core = raw_features_on(e_cpu)?.difference(disabled_by_os);
+        debug_log!("P-core {p_cpu} features: {p_core:?}; E-core {e_cpu} features: {e_core:?}");
+        Some((p_core, e_core))
+    }
+
+    #[cfg(not(all(
+        target_os = "linux",
+        feature = "linux",
+        any(target_arch = "x86", target_arch = "x86_64")
+    )))]
+    {
+        let _ = disabled_by_os;
+        None
+    }
+}
+
+/// CPUID features of logical CPU `cpu`, read on a helper thread pinned to it.
+#[cfg(all(
+    target_os = "linux",
+    feature = "linux",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+fn raw_features_on(cpu: u32) -> Option<X86Features> {
+    let id = usize::try_from(cpu).ok()?;
+    std::thread::spawn(move || {
+        core_affinity::set_for_current(core_affinity::CoreId { id })
+            .then(|| crate::cpu::CpuidWrapper::new().get_raw_features())
+    })
+    .join()
+    .ok()
+    .flatten()
+}
+
+/// Split per-kind feature sets into `(features, p_core_features, e_core_features)` for [`crate::cpu::CpuInfo`].
+///
+/// When both kinds agree the chip is reported as homogeneous, with no
+/// per-kind sets. Otherwise `features` is what every core supports, so code
+/// that may be scheduled on either kind can rely on it.
+#[must_use]
+pub fn merge_core_features(
+    p_core: X86Features,
+    e_core: X86Features,
+) -> (X86Features, Option<X86Features>, Option<X86Features>) {
+    if p_core == e_core {
+        (p_core, None, None)
+    } else {
+        (p_core.intersection(e_core), Some(p_core), Some(e_core))
+    }
+}
+
+/// The first P-core and first E-core listed by the `cpu_core` / `cpu_atom` PMUs under `devices`.
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn hybrid_cpus(devices: &std::path::Path) -> Option<(u32, u32)> {
+    let first_cpu = |pmu: &str| {
+        let list = std::fs::read_to_string(devices.join(pmu).join("cpus")).ok()?;
+        crate::cpu::numa::parse_cpulist(&list).first().copied()
+    };
+    Some((first_cpu("cpu_core")?, first_cpu("cpu_atom")?))
+}
+
+/// [`detect_hybrid_caches`] over a sysfs tree rooted at `devices` (normally `/sys/devices`).
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn hybrid_caches_in(devices: &std::path::Path) -> Vec<CacheInfo> {
+    use crate::cpu::cache::caches_of_cpu;
+
+    let Some((p_cpu, e_cpu)) = hybrid_cpus(devices) else {
+        return Vec::new();
+    };
+
+    let cpus = devices.join("system").join("cpu");
+    let mut caches = caches_of_cpu(&cpus.join(format!("cpu{p_cpu}")), Some(CoreKind::Performance));
+    caches.extend(caches_of_cpu(
+        &cpus.join(format!("cpu{e_cpu}")),
+        Some(CoreKind::Efficiency),
+    ));
+    caches
+}
+
+#[cfg(test)]
+#[cfg(all(target_os = "linux", feature = "std"))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// Write one `cpuN/cache/indexM` directory of a mock sysfs tree.
+    fn write_cache(cpu: &Path, index: u32, level: &str, kind: &str, size: &str, ways: &str, shared: &str) {
+        let dir = cpu.join("cache").join(format!("index{index}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, value) in [
+            ("level", level),
+            ("type", kind),
+            ("size", size),
+            ("ways_of_associativity", ways),
+            ("coherency_line_size", "64"),
+            ("number_of_sets", "64"),
+            ("shared_cpu_list", shared),
+        ] {
+            std::fs::write(dir.join(file), format!("{value}\n")).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_alder_lake_l2_sharing() {
+        // i9-12900K layout: P-cores are CPUs 0-15 (SMT pairs), E-cores 16-23 in clusters of four
+        let devices = tempfile::tempdir().unwrap();
+        let root = devices.path();
+        for (pmu, cpus) in [("cpu_core", "0-15"), ("cpu_atom", "16-23")] {
+            std::fs::create_dir_all(root.join(pmu)).unwrap();
+            std::fs::write(root.join(pmu).join("cpus"), format!("{cpus}\n")).unwrap();
+        }
+
+        let cpu0 = root.join("system/cpu/cpu0");
+        write_cache(&cpu0, 0, "1", "Data", "48K", "12", "0-1");
+        write_cache(&cpu0, 2, "2", "Unified", "1280K", "10", "0-1");
+        std::fs::create_dir_all(cpu0.join("topology")).unwrap();
+        std::fs::write(cpu0.join("topology/thread_siblings_list"), "0-1\n").unwrap();
+
+        let cpu16 = root.join("system/cpu/cpu16");
+        write_cache(&cpu16, 0, "1", "Data", "32K", "8", "16");
+        write_cache(&cpu16, 2, "2", "Unified", "2048K", "16", "16-19");
+
+        let caches = hybrid_caches_in(root);
+        let l2: Vec<_> = caches.iter().filter(|cache| cache.level == 2).collect();
+
+        assert_eq!(caches.len(), 4);
+        assert_eq!(l2[0].core_kind, Some(CoreKind::Performance));
+        assert_eq!((l2[0].size_kb, l2[0].shared_by), (1280, 1));
+        assert_eq!(l2[1].core_kind, Some(CoreKind::Efficiency));
+        assert_eq!((l2[1].size_kb, l2[1].shared_by, l2[1].associativity), (2048, 4, 16));
+    }
+
+    #[test]
+    fn test_merge_core_features() {
+        let common = X86Features::SSE2 | X86Features::AVX2;
+
+        // Homogeneous: no per-kind sets
+        assert_eq!(merge_core_features(common, common), (common, None, None));
+
+        // Only the P-cores implement AVX-512: the common set leaves it out
+        let p_core = common | X86Features::AVX512F;
+        assert_eq!(
+            merge_core_features(p_core, common),
+            (common, Some(p_core), Some(common))
+        );
+    }
+
+    #[test]
+    fn test_non_hybrid_is_empty() {
+        let devices = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(devices.path().join("cpu")).unwrap();
+        assert!(hybrid_caches_in(devices.path()).is_empty());
+    }
+}
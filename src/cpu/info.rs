@@ -4,9 +4,16 @@
 //! across different architectures. It aims to provide a unified interface for
 //! accessing CPU details regardless of the underlying hardware.
 
+use crate::cpu::cpuid::{AmdTopology, CacheTopology, PmuInfo};
+use crate::cpu::mitigations::Mitigation;
+use crate::cpu::numa::NumaNode;
 use crate::cpu::uarch::Microarch;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
 
 /// Error types for CPU information gathering
 #[derive(Debug, thiserror::Error)]
@@ -17,18 +24,153 @@ pub enum CpuError {
     InfoRead(String),
     #[error("Unsupported CPU architecture")]
     UnsupportedArch,
+    #[error("CPUID query failed: {0}")]
+    Cpuid(#[from] crate::cpu::CpuidError),
+    #[error("Failed to detect CPU features: {0}")]
+    Feature(#[from] crate::cpu::FeatureError),
+}
+
+/// CPUID vendor strings used by hypervisors, paired with a readable name.
+const HYPERVISOR_SIGNATURES: &[(&str, &str)] = &[
+    ("KVMKVMKVM", "KVM"),
+    ("KVMKVMKVM\0\0\0", "KVM"),
+    ("Microsoft Hv", "Hyper-V"),
+    ("VMwareVMware", "VMware"),
+    ("XenVMMXenVMM", "Xen"),
+    (" lrpepyh vr", "Parallels"),
+    (" lrpepyh  vr", "Parallels"),
+    ("bhyve bhyve ", "bhyve"),
+    ("TCGTCGTCGTCG", "QEMU"),
+    ("ACRNACRNACRN", "ACRN"),
+    ("QNXQVMBSQG", "QNX"),
+];
+
+/// Process-wide detection outcome behind [`CpuInfo::get`] and [`CpuInfo::shared`]
+static CPU_INFO: OnceLock<Result<Arc<CpuInfo>, CpuError>> = OnceLock::new();
+
+/// Run `detect` at most once for `cell` and hand out the stored outcome.
+fn cached_detection(
+    cell: &'static OnceLock<Result<Arc<CpuInfo>, CpuError>>,
+    detect: impl FnOnce() -> Result<CpuInfo, CpuError>,
+) -> Result<&'static Arc<CpuInfo>, &'static CpuError> {
+    cell.get_or_init(|| detect().map(Arc::new)).as_ref()
+}
+
+/// Readable name for a hypervisor signature (CPUID leaf 0x40000000), ignoring NUL padding.
+pub(crate) fn hypervisor_name(signature: &str) -> Option<&'static str> {
+    let signature = signature.trim_end_matches('\0');
+    HYPERVISOR_SIGNATURES
+        .iter()
+        .find(|(known, _)| known.trim_end_matches('\0') == signature)
+        .map(|(_, name)| *name)
 }
 
 /// CPU vendor identification
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Vendor {
     Intel,
     AMD,
     ARM,
     Apple,
+    /// Hygon Dhyana (Zen-derived, licensed from AMD)
+    Hygon,
+    /// VIA / Centaur
+    VIA,
+    /// Zhaoxin (VIA joint venture, "  Shanghai  " vendor string)
+    Zhaoxin,
+    /// Qualcomm (Arm implementer `0x51`)
+    Qualcomm,
+    /// NVIDIA (Arm implementer `0x4e`)
+    NVIDIA,
+    /// A hypervisor that replaces the CPUID vendor string with its own signature
+    Hypervisor(String),
     Unknown,
 }
 
+impl Vendor {
+    /// Map an x86 CPUID vendor string (leaf 0) to a `Vendor`.
+    ///
+    /// When the vendor string is unrecognised, the brand string is used as a
+    /// fallback so that chips with unusual or masked vendor IDs keep their
+    /// manufacturer where the brand makes it obvious.
+    #[must_use]
+    pub fn from_cpuid(vendor_string: &str, brand_string: &str) -> Self {
+        match vendor_string {
+            "GenuineIntel" => Vendor::Intel,
+            "AuthenticAMD" => Vendor::AMD,
+            "HygonGenuine" => Vendor::Hygon,
+            "CentaurHauls" | "VIA VIA VIA " => Vendor::VIA,
+            "  Shanghai  " => Vendor::Zhaoxin,
+            _ => HYPERVISOR_SIGNATURES
+                .iter()
+                .find(|(signature, _)| *signature == vendor_string)
+                .map_or_else(
+                    || Self::from_brand_string(brand_string),
+                    |(_, name)| Vendor::Hypervisor((*name).to_string()),
+                ),
+        }
+    }
+
+    /// Best-effort vendor guess from a marketing brand string.
+    fn from_brand_string(brand_string: &str) -> Self {
+        let brand = brand_string.to_ascii_lowercase();
+        if brand.contains("intel") {
+            Vendor::Intel
+        } else if brand.contains("amd") {
+            Vendor::AMD
+        } else if brand.contains("hygon") {
+            Vendor::Hygon
+        } else if brand.contains("zhaoxin") {
+            Vendor::Zhaoxin
+        } else if brand.contains("via") || brand.contains("centaur") {
+            Vendor::VIA
+        } else {
+            Vendor::Unknown
+        }
+    }
+}
+
+/// Parse a vendor from its display name ("Intel", "KVM (virtual)") or raw
+/// CPUID vendor string (`GenuineIntel`), ignoring ASCII case.
+///
+/// Parsing is lenient: unrecognised input yields `Vendor::Unknown` rather than
+/// an error, mirroring how detection treats unknown hardware. Use
+/// `s.parse::<Vendor>()` followed by a check for `Unknown` if strictness is needed.
+impl FromStr for Vendor {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Some hypervisor signatures (Parallels' " lrpepyh vr") carry spaces, so match before trimming
+        if let Some((_, name)) = HYPERVISOR_SIGNATURES
+            .iter()
+            .find(|(signature, _)| signature.eq_ignore_ascii_case(s))
+        {
+            return Ok(Vendor::Hypervisor((*name).to_string()));
+        }
+
+        let trimmed = s.trim();
+        if let Some(name) = trimmed.strip_suffix(" (virtual)") {
+            return Ok(Vendor::Hypervisor(name.to_string()));
+        }
+
+        let vendor = match trimmed.to_ascii_lowercase().as_str() {
+            "intel" | "genuineintel" => Vendor::Intel,
+            "amd" | "authenticamd" => Vendor::AMD,
+            "arm" => Vendor::ARM,
+            "apple" => Vendor::Apple,
+            "hygon" | "hygongenuine" => Vendor::Hygon,
+            "via" | "centaurhauls" | "via via via" => Vendor::VIA,
+            // Zhaoxin's vendor string "  Shanghai  " is padded with spaces, matched here once trimmed
+            "zhaoxin" | "shanghai" => Vendor::Zhaoxin,
+            "qualcomm" => Vendor::Qualcomm,
+            "nvidia" => Vendor::NVIDIA,
+            _ => Vendor::Unknown,
+        };
+        Ok(vendor)
+    }
+}
+
 impl fmt::Display for Vendor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -36,20 +178,119 @@ impl fmt::Display for Vendor {
             Vendor::AMD => write!(f, "AMD"),
             Vendor::ARM => write!(f, "ARM"),
             Vendor::Apple => write!(f, "Apple"),
+            Vendor::Hygon => write!(f, "Hygon"),
+            Vendor::VIA => write!(f, "VIA"),
+            Vendor::Zhaoxin => write!(f, "Zhaoxin"),
+            Vendor::Qualcomm => write!(f, "Qualcomm"),
+            Vendor::NVIDIA => write!(f, "NVIDIA"),
+            Vendor::Hypervisor(name) => write!(f, "{name} (virtual)"),
             Vendor::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// Byte order of the CPU as this build sees it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Endianness {
+    /// Least significant byte first (x86, and Arm as most systems run it)
+    Little,
+    /// Most significant byte first (s390x, big-endian PowerPC and MIPS)
+    Big,
+}
+
+impl Endianness {
+    /// The byte order of the target this crate was compiled for.
+    #[must_use]
+    pub const fn native() -> Self {
+        if cfg!(target_endian = "big") {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        }
+    }
+}
+
+impl Default for Endianness {
+    fn default() -> Self {
+        Self::native()
+    }
+}
+
+impl fmt::Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Endianness::Little => write!(f, "Little-endian"),
+            Endianness::Big => write!(f, "Big-endian"),
+        }
+    }
+}
+
 /// CPU frequency information in MHz
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+///
+/// Marked `#[non_exhaustive]` so that new fields are not a breaking change.
+/// Outside this crate, build one with [`Frequency::new`] or start from
+/// `Frequency::default()` and assign fields, instead of a struct literal.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub struct Frequency {
     /// Base/nominal frequency
     pub base: Option<f64>,
     /// Maximum turbo frequency
     pub max: Option<f64>,
-    /// Current operating frequency
+    /// Highest current clock among the active cores (on Linux, every online
+    /// core is sampled; elsewhere the OS may report a single core)
+    pub current: Option<f64>,
+    /// Mean current clock across the sampled cores
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub average: Option<f64>,
+    /// Clock estimated by timing a busy loop, only with `--benchmark-freq`
+    /// (see [`crate::cpu::frequency::estimate_frequency_by_benchmark`])
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub estimated: Option<f64>,
+}
+
+impl Frequency {
+    /// Create a frequency reading from base, maximum and current values in MHz, with no average or estimate.
+    #[must_use]
+    pub const fn new(base: Option<f64>, max: Option<f64>, current: Option<f64>) -> Self {
+        Self {
+            base,
+            max,
+            current,
+            average: None,
+            estimated: None,
+        }
+    }
+}
+
+/// Clock of one frequency domain, such as the little or big cores of an Arm chip
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClusterFrequency {
+    /// cpufreq policy number (`policyN`), normally the cluster's first CPU
+    pub policy: u32,
+    /// Logical CPUs sharing the clock, in ascending order
+    pub cpus: Vec<u32>,
+    /// Current clock in MHz
     pub current: Option<f64>,
+    /// Highest clock the cluster can reach, in MHz
+    pub max: Option<f64>,
+}
+
+impl fmt::Display for ClusterFrequency {
+    /// "CPUs 4-7: 1.800 GHz (max 2.400 GHz)", leaving out unknown clocks.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CPUs ")?;
+        crate::cpu::numa::write_cpulist(f, &self.cpus)?;
+        match (self.current, self.max) {
+            (Some(current), Some(max)) => write!(f, ": {:.3} GHz (max {:.3} GHz)", current / 1000.0, max / 1000.0),
+            (Some(current), None) => write!(f, ": {:.3} GHz", current / 1000.0),
+            (None, Some(max)) => write!(f, ": max {:.3} GHz", max / 1000.0),
+            (None, None) => Ok(()),
+        }
+    }
 }
 
 impl fmt::Display for Frequency {
@@ -69,7 +310,11 @@ impl fmt::Display for Frequency {
 }
 
 /// Represents version information for a CPU
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+///
+/// Marked `#[non_exhaustive]`; construct it with [`Version::new`] outside this crate.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub struct Version {
     /// CPU family identifier
     pub family: u8,
@@ -79,8 +324,53 @@ pub struct Version {
     pub stepping: u8,
 }
 
+impl Version {
+    /// Create a version from its family, model and stepping identifiers.
+    #[must_use]
+    pub const fn new(family: u8, model: u8, stepping: u8) -> Self {
+        Self {
+            family,
+            model,
+            stepping,
+        }
+    }
+}
+
+/// Version of the machine-readable (JSON, YAML, TOML) output shape.
+///
+/// Emitted as the top-level `schema_version` field. Bump it whenever a field
+/// is renamed, removed or changes meaning, so consumers can detect the change.
+///
+/// Version 2 writes feature flags as arrays of names instead of `"A | B"` text.
+pub const JSON_SCHEMA_VERSION: u32 = 2;
+
+/// The machine-readable output: `schema_version` first, then the `CpuInfo` fields.
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+struct VersionedOutput<'a> {
+    schema_version: u32,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    info: &'a CpuInfo,
+}
+
 /// Core CPU information structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Marked `#[non_exhaustive]` so that detecting something new does not break
+/// downstream crates. Struct literals and exhaustive destructuring are
+/// therefore only possible inside this crate; elsewhere, obtain a `CpuInfo`
+/// from [`CpuInfo::new`] or start from `CpuInfo::default()` and assign the
+/// fields you need:
+///
+/// ```
+/// use cpufetch_rs::{CpuInfo, Vendor};
+///
+/// let mut info = CpuInfo::default();
+/// info.vendor = Vendor::AMD;
+/// info.brand_string = "AMD Ryzen 9 7950X".to_string();
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub struct CpuInfo {
     /// CPU vendor identification
     pub vendor: Vendor,
@@ -88,34 +378,138 @@ pub struct CpuInfo {
     pub brand_string: String,
     /// Version information (family/model/stepping)
     pub version: Version,
-    /// Number of physical CPU cores
+    /// Number of physical CPU cores across all packages.
+    ///
+    /// Each P-core and E-core on a hybrid part counts once; SMT siblings do not.
+    /// Never exceeds `logical_cores`.
     pub physical_cores: u32,
-    /// Number of logical CPU threads
+    /// Number of logical CPU threads (hardware threads visible to the OS)
     pub logical_cores: u32,
+    /// Number of physical packages (sockets), when the platform reports it
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub sockets: Option<u32>,
+    /// The package this info describes, when split per socket by [`CpuInfo::detect_all`]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub package_id: Option<u32>,
+    /// Processor packages and the logical CPUs in each, when the platform lists them (Linux)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub packages: Option<Vec<crate::cpu::Package>>,
     /// Frequency information
     pub frequency: Frequency,
     /// Cache sizes in KB (L1i, L1d, L2, L3)
+    ///
+    /// Prefer the named accessors ([`CpuInfo::l1i`], [`CpuInfo::l1d`],
+    /// [`CpuInfo::l2`], [`CpuInfo::l3`]) over indexing.
     pub cache_sizes: [Option<u32>; 4],
+    /// Full per-cache detail (line size, associativity, sharing), where the platform exposes it
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub cache_topology: Option<CacheTopology>,
     /// CPU features
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     pub features: crate::cpu::X86Features,
     #[cfg(target_arch = "aarch64")]
     pub features: crate::cpu::ArmFeatures,
+    /// Features CPUID advertises that the OS has not enabled (e.g. AVX-512 masked in a VM)
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "crate::cpu::X86Features::empty",
+            skip_serializing_if = "crate::cpu::X86Features::is_empty"
+        )
+    )]
+    pub available_but_disabled: crate::cpu::X86Features,
+    /// Features of the P-cores on a hybrid CPU whose core kinds differ (x86 only).
+    ///
+    /// `None` on homogeneous chips; `features` then holds the common set.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub p_core_features: Option<crate::cpu::X86Features>,
+    /// Features of the E-cores on a hybrid CPU whose core kinds differ (x86 only)
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub e_core_features: Option<crate::cpu::X86Features>,
+    /// Loaded microcode revision, where the OS exposes it
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub microcode: Option<u64>,
+    /// Active cpufreq scaling governor, e.g. `performance` (Linux only)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub governor: Option<String>,
+    /// Energy-performance preference hint, e.g. `balance_performance` (Linux only)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub epp: Option<String>,
+    /// SVE hardware vector length in bits (ARM with SVE only)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub sve_vector_bits: Option<u16>,
+    /// Time Stamp Counter frequency in MHz (CPUID leaves 0x15/0x16 on x86, else the kernel's calibration)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub tsc_frequency_mhz: Option<f64>,
+    /// Whether the TSC runs at a constant rate regardless of power state (x86 only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub invariant_tsc: bool,
+    /// Performance monitoring unit version and counters (CPUID leaf 0xA, Intel x86 only)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub pmu: Option<PmuInfo>,
+    /// Physical address width in bits (CPUID leaf 0x80000008 on x86)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub physical_address_bits: Option<u8>,
+    /// Virtual (linear) address width in bits (CPUID leaf 0x80000008 on x86)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub virtual_address_bits: Option<u8>,
+    /// Byte order, taken from the compilation target
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub endianness: Endianness,
     /// Detected CPU microarchitecture (if recognised)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub microarch: Option<Microarch>,
+    /// Manufacturing process, e.g. "Intel 7" or "7 nm", of the detected
+    /// microarchitecture (see [`Microarch::process_node`]); not hardware-reported
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub process_node: Option<String>,
     /// Hypervisor name if running inside a virtual machine
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub hypervisor: Option<String>,
+    /// Whether CPUID reports a hypervisor (leaf 1 ECX bit 31)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_virtualized: bool,
+    /// Whether this x86-64 process runs translated by Rosetta 2 on Apple Silicon
+    /// (macOS only); CPUID then describes the emulated CPU, not the M-series chip
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub translated: bool,
+    /// Raw hypervisor signature from CPUID leaf 0x40000000 (e.g. `KVMKVMKVM`);
+    /// `hypervisor` holds the readable name
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub hypervisor_vendor: Option<String>,
     /// Theoretical peak double-precision performance in GFLOP/s
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub peak_flops: Option<f64>,
     /// Performance core count (for hybrid architectures like Apple Silicon, Alder Lake)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub p_cores: Option<u32>,
     /// Efficiency core count (for hybrid architectures)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub e_cores: Option<u32>,
+    /// AMD CCX/CCD chiplet layout (AMD Zen and later only)
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub amd_topology: Option<AmdTopology>,
+    /// Package temperature in degrees Celsius at detection time (requires `thermal`)
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub temperature_celsius: Option<f32>,
+    /// Average package power in watts (requires `power`).
+    ///
+    /// Measuring it blocks for a sampling interval, so detection leaves this
+    /// `None`; fill it in with [`crate::cpu::detect_power`] when wanted.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub power_watts: Option<f32>,
+    /// NUMA node to logical CPU mapping (Linux only)
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub numa_nodes: Option<Vec<NumaNode>>,
+    /// Frequency domains on CPUs whose clusters clock independently (Arm big.LITTLE, Linux only)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub clusters: Option<Vec<ClusterFrequency>>,
+    /// Speculative-execution vulnerabilities and their mitigation status (Linux only)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub mitigations: Vec<Mitigation>,
 }
 
 impl CpuInfo {
@@ -133,21 +527,535 @@ impl CpuInfo {
         {
             crate::arch::aarch64::detect_cpu()
         }
-        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::arch::wasm32::detect_cpu()
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "wasm32")))]
+        {
+            crate::arch::generic::detect_cpu()
+        }
+    }
+
+    /// Detects the current CPU using only the CPUID instruction
+    ///
+    /// Unlike [`CpuInfo::new`], this performs no OS queries, so it works in
+    /// sandboxed and embedded environments and builds without the `std`
+    /// feature's dependencies. Core counts are `0`; frequency, temperature and
+    /// NUMA layout are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CpuError::UnsupportedArch` on non-x86 targets, or `CpuError` if
+    /// CPUID access fails.
+    pub fn from_cpuid_only() -> Result<Self, CpuError> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            crate::arch::x86_64::detect_cpu_from_cpuid()
+        }
+        #[cfg(not(target_arch = "x86_64"))]
         {
             Err(CpuError::UnsupportedArch)
         }
     }
 
+    /// Detects the current CPU once per processor package (socket).
+    ///
+    /// Each element describes one package, with its own core counts, NUMA
+    /// nodes and `package_id`; everything CPUID reports is shared. A
+    /// single-socket system, or one whose packages cannot be told apart (see
+    /// [`crate::cpu::detect_packages`]), yields a one-element vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CpuError` if CPU detection fails.
+    pub fn detect_all() -> Result<Vec<Self>, CpuError> {
+        Ok(Self::new()?.split_packages())
+    }
+
+    /// Split system-wide info into one `CpuInfo` per package in [`CpuInfo::packages`].
+    ///
+    /// The split follows the layout recorded at detection, so a snapshot read
+    /// back from JSON divides into the packages of the machine it describes.
+    /// Peak FLOPS is scaled by the package's share of the physical cores.
+    /// With no packages the info is returned unchanged as the only element.
+    #[must_use]
+    pub fn split_packages(&self) -> Vec<Self> {
+        let packages = self.packages.as_deref().unwrap_or_default();
+        if packages.is_empty() {
+            return vec![self.clone()];
+        }
+
+        packages
+            .iter()
+            .map(|package| {
+                let numa_nodes: Option<Vec<NumaNode>> = self.numa_nodes.as_ref().map(|nodes| {
+                    nodes
+                        .iter()
+                        .filter(|node| node.cpus.iter().any(|cpu| package.cpus.contains(cpu)))
+                        .cloned()
+                        .collect()
+                });
+                Self {
+                    physical_cores: package.physical_cores,
+                    logical_cores: u32::try_from(package.cpus.len()).unwrap_or(u32::MAX),
+                    sockets: Some(1),
+                    package_id: Some(package.id),
+                    packages: Some(vec![package.clone()]),
+                    peak_flops: self
+                        .peak_flops
+                        .filter(|_| self.physical_cores > 0)
+                        .map(|flops| flops * f64::from(package.physical_cores) / f64::from(self.physical_cores)),
+                    numa_nodes: numa_nodes.filter(|nodes| !nodes.is_empty()),
+                    ..self.clone()
+                }
+            })
+            .collect()
+    }
+
     /// Returns a reference to a statically detected CPU info
     ///
     /// This is useful when you want to avoid the overhead of detecting
     /// the CPU multiple times during program execution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if detection fails; use [`CpuInfo::try_get`] to handle that case.
     #[must_use]
     pub fn get() -> &'static Self {
-        static CPU_INFO: std::sync::LazyLock<CpuInfo> =
-            std::sync::LazyLock::new(|| CpuInfo::new().expect("Failed to detect CPU information"));
-        &CPU_INFO
+        Self::try_get().expect("Failed to detect CPU information")
+    }
+
+    /// Fallible form of [`CpuInfo::get`]
+    ///
+    /// Detection runs once per process, even with concurrent first callers,
+    /// and its outcome is cached: a failure is returned to every caller rather
+    /// than being retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `CpuError` from the one detection attempt if it failed.
+    pub fn try_get() -> Result<&'static Self, &'static CpuError> {
+        cached_detection(&CPU_INFO, Self::new).map(|info| &**info)
+    }
+
+    /// Returns an owned, cheaply clonable handle to the detected CPU info
+    ///
+    /// Shares the single detection behind [`CpuInfo::get`]; each call only
+    /// bumps a reference count. Useful where a `'static` borrow is awkward,
+    /// such as values moved into async tasks or stored in generic containers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if detection fails; use [`CpuInfo::try_shared`] to handle that case.
+    #[must_use]
+    pub fn shared() -> Arc<Self> {
+        Self::try_shared().expect("Failed to detect CPU information")
+    }
+
+    /// Fallible form of [`CpuInfo::shared`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the `CpuError` from the one detection attempt if it failed.
+    pub fn try_shared() -> Result<Arc<Self>, &'static CpuError> {
+        cached_detection(&CPU_INFO, Self::new).map(Arc::clone)
+    }
+
+    /// Size in KB of the L1 instruction cache
+    #[must_use]
+    pub fn l1i(&self) -> Option<u32> {
+        self.cache_sizes[0]
+    }
+
+    /// Size in KB of the L1 data cache
+    #[must_use]
+    pub fn l1d(&self) -> Option<u32> {
+        self.cache_sizes[1]
+    }
+
+    /// Size in KB of the L2 cache
+    #[must_use]
+    pub fn l2(&self) -> Option<u32> {
+        self.cache_sizes[2]
+    }
+
+    /// Size in KB of the L3 cache
+    #[must_use]
+    pub fn l3(&self) -> Option<u32> {
+        self.cache_sizes[3]
+    }
+
+    /// Size in KB of the L4 cache, e.g. the eDRAM of Crystal Well parts.
+    ///
+    /// Only known with a full cache topology; `cache_sizes` stops at L3.
+    #[must_use]
+    pub fn l4(&self) -> Option<u32> {
+        Some(self.cache_topology.as_ref()?.l4()?.size_kb)
+    }
+
+    /// Line size in bytes of the L1 data cache, for aligning and padding cache-aware data.
+    ///
+    /// Read from the detected cache topology (CPUID on x86). It is 64 bytes on
+    /// most current x86 and Arm cores, but Apple's M-series use 128; `None`
+    /// means it could not be detected, and no guess is substituted.
+    #[must_use]
+    pub fn cache_line_size(&self) -> Option<u16> {
+        let topology = self.cache_topology.as_ref()?;
+        let l1d = topology.iter_present().find(|cache| {
+            cache.level == 1
+                && matches!(
+                    cache.cache_type,
+                    crate::cpu::CacheType::Data | crate::cpu::CacheType::Unified
+                )
+        })?;
+        (l1d.line_size > 0).then_some(l1d.line_size)
+    }
+
+    /// Whether the CPU has the feature named `name`, e.g. "avx2" or "NEON".
+    ///
+    /// Names are matched like [`crate::cpu::parse_features`], ignoring case and
+    /// punctuation, against this architecture's flags (see
+    /// [`crate::cpu::feature_names`]). Returns `None` for a name that is not a
+    /// single known feature, and on architectures without a feature set.
+    #[must_use]
+    pub fn has_feature_named(&self, name: &str) -> Option<bool> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+        {
+            let flag = crate::cpu::flags::feature_named(name)?;
+            Some(self.features.contains(flag))
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+        {
+            let _ = name;
+            None
+        }
+    }
+
+    /// Whether every feature in `required` is present, e.g. all the ISA
+    /// extensions a binary was compiled for.
+    ///
+    /// An empty `required` set is always supported.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[must_use]
+    pub fn supports_all(&self, required: crate::cpu::X86Features) -> bool {
+        self.features.contains(required)
+    }
+
+    /// Whether every feature in `required` is present, e.g. all the ISA
+    /// extensions a binary was compiled for.
+    ///
+    /// An empty `required` set is always supported.
+    #[cfg(target_arch = "aarch64")]
+    #[must_use]
+    pub fn supports_all(&self, required: crate::cpu::ArmFeatures) -> bool {
+        self.features.contains(required)
+    }
+
+    /// The features in `required` that this CPU lacks; empty when [`CpuInfo::supports_all`] holds.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[must_use]
+    pub fn missing_features(&self, required: crate::cpu::X86Features) -> crate::cpu::X86Features {
+        required.difference(self.features)
+    }
+
+    /// The features in `required` that this CPU lacks; empty when [`CpuInfo::supports_all`] holds.
+    #[cfg(target_arch = "aarch64")]
+    #[must_use]
+    pub fn missing_features(&self, required: crate::cpu::ArmFeatures) -> crate::cpu::ArmFeatures {
+        required.difference(self.features)
+    }
+
+    /// Whether this CPU has every feature of `other`, so code built for
+    /// `other` (e.g. a snapshot of the build machine) can run here.
+    ///
+    /// Only feature flags are compared; on architectures without a feature
+    /// set every CPU counts as a superset.
+    #[must_use]
+    pub fn is_feature_superset_of(&self, other: &CpuInfo) -> bool {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+        {
+            self.supports_all(other.features)
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+        {
+            let _ = other;
+            true
+        }
+    }
+
+    /// Width in bytes of the widest SIMD register the CPU can use, for picking a kernel.
+    ///
+    /// 64 with AVX-512, 32 with AVX or AVX2, 16 with SSE2 or NEON. On Arm with
+    /// SVE this is the hardware vector length when it is known (at least 16).
+    /// Features the OS has not enabled do not count. Falls back to 8, the width
+    /// of a general-purpose register, when no SIMD is detected and on
+    /// architectures without a feature set.
+    #[must_use]
+    pub fn max_vector_bytes(&self) -> usize {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            use crate::cpu::X86Features;
+            if self.features.contains(X86Features::AVX512F) {
+                64
+            } else if self.features.intersects(X86Features::AVX | X86Features::AVX2) {
+                32
+            } else if self.features.contains(X86Features::SSE2) {
+                16
+            } else {
+                8
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            use crate::cpu::ArmFeatures;
+            if self.features.contains(ArmFeatures::SVE)
+                && let Some(bits) = self.sve_vector_bits
+            {
+                usize::from(bits / 8).max(16)
+            } else if self
+                .features
+                .intersects(ArmFeatures::NEON | ArmFeatures::ASIMD | ArmFeatures::SVE)
+            {
+                16
+            } else {
+                8
+            }
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+        {
+            8
+        }
+    }
+
+    /// One-line summary for status bars and logs, e.g. "Intel Core i7-9750H (6C/12T) @ 2.6GHz".
+    ///
+    /// Trademark symbols and the brand string's own clock suffix are dropped,
+    /// the vendor is prefixed when the brand omits it, and the core count and
+    /// frequency (base, else max) clauses are left out when unknown.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        use std::fmt::Write as FmtWrite;
+
+        let mut summary = self.short_brand();
+
+        if self.logical_cores > 0 {
+            let _ = write!(summary, " ({}C/{}T)", self.physical_cores, self.logical_cores);
+        }
+        if let Some(mhz) = self.frequency.base.or(self.frequency.max) {
+            let _ = write!(summary, " @ {:.1}GHz", mhz / 1000.0);
+        }
+
+        summary
+    }
+
+    /// The brand string without trademark symbols, filler words or the clock
+    /// suffix, e.g. "Intel Core i7-9750H" for "Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz".
+    ///
+    /// `brand_string` itself is left as the CPU reports it; see [`normalize_brand`]
+    /// to keep the clock.
+    #[must_use]
+    pub fn normalized_brand(&self) -> String {
+        normalize_brand(&self.brand_string, false)
+    }
+
+    /// Brand string without trademark symbols, clock suffix or filler words,
+    /// prefixed with the vendor when the brand does not name it.
+    fn short_brand(&self) -> String {
+        // "with Radeon Graphics" reads better as "Radeon Graphics" in one line
+        let brand = self
+            .normalized_brand()
+            .split(' ')
+            .filter(|word| *word != "with")
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let vendor = self.vendor.to_string();
+        if brand.is_empty() {
+            vendor
+        } else if matches!(self.vendor, Vendor::Unknown | Vendor::Hypervisor(_))
+            || brand.to_ascii_lowercase().contains(&vendor.to_ascii_lowercase())
+        {
+            brand
+        } else {
+            format!("{vendor} {brand}")
+        }
+    }
+
+    /// Wrap this `CpuInfo` in the machine-readable output envelope.
+    #[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+    fn versioned(&self) -> VersionedOutput<'_> {
+        VersionedOutput {
+            schema_version: JSON_SCHEMA_VERSION,
+            info: self,
+        }
+    }
+
+    /// Serialise this `CpuInfo` to a compact JSON string.
+    ///
+    /// Only requires the `json` feature, so library users can export detection
+    /// results without pulling in the terminal display stack. The object
+    /// carries a top-level `schema_version` (see [`JSON_SCHEMA_VERSION`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Json` if serialisation fails.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, crate::Error> {
+        Ok(serde_json::to_string(&self.versioned())?)
+    }
+
+    /// Parse a snapshot saved with [`CpuInfo::to_json`] or `cpufetch --json`.
+    ///
+    /// Fields added since the snapshot was written take their defaults, so
+    /// older reports still load.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Json` if the text is not a `CpuInfo` object, or
+    /// `Error::Other` if it was written with a newer [`JSON_SCHEMA_VERSION`].
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, crate::Error> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if let Some(version) = value.get("schema_version").and_then(serde_json::Value::as_u64)
+            && version > u64::from(JSON_SCHEMA_VERSION)
+        {
+            return Err(crate::Error::Other(format!(
+                "snapshot uses JSON schema version {version}, newer than the supported {JSON_SCHEMA_VERSION}"
+            )));
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Serialise this `CpuInfo` to an indented, human-readable JSON string.
+    ///
+    /// This is the representation used by `cpufetch --json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Json` if serialisation fails.
+    #[cfg(feature = "json")]
+    pub fn to_json_pretty(&self) -> Result<String, crate::Error> {
+        Ok(serde_json::to_string_pretty(&self.versioned())?)
+    }
+
+    /// Serialise this `CpuInfo` to a YAML document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Yaml` if serialisation fails.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String, crate::Error> {
+        Ok(serde_yaml::to_string(&self.versioned())?)
+    }
+
+    /// Serialise this `CpuInfo` to a TOML document.
+    ///
+    /// TOML has no null, so `None` struct fields are omitted and `None` array
+    /// entries (e.g. a missing L3 in `cache_sizes`) become empty tables. This
+    /// keeps array positions stable for consumers indexing by cache level.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Json` or `Error::Toml` if serialisation fails.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, crate::Error> {
+        let value = json_to_toml(serde_json::to_value(self.versioned())?)
+            .unwrap_or_else(|| toml::Value::Table(toml::Table::new()));
+        Ok(toml::to_string_pretty(&value)?)
+    }
+}
+
+/// Clean up a CPU brand string for display or for matching against spec databases.
+///
+/// Drops the `(R)`/`(TM)` marks, "CPU", "Processor" and core-count words
+/// such as "16-Core", and collapses runs of whitespace. The "@ 2.60GHz" clock
+/// suffix is kept only with `keep_clock`.
+#[must_use]
+pub fn normalize_brand(brand: &str, keep_clock: bool) -> String {
+    let (name, clock) = match brand.split_once('@') {
+        Some((name, clock)) => (name, Some(clock.trim()).filter(|clock| !clock.is_empty())),
+        None => (brand, None),
+    };
+
+    // A space rather than nothing, so "Core(TM)2 Duo" becomes "Core 2 Duo"
+    let mut name = name.to_string();
+    for mark in ["(R)", "(r)", "(TM)", "(tm)", "\u{ae}", "\u{2122}"] {
+        name = name.replace(mark, " ");
+    }
+
+    let mut words: Vec<&str> = name
+        .split_whitespace()
+        .filter(|word| {
+            !matches!(*word, "CPU" | "Processor")
+                && !word
+                    .strip_suffix("-Core")
+                    .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    if keep_clock && let Some(clock) = clock {
+        words.extend(["@", clock]);
+    }
+    words.join(" ")
+}
+
+/// Plain multi-line summary (no colour, no logo): vendor, model, cores,
+/// the caches that were detected and, if known, the frequency.
+impl fmt::Display for CpuInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "CPU Information:")?;
+        writeln!(f, "Vendor: {}", self.vendor)?;
+        writeln!(f, "Model: {}", self.brand_string)?;
+        write!(
+            f,
+            "Cores: {} physical, {} logical",
+            self.physical_cores, self.logical_cores
+        )?;
+
+        let labels = ["L1i Cache", "L1d Cache", "L2 Cache", "L3 Cache"];
+        for (label, size) in labels.iter().zip(self.cache_sizes) {
+            if let Some(kb) = size {
+                write!(f, "\n{label}: {kb} KB")?;
+            }
+        }
+        if let Some(kb) = self.l4() {
+            write!(f, "\nL4 Cache: {kb} KB")?;
+        }
+
+        if self.frequency.base.is_some() || self.frequency.current.is_some() || self.frequency.max.is_some() {
+            write!(f, "\nFrequency: {}", self.frequency)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a JSON value into a TOML value, returning `None` for JSON `null`.
+#[cfg(feature = "toml")]
+fn json_to_toml(value: serde_json::Value) -> Option<toml::Value> {
+    use serde_json::Value as Json;
+    use toml::Value as Toml;
+
+    match value {
+        Json::Null => None,
+        Json::Bool(b) => Some(Toml::Boolean(b)),
+        Json::Number(n) => n.as_i64().map(Toml::Integer).or_else(|| n.as_f64().map(Toml::Float)),
+        Json::String(s) => Some(Toml::String(s)),
+        Json::Array(items) => Some(Toml::Array(
+            items
+                .into_iter()
+                .map(|item| json_to_toml(item).unwrap_or_else(|| Toml::Table(toml::Table::new())))
+                .collect(),
+        )),
+        Json::Object(map) => Some(Toml::Table(
+            map.into_iter()
+                .filter_map(|(key, val)| json_to_toml(val).map(|v| (key, v)))
+                .collect(),
+        )),
     }
 }
 
@@ -159,17 +1067,47 @@ impl Default for CpuInfo {
             version: Version::default(),
             physical_cores: 0,
             logical_cores: 0,
+            sockets: None,
+            package_id: None,
+            packages: None,
             frequency: Frequency::default(),
             cache_sizes: [None; 4],
+            cache_topology: None,
             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
             features: crate::cpu::X86Features::empty(),
             #[cfg(target_arch = "aarch64")]
             features: crate::cpu::ArmFeatures::empty(),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            available_but_disabled: crate::cpu::X86Features::empty(),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            p_core_features: None,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            e_core_features: None,
+            microcode: None,
+            governor: None,
+            epp: None,
+            sve_vector_bits: None,
+            tsc_frequency_mhz: None,
+            invariant_tsc: false,
+            pmu: None,
+            physical_address_bits: None,
+            virtual_address_bits: None,
+            endianness: Endianness::native(),
             microarch: None,
+            process_node: None,
             hypervisor: None,
+            is_virtualized: false,
+            translated: false,
+            hypervisor_vendor: None,
             peak_flops: None,
             p_cores: None,
             e_cores: None,
+            amd_topology: None,
+            temperature_celsius: None,
+            power_watts: None,
+            numa_nodes: None,
+            clusters: None,
+            mitigations: Vec::new(),
         }
     }
 }
@@ -178,12 +1116,490 @@ impl Default for CpuInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cpu_error_keeps_typed_cause() {
+        use std::error::Error as _;
+
+        let err = CpuError::from(crate::cpu::FeatureError::UnsupportedArch);
+        assert!(matches!(
+            err,
+            CpuError::Feature(crate::cpu::FeatureError::UnsupportedArch)
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Failed to detect CPU features: Feature detection not supported on this architecture"
+        );
+
+        let err = crate::Error::from(CpuError::from(crate::cpu::CpuidError::UnsupportedLeaf(0x16)));
+        let cause = err.source().and_then(|cpu| cpu.downcast_ref::<CpuError>());
+        assert!(matches!(
+            cause,
+            Some(CpuError::Cpuid(crate::cpu::CpuidError::UnsupportedLeaf(0x16)))
+        ));
+    }
+
+    #[test]
+    fn test_cached_detection_runs_once_across_threads() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CELL: OnceLock<Result<Arc<CpuInfo>, CpuError>> = OnceLock::new();
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    cached_detection(&CELL, || {
+                        RUNS.fetch_add(1, Ordering::SeqCst);
+                        std::thread::sleep(std::time::Duration::from_millis(20));
+                        Err(CpuError::InfoRead("mocked failure".to_string()))
+                    })
+                    .map(|_| ())
+                    .map_err(ToString::to_string)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let result = handle.join().expect("accessor must not panic");
+            assert_eq!(result.unwrap_err(), "Failed to read CPU information: mocked failure");
+        }
+        assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_shared_hands_out_one_allocation() {
+        let handles: Vec<_> = (0..8).map(|_| std::thread::spawn(CpuInfo::try_shared)).collect();
+        let shared: Vec<Arc<CpuInfo>> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("accessor must not panic").unwrap())
+            .collect();
+
+        let first = &shared[0];
+        assert!(shared.iter().all(|info| Arc::ptr_eq(info, first)));
+        assert!(std::ptr::eq(CpuInfo::get(), Arc::as_ptr(first)));
+    }
+
+    #[test]
+    fn test_cache_size_accessors() {
+        let info = CpuInfo {
+            cache_sizes: [Some(32), Some(48), Some(2048), None],
+            ..CpuInfo::default()
+        };
+        assert_eq!(info.l1i(), Some(32));
+        assert_eq!(info.l1d(), Some(48));
+        assert_eq!(info.l2(), Some(2048));
+        assert_eq!(info.l3(), None);
+    }
+
+    #[test]
+    fn test_cache_line_size() {
+        use crate::cpu::{CacheInfo, CacheTopology, CacheType};
+
+        let mut l1d = CacheInfo::new(1, CacheType::Data, 48);
+        l1d.line_size = 64;
+        let mut l2 = CacheInfo::new(2, CacheType::Unified, 2048);
+        l2.line_size = 128;
+        let mut topology = CacheTopology::default();
+        topology.caches[1] = Some(l1d);
+        topology.caches[2] = Some(l2);
+
+        let mut info = CpuInfo {
+            cache_topology: Some(topology.clone()),
+            ..CpuInfo::default()
+        };
+        assert_eq!(info.cache_line_size(), Some(64));
+
+        // Sizes alone, or an L1d without geometry, give no answer rather than a guess
+        info.cache_topology = None;
+        info.cache_sizes = [None, Some(48), None, None];
+        assert_eq!(info.cache_line_size(), None);
+        topology.caches[1] = Some(CacheInfo::new(1, CacheType::Data, 48));
+        info.cache_topology = Some(topology);
+        assert_eq!(info.cache_line_size(), None);
+    }
+
+    #[test]
+    fn test_split_packages() {
+        use crate::cpu::Package;
+
+        let packages = vec![
+            Package {
+                id: 0,
+                cpus: vec![0, 1, 4, 5],
+                physical_cores: 2,
+            },
+            Package {
+                id: 1,
+                cpus: vec![2, 3, 6, 7],
+                physical_cores: 2,
+            },
+        ];
+        let mut info = CpuInfo {
+            physical_cores: 4,
+            logical_cores: 8,
+            sockets: Some(2),
+            packages: Some(packages.clone()),
+            peak_flops: Some(400.0),
+            numa_nodes: Some(vec![
+                NumaNode {
+                    id: 0,
+                    cpus: vec![0, 1, 4, 5],
+                },
+                NumaNode {
+                    id: 1,
+                    cpus: vec![2, 3, 6, 7],
+                },
+            ]),
+            ..CpuInfo::default()
+        };
+
+        let split = info.split_packages();
+        assert_eq!(split.len(), 2);
+        assert_eq!(split[1].package_id, Some(1));
+        assert_eq!((split[1].physical_cores, split[1].logical_cores), (2, 4));
+        assert_eq!(split[1].sockets, Some(1));
+        assert_eq!(split[1].peak_flops, Some(200.0));
+        assert_eq!(split[1].numa_nodes.as_ref().map(|nodes| nodes[0].id), Some(1));
+        assert_eq!(split[1].packages.as_deref(), Some(&packages[1..]));
+
+        // Unknown package layout: the system-wide view is the only package
+        info.packages = None;
+        let whole = info.split_packages();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].package_id, None);
+        assert_eq!(whole[0].logical_cores, 8);
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_has_feature_named() {
+        use crate::cpu::X86Features;
+
+        let info = CpuInfo {
+            features: X86Features::SSE2 | X86Features::AVX2,
+            ..CpuInfo::default()
+        };
+        assert_eq!(info.has_feature_named("avx2"), Some(true));
+        assert_eq!(info.has_feature_named("SSE4.2"), Some(false));
+        assert_eq!(info.has_feature_named("warp_drive"), None);
+        // Lists and empty names are not a single feature
+        assert_eq!(info.has_feature_named("sse2,avx2"), None);
+        assert_eq!(info.has_feature_named(""), None);
+        assert!(crate::cpu::feature_names().contains(&"AVX2"));
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_supports_all() {
+        use crate::cpu::X86Features;
+
+        let build = CpuInfo {
+            features: X86Features::SSE2 | X86Features::AVX2,
+            ..CpuInfo::default()
+        };
+        let target = CpuInfo {
+            features: X86Features::SSE2 | X86Features::AVX2 | X86Features::AVX512F,
+            ..CpuInfo::default()
+        };
+
+        // Exact match
+        assert!(build.supports_all(build.features));
+        assert!(build.missing_features(build.features).is_empty());
+        assert!(build.is_feature_superset_of(&build));
+
+        // Superset
+        assert!(target.supports_all(build.features));
+        assert!(target.is_feature_superset_of(&build));
+        assert!(target.supports_all(X86Features::empty()));
+
+        // Missing feature
+        assert!(!build.supports_all(target.features));
+        assert!(!build.is_feature_superset_of(&target));
+        assert_eq!(build.missing_features(target.features), X86Features::AVX512F);
+        assert_eq!(
+            build.missing_features(X86Features::AVX512F | X86Features::FMA | X86Features::SSE2),
+            X86Features::AVX512F | X86Features::FMA
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_max_vector_bytes() {
+        use crate::cpu::X86Features;
+
+        let with = |features| CpuInfo {
+            features,
+            ..CpuInfo::default()
+        };
+        assert_eq!(
+            with(X86Features::SSE2 | X86Features::AVX2 | X86Features::AVX512F).max_vector_bytes(),
+            64
+        );
+        assert_eq!(with(X86Features::SSE2 | X86Features::AVX2).max_vector_bytes(), 32);
+        assert_eq!(with(X86Features::SSE2 | X86Features::AVX).max_vector_bytes(), 32);
+        assert_eq!(with(X86Features::SSE2 | X86Features::SSE4_2).max_vector_bytes(), 16);
+        assert_eq!(with(X86Features::empty()).max_vector_bytes(), 8);
+
+        // AVX-512 masked by the OS does not count
+        let masked = CpuInfo {
+            available_but_disabled: X86Features::AVX512F,
+            ..with(X86Features::SSE2 | X86Features::AVX2)
+        };
+        assert_eq!(masked.max_vector_bytes(), 32);
+    }
+
+    #[test]
+    #[cfg(target_arch = "aarch64")]
+    fn test_max_vector_bytes() {
+        use crate::cpu::ArmFeatures;
+
+        let with = |features, sve_vector_bits| CpuInfo {
+            features,
+            sve_vector_bits,
+            ..CpuInfo::default()
+        };
+        // A64FX: 512-bit SVE
+        assert_eq!(
+            with(ArmFeatures::NEON | ArmFeatures::SVE, Some(512)).max_vector_bytes(),
+            64
+        );
+        // Neoverse V1: 256-bit SVE
+        assert_eq!(
+            with(ArmFeatures::NEON | ArmFeatures::SVE, Some(256)).max_vector_bytes(),
+            32
+        );
+        // SVE of unknown length is at least as wide as NEON
+        assert_eq!(with(ArmFeatures::NEON | ArmFeatures::SVE, None).max_vector_bytes(), 16);
+        assert_eq!(
+            with(ArmFeatures::NEON | ArmFeatures::ASIMD, None).max_vector_bytes(),
+            16
+        );
+        assert_eq!(with(ArmFeatures::empty(), None).max_vector_bytes(), 8);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_from_json_roundtrip() {
+        let info = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD Ryzen 9 5950X 16-Core Processor".to_string(),
+            physical_cores: 16,
+            logical_cores: 32,
+            cache_sizes: [Some(32), Some(32), Some(512), Some(32768)],
+            frequency: Frequency::new(Some(3400.0), Some(4900.0), None),
+            ..CpuInfo::default()
+        };
+
+        let restored = CpuInfo::from_json(&info.to_json_pretty().unwrap()).unwrap();
+        assert!(info.diff(&restored).is_empty());
+        assert_eq!(restored.brand_string, info.brand_string);
+
+        // A minimal snapshot from an older release still loads
+        let minimal = r#"{"vendor":"Intel","brand_string":"x","version":{"family":6,"model":158,"stepping":10},
+            "physical_cores":6,"logical_cores":12,"frequency":{"base":null,"max":null,"current":null},
+            "cache_sizes":[null,null,null,null],"features":""}"#;
+        assert_eq!(CpuInfo::from_json(minimal).unwrap().logical_cores, 12);
+
+        let newer = r#"{"schema_version":99}"#;
+        assert!(
+            CpuInfo::from_json(newer)
+                .unwrap_err()
+                .to_string()
+                .contains("schema version 99")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_json_has_schema_version() {
+        let json: serde_json::Value = serde_json::from_str(&CpuInfo::default().to_json().unwrap()).unwrap();
+        assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+        assert!(json.get("vendor").is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_json_has_version() {
+        let info = CpuInfo {
+            version: Version::new(0x19, 0x21, 2),
+            ..CpuInfo::default()
+        };
+        let json: serde_json::Value = serde_json::from_str(&info.to_json().unwrap()).unwrap();
+        assert_eq!(json["version"]["family"], 0x19);
+        assert_eq!(json["version"]["model"], 0x21);
+        assert_eq!(json["version"]["stepping"], 2);
+    }
+
+    #[test]
+    fn test_summary_with_frequency() {
+        let info = CpuInfo {
+            vendor: Vendor::Intel,
+            brand_string: "Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz".to_string(),
+            physical_cores: 6,
+            logical_cores: 12,
+            frequency: Frequency::new(Some(2600.0), Some(4500.0), None),
+            ..CpuInfo::default()
+        };
+        assert_eq!(info.summary(), "Intel Core i7-9750H (6C/12T) @ 2.6GHz");
+    }
+
+    #[test]
+    fn test_summary_without_frequency() {
+        let info = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD Ryzen 9 5950X 16-Core Processor".to_string(),
+            physical_cores: 16,
+            logical_cores: 32,
+            ..CpuInfo::default()
+        };
+        assert_eq!(info.summary(), "AMD Ryzen 9 5950X (16C/32T)");
+
+        // Brand without the vendor name gets it prefixed; max is used when base is unknown
+        let info = CpuInfo {
+            vendor: Vendor::ARM,
+            brand_string: "Cortex-A76".to_string(),
+            physical_cores: 4,
+            logical_cores: 4,
+            frequency: Frequency::new(None, Some(2400.0), None),
+            ..CpuInfo::default()
+        };
+        assert_eq!(info.summary(), "ARM Cortex-A76 (4C/4T) @ 2.4GHz");
+    }
+
+    #[test]
+    fn test_normalize_brand() {
+        let cases = [
+            ("Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz", "Intel Core i7-9750H"),
+            (
+                "Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz",
+                "Intel Xeon Platinum 8375C",
+            ),
+            (
+                "Intel(R) Core(TM)2 Duo CPU     E8400  @ 3.00GHz",
+                "Intel Core 2 Duo E8400",
+            ),
+            ("12th Gen Intel(R) Core(TM) i9-12900K", "12th Gen Intel Core i9-12900K"),
+            ("AMD Ryzen 9 5950X 16-Core Processor            ", "AMD Ryzen 9 5950X"),
+            ("AMD EPYC 7763 64-Core Processor", "AMD EPYC 7763"),
+            (
+                "AMD Ryzen 7 PRO 4750U with Radeon Graphics",
+                "AMD Ryzen 7 PRO 4750U with Radeon Graphics",
+            ),
+            ("Apple M2 Pro", "Apple M2 Pro"),
+            ("", ""),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(normalize_brand(raw, false), expected, "{raw:?}");
+        }
+
+        assert_eq!(
+            normalize_brand("Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz", true),
+            "Intel Core i7-9750H @ 2.60GHz"
+        );
+        assert_eq!(normalize_brand("Apple M1", true), "Apple M1");
+    }
+
+    #[test]
+    fn test_normalized_brand_keeps_raw_string() {
+        let raw = "Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz";
+        let info = CpuInfo {
+            brand_string: raw.to_string(),
+            ..CpuInfo::default()
+        };
+        assert_eq!(info.normalized_brand(), "Intel Core i7-9750H");
+        assert_eq!(info.brand_string, raw);
+    }
+
+    #[test]
+    fn test_summary_default() {
+        assert_eq!(CpuInfo::default().summary(), "Unknown");
+    }
+
+    #[test]
+    fn test_cpu_info_display() {
+        let mut info = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD Ryzen 7 5800X".to_string(),
+            physical_cores: 8,
+            logical_cores: 16,
+            cache_sizes: [Some(32), Some(32), Some(512), None],
+            ..CpuInfo::default()
+        };
+        assert_eq!(
+            info.to_string(),
+            "CPU Information:\nVendor: AMD\nModel: AMD Ryzen 7 5800X\nCores: 8 physical, 16 logical\n\
+             L1i Cache: 32 KB\nL1d Cache: 32 KB\nL2 Cache: 512 KB"
+        );
+
+        info.frequency.max = Some(4700.0);
+        assert!(
+            info.to_string()
+                .ends_with("\nFrequency: Base: Unknown, Current: Unknown, Max: 4700.00 MHz")
+        );
+    }
+
     #[test]
     fn test_vendor_display() {
         assert_eq!(Vendor::Intel.to_string(), "Intel");
         assert_eq!(Vendor::AMD.to_string(), "AMD");
         assert_eq!(Vendor::ARM.to_string(), "ARM");
         assert_eq!(Vendor::Unknown.to_string(), "Unknown");
+        assert_eq!(Vendor::Hypervisor("KVM".to_string()).to_string(), "KVM (virtual)");
+    }
+
+    #[test]
+    fn test_vendor_from_cpuid() {
+        assert_eq!(Vendor::from_cpuid("GenuineIntel", ""), Vendor::Intel);
+        assert_eq!(Vendor::from_cpuid("HygonGenuine", ""), Vendor::Hygon);
+        assert_eq!(Vendor::from_cpuid("CentaurHauls", ""), Vendor::VIA);
+        assert_eq!(Vendor::from_cpuid("  Shanghai  ", ""), Vendor::Zhaoxin);
+        assert_eq!(
+            Vendor::from_cpuid("Microsoft Hv", ""),
+            Vendor::Hypervisor("Hyper-V".to_string())
+        );
+    }
+
+    #[test]
+    fn test_vendor_from_str_round_trips_display() {
+        let vendors = [
+            Vendor::Intel,
+            Vendor::AMD,
+            Vendor::ARM,
+            Vendor::Apple,
+            Vendor::Hygon,
+            Vendor::VIA,
+            Vendor::Zhaoxin,
+            Vendor::Qualcomm,
+            Vendor::NVIDIA,
+            Vendor::Hypervisor("KVM".to_string()),
+            Vendor::Unknown,
+        ];
+        for vendor in vendors {
+            assert_eq!(vendor.to_string().parse::<Vendor>(), Ok(vendor));
+        }
+    }
+
+    #[test]
+    fn test_vendor_from_str_cpuid_strings() {
+        assert_eq!("GenuineIntel".parse(), Ok(Vendor::Intel));
+        assert_eq!("authenticamd".parse(), Ok(Vendor::AMD));
+        assert_eq!("HYGONGENUINE".parse(), Ok(Vendor::Hygon));
+        assert_eq!("CentaurHauls".parse(), Ok(Vendor::VIA));
+        assert_eq!("  Shanghai  ".parse(), Ok(Vendor::Zhaoxin));
+        assert_eq!("vmwarevmware".parse(), Ok(Vendor::Hypervisor("VMware".to_string())));
+    }
+
+    #[test]
+    fn test_vendor_from_str_garbage() {
+        assert_eq!("".parse(), Ok(Vendor::Unknown));
+        assert_eq!("Cyrix".parse(), Ok(Vendor::Unknown));
+        assert_eq!("Intel Core i7".parse(), Ok(Vendor::Unknown));
+    }
+
+    #[test]
+    fn test_vendor_from_brand_fallback() {
+        assert_eq!(Vendor::from_cpuid("", "AMD EPYC 7763 64-Core Processor"), Vendor::AMD);
+        assert_eq!(Vendor::from_cpuid("????????????", "Mystery CPU"), Vendor::Unknown);
     }
 
     #[test]
@@ -194,4 +1610,41 @@ mod tests {
         assert_eq!(info.logical_cores, 0);
         assert_eq!(info.cache_sizes, [None; 4]);
     }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_to_json_round_trip() {
+        let info = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "Mock AMD CPU".to_string(),
+            ..CpuInfo::default()
+        };
+        let compact = info.to_json().unwrap();
+        let pretty = info.to_json_pretty().unwrap();
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains('\n'));
+
+        let parsed: CpuInfo = serde_json::from_str(&compact).unwrap();
+        assert_eq!(parsed.vendor, Vendor::AMD);
+        assert_eq!(parsed.brand_string, "Mock AMD CPU");
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_to_yaml() {
+        let yaml = CpuInfo::default().to_yaml().unwrap();
+        assert!(yaml.contains("vendor: Unknown"));
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_to_toml_with_missing_caches() {
+        let info = CpuInfo {
+            cache_sizes: [Some(32), Some(48), Some(2048), None],
+            ..CpuInfo::default()
+        };
+        let toml = info.to_toml().unwrap();
+        assert!(toml.contains("vendor = \"Unknown\""));
+        assert!(toml.contains("2048"));
+    }
 }
@@ -0,0 +1,147 @@
+//! Loaded microcode revision detection.
+//!
+//! The revision tells whether a machine has the updates behind mitigations
+//! such as those for Spectre and MDS. It is only exposed by the operating
+//! system: on Linux through sysfs, `/proc/cpuinfo` or the MSR device, and on
+//! Windows through the registry. Unprivileged or unsupported systems report
+//! no revision rather than an error.
+
+use crate::cpu::Vendor;
+
+/// Model-specific register holding the microcode revision (`IA32_BIOS_SIGN_ID`, AMD `PATCH_LEVEL`)
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+const MSR_MICROCODE_REVISION: u64 = 0x8B;
+
+/// Detect the microcode revision loaded on the first CPU.
+///
+/// On Linux the sources are, in order: `/sys/devices/system/cpu/cpu0/microcode/version`,
+/// the `microcode` line of `/proc/cpuinfo`, then MSR 0x8B through `/dev/cpu/0/msr`
+/// (root and the `msr` module only). On Windows it is the registry's `Update Revision`.
+/// Returns `None` elsewhere, when none of these can be read, or without the `std` feature.
+#[must_use]
+pub fn detect_microcode(vendor: &Vendor) -> Option<u64> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        detect_microcode_linux(vendor)
+    }
+
+    #[cfg(all(target_os = "windows", feature = "windows"))]
+    {
+        detect_microcode_registry(vendor)
+    }
+
+    #[cfg(not(any(
+        all(target_os = "linux", feature = "std"),
+        all(target_os = "windows", feature = "windows")
+    )))]
+    {
+        let _ = vendor;
+        None
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn detect_microcode_linux(vendor: &Vendor) -> Option<u64> {
+    if let Some(revision) = std::fs::read_to_string("/sys/devices/system/cpu/cpu0/microcode/version")
+        .ok()
+        .and_then(|version| parse_revision(&version))
+    {
+        debug_log!("Microcode revision from sysfs: {revision:#x}");
+        return Some(revision);
+    }
+
+    if let Some(revision) = std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| proc_cpuinfo_microcode(&cpuinfo))
+    {
+        debug_log!("Microcode revision from /proc/cpuinfo: {revision:#x}");
+        return Some(revision);
+    }
+
+    let msr = read_msr(MSR_MICROCODE_REVISION)?;
+    debug_log!("Microcode revision from MSR {MSR_MICROCODE_REVISION:#x}: {msr:#x}");
+    Some(revision_from_msr(msr, vendor))
+}
+
+/// Read a model-specific register of CPU 0 through the `msr` driver.
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn read_msr(register: u64) -> Option<u64> {
+    use std::os::unix::fs::FileExt;
+
+    let file = std::fs::File::open("/dev/cpu/0/msr").ok()?;
+    let mut value = [0u8; 8];
+    file.read_exact_at(&mut value, register).ok()?;
+    Some(u64::from_le_bytes(value))
+}
+
+/// `Update Revision` under `HKLM\HARDWARE\DESCRIPTION\System\CentralProcessor\0`, an 8-byte MSR 0x8B image.
+#[cfg(all(target_os = "windows", feature = "windows"))]
+fn detect_microcode_registry(vendor: &Vendor) -> Option<u64> {
+    let key = windows_registry::LOCAL_MACHINE
+        .open(r"HARDWARE\DESCRIPTION\System\CentralProcessor\0")
+        .ok()?;
+    let value = key.get_value("Update Revision").ok()?;
+    let bytes = <[u8; 8]>::try_from(&value[..]).ok()?;
+    let revision = revision_from_msr(u64::from_le_bytes(bytes), vendor);
+    debug_log!("Registry reports microcode revision {revision:#x}");
+    (revision != 0).then_some(revision)
+}
+
+/// The revision held in a raw MSR 0x8B value: the high half on Intel, the low half on AMD.
+#[cfg_attr(
+    not(any(
+        all(target_os = "linux", feature = "std"),
+        all(target_os = "windows", feature = "windows")
+    )),
+    allow(dead_code)
+)]
+fn revision_from_msr(msr: u64, vendor: &Vendor) -> u64 {
+    match vendor {
+        Vendor::AMD | Vendor::Hygon => msr & 0xFFFF_FFFF,
+        _ => msr >> 32,
+    }
+}
+
+/// Parse a revision written in hex, with or without a `0x` prefix (e.g. `0xf4\n`).
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+fn parse_revision(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    u64::from_str_radix(digits, 16).ok()
+}
+
+/// The `microcode` value of the first processor in `/proc/cpuinfo`, e.g. `microcode\t: 0xf4`.
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+fn proc_cpuinfo_microcode(cpuinfo: &str) -> Option<u64> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "microcode")
+        .and_then(|(_, value)| parse_revision(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc_cpuinfo_microcode() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nstepping\t: 10\nmicrocode\t: 0xf4\n\
+                       cpu MHz\t\t: 2600.000\n\nprocessor\t: 1\nmicrocode\t: 0xf0\n";
+        assert_eq!(proc_cpuinfo_microcode(cpuinfo), Some(0xf4));
+        // AMD prints the full patch level
+        assert_eq!(proc_cpuinfo_microcode("microcode\t: 0xa201016\n"), Some(0x0a20_1016));
+        assert_eq!(proc_cpuinfo_microcode("processor\t: 0\n"), None);
+        assert_eq!(proc_cpuinfo_microcode("microcode\t: n/a\n"), None);
+    }
+
+    #[test]
+    fn test_revision_from_msr() {
+        assert_eq!(revision_from_msr(0x0000_00f4_0000_0000, &Vendor::Intel), 0xf4);
+        assert_eq!(revision_from_msr(0x0a20_1016, &Vendor::AMD), 0x0a20_1016);
+        assert_eq!(parse_revision("F4"), Some(0xf4));
+    }
+}
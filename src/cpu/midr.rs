@@ -0,0 +1,258 @@
+//! ARM Main ID Register (`MIDR_EL1`) decoding.
+//!
+//! On Linux the kernel exposes the MIDR fields of every core in
+//! `/proc/cpuinfo` (`CPU implementer`, `CPU part`, ...). This module parses
+//! those fields and maps the implementer and part codes to human-readable
+//! vendor and core names. It is architecture-independent so the lookup tables
+//! can be unit-tested on any host.
+
+use crate::cpu::info::{Vendor, Version};
+
+/// Decoded `MIDR_EL1` fields for a single core type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Midr {
+    /// Implementer code (e.g. `0x41` = Arm Ltd)
+    pub implementer: u8,
+    /// Major revision (`rN` in `rNpM`)
+    pub variant: u8,
+    /// Architecture code (`0xF` = defined by ID registers)
+    pub architecture: u8,
+    /// Primary part number (e.g. `0xd0b` = Cortex-A76)
+    pub part: u16,
+    /// Minor revision (`pM` in `rNpM`)
+    pub revision: u8,
+}
+
+impl Midr {
+    /// Vendor corresponding to the implementer code.
+    ///
+    /// Implementers without a dedicated `Vendor` variant are reported as
+    /// `Vendor::ARM`, since the core is still an Arm-architecture design.
+    #[must_use]
+    pub fn vendor(&self) -> Vendor {
+        match self.implementer {
+            0x61 => Vendor::Apple,
+            0x51 => Vendor::Qualcomm,
+            0x4e => Vendor::NVIDIA,
+            _ => Vendor::ARM,
+        }
+    }
+
+    /// Readable core name, falling back to the raw part number for unknown parts.
+    #[must_use]
+    pub fn core_name(&self) -> String {
+        part_name(self.implementer, self.part).map_or_else(|| format!("Part 0x{:03x}", self.part), str::to_string)
+    }
+
+    /// Map the MIDR fields onto the x86-shaped `Version`.
+    ///
+    /// `family` carries the implementer code and `stepping` the revision.
+    /// `Version` fields are 8 bits wide, so `model` keeps only the low byte of
+    /// the 12-bit part number; the dropped nibble is `0xd` for every Arm Ltd
+    /// core and is recoverable from the implementer for the rest.
+    #[must_use]
+    pub fn version(&self) -> Version {
+        Version {
+            family: self.implementer,
+            model: self.part.to_le_bytes()[0],
+            stepping: self.revision,
+        }
+    }
+}
+
+/// Brand string for a set of core types, e.g. "ARM Cortex-A55 + Cortex-A76".
+///
+/// Returns `None` when `cores` is empty.
+#[must_use]
+pub fn brand_string(cores: &[Midr]) -> Option<String> {
+    let first = cores.first()?;
+    let names: Vec<String> = cores.iter().map(Midr::core_name).collect();
+    Some(format!("{} {}", implementer_name(first.implementer), names.join(" + ")))
+}
+
+/// Readable name of an implementer code, falling back to hex for unknown codes.
+#[must_use]
+pub fn implementer_name(implementer: u8) -> String {
+    let name = match implementer {
+        0x41 => "ARM",
+        0x42 => "Broadcom",
+        0x43 => "Cavium",
+        0x46 => "Fujitsu",
+        0x48 => "HiSilicon",
+        0x4e => "NVIDIA",
+        0x50 => "APM",
+        0x51 => "Qualcomm",
+        0x53 => "Samsung",
+        0x61 => "Apple",
+        0x6d => "Microsoft",
+        0xc0 => "Ampere",
+        _ => return format!("Implementer 0x{implementer:02x}"),
+    };
+    name.to_string()
+}
+
+/// Readable core name for a known (implementer, part) pair.
+#[must_use]
+pub fn part_name(implementer: u8, part: u16) -> Option<&'static str> {
+    let name = match (implementer, part) {
+        // Arm Ltd
+        (0x41, 0xd03) => "Cortex-A53",
+        (0x41, 0xd04) => "Cortex-A35",
+        (0x41, 0xd05) => "Cortex-A55",
+        (0x41, 0xd07) => "Cortex-A57",
+        (0x41, 0xd08) => "Cortex-A72",
+        (0x41, 0xd09) => "Cortex-A73",
+        (0x41, 0xd0a) => "Cortex-A75",
+        (0x41, 0xd0b) => "Cortex-A76",
+        (0x41, 0xd0c) => "Neoverse N1",
+        (0x41, 0xd0d) => "Cortex-A77",
+        (0x41, 0xd40) => "Neoverse V1",
+        (0x41, 0xd41) => "Cortex-A78",
+        (0x41, 0xd44) => "Cortex-X1",
+        (0x41, 0xd46) => "Cortex-A510",
+        (0x41, 0xd47) => "Cortex-A710",
+        (0x41, 0xd48) => "Cortex-X2",
+        (0x41, 0xd49) => "Neoverse N2",
+        (0x41, 0xd4b) => "Cortex-A78C",
+        (0x41, 0xd4d) => "Cortex-A715",
+        (0x41, 0xd4e) => "Cortex-X3",
+        (0x41, 0xd4f) => "Neoverse V2",
+        (0x41, 0xd80) => "Cortex-A520",
+        (0x41, 0xd81) => "Cortex-A720",
+        (0x41, 0xd82) => "Cortex-X4",
+        // Qualcomm
+        (0x51, 0x800) => "Kryo 2xx Gold",
+        (0x51, 0x801) => "Kryo 2xx Silver",
+        (0x51, 0x802) => "Kryo 3xx Gold",
+        (0x51, 0x803) => "Kryo 3xx Silver",
+        (0x51, 0x804) => "Kryo 4xx Gold",
+        (0x51, 0x805) => "Kryo 4xx Silver",
+        (0x51, 0x001) => "Oryon",
+        // NVIDIA
+        (0x4e, 0x003) => "Denver 2",
+        (0x4e, 0x004) => "Carmel",
+        // Apple (as seen by Linux on Asahi)
+        (0x61, 0x022) => "Icestorm (M1)",
+        (0x61, 0x023) => "Firestorm (M1)",
+        (0x61, 0x032) => "Blizzard (M2)",
+        (0x61, 0x033) => "Avalanche (M2)",
+        // Ampere
+        (0xc0, 0xac3) => "Ampere-1",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Parse a `0x`-prefixed or decimal integer as found in `/proc/cpuinfo`.
+fn parse_int(value: &str) -> Option<u32> {
+    let value = value.trim();
+    value
+        .strip_prefix("0x")
+        .map_or_else(|| value.parse().ok(), |hex| u32::from_str_radix(hex, 16).ok())
+}
+
+/// Parse every core's MIDR fields from `/proc/cpuinfo` content.
+///
+/// Returns one entry per distinct core type, in order of first appearance,
+/// so big.LITTLE systems yield one `Midr` per cluster type.
+#[must_use]
+pub fn parse_proc_cpuinfo(cpuinfo: &str) -> Vec<Midr> {
+    let mut cores: Vec<Midr> = Vec::new();
+
+    // Each processor is a blank-line separated block
+    for block in cpuinfo.split("\n\n") {
+        let field = |name: &str| {
+            block
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(key, _)| key.trim() == name)
+                .and_then(|(_, value)| parse_int(value))
+        };
+
+        let (Some(implementer), Some(part)) = (field("CPU implementer"), field("CPU part")) else {
+            continue;
+        };
+
+        // Field widths are fixed by the MIDR_EL1 layout, so truncation cannot lose data.
+        #[allow(clippy::cast_possible_truncation)]
+        let midr = Midr {
+            implementer: implementer as u8,
+            variant: field("CPU variant").unwrap_or(0) as u8,
+            architecture: field("CPU architecture").unwrap_or(0) as u8,
+            part: part as u16,
+            revision: field("CPU revision").unwrap_or(0) as u8,
+        };
+
+        if !cores
+            .iter()
+            .any(|c| c.implementer == midr.implementer && c.part == midr.part)
+        {
+            cores.push(midr);
+        }
+    }
+
+    cores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RK3588_CPUINFO: &str = "\
+processor\t: 0
+BogoMIPS\t: 48.00
+CPU implementer\t: 0x41
+CPU architecture: 8
+CPU variant\t: 0x2
+CPU part\t: 0xd05
+CPU revision\t: 0
+
+processor\t: 4
+BogoMIPS\t: 48.00
+CPU implementer\t: 0x41
+CPU architecture: 8
+CPU variant\t: 0x4
+CPU part\t: 0xd0b
+CPU revision\t: 0
+";
+
+    #[test]
+    fn test_parse_big_little() {
+        let cores = parse_proc_cpuinfo(RK3588_CPUINFO);
+        assert_eq!(cores.len(), 2);
+        assert_eq!(cores[0].core_name(), "Cortex-A55");
+        assert_eq!(cores[1].core_name(), "Cortex-A76");
+        assert_eq!(cores[1].variant, 4);
+        assert_eq!(cores[1].architecture, 8);
+        assert_eq!(brand_string(&cores).as_deref(), Some("ARM Cortex-A55 + Cortex-A76"));
+        assert_eq!(cores[1].version().model, 0x0b);
+    }
+
+    #[test]
+    fn test_vendor_mapping() {
+        let midr = |implementer| Midr {
+            implementer,
+            variant: 0,
+            architecture: 8,
+            part: 0,
+            revision: 0,
+        };
+        assert_eq!(midr(0x41).vendor(), Vendor::ARM);
+        assert_eq!(midr(0x61).vendor(), Vendor::Apple);
+        assert_eq!(midr(0x51).vendor(), Vendor::Qualcomm);
+        assert_eq!(midr(0x4e).vendor(), Vendor::NVIDIA);
+    }
+
+    #[test]
+    fn test_unknown_fallbacks() {
+        assert_eq!(implementer_name(0x99), "Implementer 0x99");
+        let midr = Midr {
+            implementer: 0x41,
+            variant: 0,
+            architecture: 8,
+            part: 0xfff,
+            revision: 0,
+        };
+        assert_eq!(midr.core_name(), "Part 0xfff");
+    }
+}
@@ -0,0 +1,145 @@
+//! Speculative-execution vulnerability status.
+//!
+//! Linux lists every CPU vulnerability it knows of (Spectre, Meltdown, MDS,
+//! ...) as a file under `/sys/devices/system/cpu/vulnerabilities`, named for
+//! the vulnerability and holding the kernel's verdict for this machine: "Not
+//! affected", "Mitigation: ..." or "Vulnerable". Other platforms report none.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Directory the kernel lists the vulnerabilities in
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+const VULNERABILITIES_DIR: &str = "/sys/devices/system/cpu/vulnerabilities";
+
+/// One vulnerability and how the running kernel deals with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mitigation {
+    /// Vulnerability name as the kernel spells it, e.g. `spectre_v2`
+    pub vulnerability: String,
+    /// Kernel status, e.g. "Not affected", "Mitigation: PTI" or "Vulnerable"
+    pub status: String,
+}
+
+/// How exposed the CPU is to a vulnerability, judged from its status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Exposure {
+    /// The CPU is not affected
+    NotAffected,
+    /// Affected, but mitigated; also statuses the kernel cannot judge ("Unknown: ...")
+    Mitigated,
+    /// Affected and not (fully) mitigated
+    Vulnerable,
+}
+
+impl Mitigation {
+    /// How exposed the CPU is, from the leading word of the status.
+    #[must_use]
+    pub fn exposure(&self) -> Exposure {
+        let status = self.status.trim_start();
+        if status.starts_with("Not affected") {
+            Exposure::NotAffected
+        } else if status.starts_with("Vulnerable") {
+            Exposure::Vulnerable
+        } else {
+            Exposure::Mitigated
+        }
+    }
+}
+
+/// Detect the kernel's vulnerability and mitigation report.
+///
+/// Read from `/sys/devices/system/cpu/vulnerabilities`, sorted by name. Empty
+/// on non-Linux platforms, on kernels too old to list them, or without the
+/// `std` feature.
+#[must_use]
+pub fn detect_mitigations() -> Vec<Mitigation> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        let mitigations = mitigations_in(std::path::Path::new(VULNERABILITIES_DIR));
+        debug_log!("{} vulnerabilities listed", mitigations.len());
+        mitigations
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        Vec::new()
+    }
+}
+
+/// The vulnerabilities listed as files in `dir`, sorted by name.
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn mitigations_in(dir: &std::path::Path) -> Vec<Mitigation> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut mitigations: Vec<Mitigation> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let vulnerability = entry.file_name().into_string().ok()?;
+            let status = std::fs::read_to_string(entry.path()).ok()?.trim().to_string();
+            (!status.is_empty()).then_some(Mitigation { vulnerability, status })
+        })
+        .collect();
+    mitigations.sort_by(|a, b| a.vulnerability.cmp(&b.vulnerability));
+    mitigations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mitigation(status: &str) -> Mitigation {
+        Mitigation {
+            vulnerability: "spectre_v2".to_string(),
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_exposure() {
+        assert_eq!(mitigation("Not affected").exposure(), Exposure::NotAffected);
+        assert_eq!(
+            mitigation("Mitigation: Enhanced / Automatic IBRS; IBPB: conditional").exposure(),
+            Exposure::Mitigated
+        );
+        assert_eq!(
+            mitigation("Unknown: Dependent on hypervisor status").exposure(),
+            Exposure::Mitigated
+        );
+        assert_eq!(
+            mitigation("Vulnerable: Clear CPU buffers attempted, no microcode").exposure(),
+            Exposure::Vulnerable
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    fn test_mitigations_in() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, status) in [
+            (
+                "spectre_v2",
+                "Mitigation: Retpolines; IBPB: conditional; STIBP: disabled\n",
+            ),
+            ("meltdown", "Vulnerable\n"),
+            ("l1tf", "Not affected\n"),
+            ("empty", "\n"),
+        ] {
+            std::fs::write(dir.path().join(name), status).unwrap();
+        }
+
+        let mitigations = mitigations_in(dir.path());
+        let names: Vec<&str> = mitigations.iter().map(|m| m.vulnerability.as_str()).collect();
+        assert_eq!(names, ["l1tf", "meltdown", "spectre_v2"]);
+        assert_eq!(mitigations[0].status, "Not affected");
+        assert_eq!(mitigations[1].exposure(), Exposure::Vulnerable);
+        assert_eq!(
+            mitigations[2].status,
+            "Mitigation: Retpolines; IBPB: conditional; STIBP: disabled"
+        );
+
+        assert!(mitigations_in(&dir.path().join("missing")).is_empty());
+    }
+}
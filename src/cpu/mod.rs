@@ -3,24 +3,66 @@
 //! This module provides functionality for detecting and querying CPU information,
 //! including feature detection, frequency measurement, and vendor identification.
 
+pub mod cache;
 pub mod cpuid;
+pub mod diff;
+pub mod fields;
 pub mod flags;
+pub mod governor;
+pub mod hybrid;
 pub mod info;
+pub mod microcode;
+pub mod midr;
+pub mod mitigations;
+pub mod numa;
+pub mod package;
 pub mod perf;
+pub mod tsc;
 pub mod uarch;
 
 // Conditionally include the frequency module based on feature flag
 #[cfg(feature = "frequency")]
 pub mod frequency;
 
+// Conditionally include the thermal module based on feature flag
+#[cfg(feature = "thermal")]
+pub mod thermal;
+
+// Conditionally include the power module based on feature flag
+#[cfg(feature = "power")]
+pub mod power;
+
 // Re-export commonly used types and functions
-pub use cpuid::{CacheInfo, CacheType, CpuidError, CpuidWrapper};
-pub use flags::{ArmFeatures, FeatureError, X86Features, detect_features};
-pub use info::{CpuError, CpuInfo, Vendor, Version};
+pub use cache::detect_sysfs_caches;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub use cpuid::CpuidLeafDump;
+pub use cpuid::{
+    AddressSizes, AmdTopology, CacheInfo, CacheTopology, CacheType, CpuidError, CpuidFrequencyInfo, CpuidReader,
+    CpuidRegisters, CpuidWrapper, HypervisorInfo, NativeCpuidReader, PmuInfo,
+};
+pub use diff::FieldDiff;
+pub use fields::{FieldValue, field_names};
+pub use flags::{ArmFeatures, FeatureError, X86Features, detect_features, feature_names, parse_features};
+pub use governor::{detect_epp, detect_governor};
+pub use hybrid::{CoreKind, detect_hybrid_caches, detect_hybrid_features, merge_core_features};
+pub use info::{ClusterFrequency, CpuError, CpuInfo, Endianness, Vendor, Version, normalize_brand};
+pub use microcode::detect_microcode;
+pub use midr::Midr;
+pub use mitigations::{Exposure, Mitigation, detect_mitigations};
+pub use numa::{NumaNode, detect_numa_nodes};
+pub use package::{Package, detect_packages};
+pub use tsc::{detect_kernel_invariant_tsc, detect_kernel_tsc_frequency};
 pub use uarch::{Microarch, detect_uarch};
 
 // Conditionally re-export the frequency module
 #[cfg(feature = "frequency")]
-pub use frequency::{Frequency, detect_frequency};
+pub use frequency::{
+    Frequency, FrequencyMonitor, detect_cluster_frequencies, detect_frequency, detect_per_core_frequencies,
+    estimate_frequency_by_benchmark,
+};
 #[cfg(not(feature = "frequency"))]
 pub use info::Frequency;
+#[cfg(feature = "power")]
+pub use power::detect_power;
+#[cfg(feature = "thermal")]
+pub use thermal::detect_temperature;
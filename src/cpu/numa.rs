@@ -0,0 +1,151 @@
+//! NUMA topology detection.
+//!
+//! Maps logical CPUs to NUMA nodes so memory-bound workloads can be profiled
+//! against the real memory layout. Only Linux exposes this information in a
+//! portable way (via sysfs); other platforms report no NUMA data.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A single NUMA node and the logical CPUs attached to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NumaNode {
+    /// Node identifier as reported by the OS (e.g. `0` for `node0`)
+    pub id: u32,
+    /// Logical CPU ids belonging to this node, in ascending order
+    pub cpus: Vec<u32>,
+}
+
+impl fmt::Display for NumaNode {
+    /// Formats the CPU list in the kernel's compact range notation (e.g. `0-3,8-11`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_cpulist(f, &self.cpus)
+    }
+}
+
+/// Write ascending CPU ids in the kernel's compact range notation (e.g. `0-3,8-11`).
+pub(crate) fn write_cpulist(f: &mut fmt::Formatter<'_>, cpus: &[u32]) -> fmt::Result {
+    let mut first = true;
+    let mut iter = cpus.iter().copied().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end += 1;
+            iter.next();
+        }
+        if !first {
+            write!(f, ",")?;
+        }
+        first = false;
+        if start == end {
+            write!(f, "{start}")?;
+        } else {
+            write!(f, "{start}-{end}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Ascending CPU ids in the kernel's compact range notation, as a `String`.
+#[cfg_attr(not(all(feature = "display", feature = "frequency")), allow(dead_code))]
+pub(crate) fn cpulist(cpus: &[u32]) -> String {
+    struct List<'a>(&'a [u32]);
+    impl fmt::Display for List<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write_cpulist(f, self.0)
+        }
+    }
+    List(cpus).to_string()
+}
+
+/// Parse a kernel CPU list string such as `0-3,8,10-11` into individual CPU ids.
+///
+/// Malformed entries are skipped rather than failing the whole list, since a
+/// partially readable topology is still more useful than none.
+#[must_use]
+pub fn parse_cpulist(list: &str) -> Vec<u32> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        if let Some((start, end)) = part.split_once('-') {
+            if let (Ok(start), Ok(end)) = (start.trim().parse::<u32>(), end.trim().parse::<u32>()) {
+                cpus.extend(start..=end);
+            }
+        } else if let Ok(cpu) = part.trim().parse::<u32>() {
+            cpus.push(cpu);
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    cpus
+}
+
+/// Detect the NUMA node layout of the current system.
+///
+/// Reads `/sys/devices/system/node/node*/cpulist` on Linux. A single-node
+/// machine yields a one-element vector; `None` is returned when sysfs is
+/// unavailable, on non-Linux platforms, or when the `std` feature is disabled.
+#[must_use]
+pub fn detect_numa_nodes() -> Option<Vec<NumaNode>> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        detect_numa_nodes_linux()
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        None
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "std"))]
+fn detect_numa_nodes_linux() -> Option<Vec<NumaNode>> {
+    let entries = std::fs::read_dir("/sys/devices/system/node").ok()?;
+
+    let mut nodes: Vec<NumaNode> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let id = name.to_str()?.strip_prefix("node")?.parse::<u32>().ok()?;
+            let list = std::fs::read_to_string(entry.path().join("cpulist")).ok()?;
+            Some(NumaNode {
+                id,
+                cpus: parse_cpulist(&list),
+            })
+        })
+        .collect();
+
+    if nodes.is_empty() {
+        return None;
+    }
+
+    nodes.sort_by_key(|node| node.id);
+    Some(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpulist_ranges() {
+        assert_eq!(parse_cpulist("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpulist("5"), vec![5]);
+        assert!(parse_cpulist("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_cpulist_skips_garbage() {
+        assert_eq!(parse_cpulist("0-1,x,4"), vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn test_numa_node_display() {
+        let node = NumaNode {
+            id: 0,
+            cpus: vec![0, 1, 2, 3, 8, 10, 11],
+        };
+        assert_eq!(node.to_string(), "0-3,8,10-11");
+    }
+}
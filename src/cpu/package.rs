@@ -0,0 +1,157 @@
+//! Processor package (socket) topology.
+//!
+//! Groups logical CPUs by the physical package they sit in, so multi-socket
+//! systems can be reported one socket at a time. Linux lists the package of
+//! every logical CPU as `physical id` in `/proc/cpuinfo`; when that field is
+//! missing, x86 packages are derived from the `apicid` instead. Other
+//! platforms report no package layout.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single processor package and the logical CPUs it contains
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Package {
+    /// Package identifier as reported by the OS (`physical id`)
+    pub id: u32,
+    /// Logical CPU ids in this package, in ascending order
+    pub cpus: Vec<u32>,
+    /// Physical cores in this package (distinct `core id` values)
+    pub physical_cores: u32,
+}
+
+/// Detect the processor packages of the current system.
+///
+/// A single-socket machine yields a one-element vector; `None` is returned
+/// when `/proc/cpuinfo` is unreadable or does not identify the package of
+/// every CPU, on non-Linux platforms, or when the `std` feature is disabled.
+#[must_use]
+pub fn detect_packages() -> Option<Vec<Package>> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        let apic_shift = crate::cpu::CpuidWrapper::new()
+            .logical_processors_per_package()
+            .map(apic_package_shift);
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        let apic_shift = None;
+
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+        parse_packages(&cpuinfo, apic_shift)
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        None
+    }
+}
+
+/// Bits of an x86 APIC ID below the package ID, for `per_package` logical processors.
+///
+/// Each package reserves a power-of-two block of APIC IDs, so the package ID
+/// is the APIC ID shifted right by the block's width.
+#[cfg_attr(
+    not(all(
+        target_os = "linux",
+        feature = "std",
+        any(target_arch = "x86", target_arch = "x86_64")
+    )),
+    allow(dead_code)
+)]
+fn apic_package_shift(per_package: u32) -> u32 {
+    per_package.next_power_of_two().trailing_zeros()
+}
+
+/// Group the processors of `/proc/cpuinfo` content into packages.
+///
+/// The package comes from `physical id`, else from `apicid >> apic_shift`.
+/// `None` when any processor has neither, as on most Arm kernels, since a
+/// partial grouping would misreport the sockets.
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+fn parse_packages(cpuinfo: &str, apic_shift: Option<u32>) -> Option<Vec<Package>> {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let mut packages: BTreeMap<u32, (Vec<u32>, BTreeSet<u32>)> = BTreeMap::new();
+    for block in cpuinfo.split("\n\n") {
+        let field = |name: &str| {
+            block
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(key, _)| key.trim() == name)
+                .and_then(|(_, value)| value.trim().parse::<u32>().ok())
+        };
+        let Some(cpu) = field("processor") else {
+            continue;
+        };
+        let id = field("physical id").or_else(|| Some(field("apicid")? >> apic_shift?))?;
+
+        let (cpus, cores) = packages.entry(id).or_default();
+        cpus.push(cpu);
+        if let Some(core) = field("core id") {
+            cores.insert(core);
+        }
+    }
+
+    if packages.is_empty() {
+        return None;
+    }
+
+    Some(
+        packages
+            .into_iter()
+            .map(|(id, (mut cpus, cores))| {
+                cpus.sort_unstable();
+                // Without core ids, assume no SMT rather than guess a sibling count
+                let physical = if cores.is_empty() { cpus.len() } else { cores.len() };
+                Package {
+                    id,
+                    physical_cores: u32::try_from(physical).unwrap_or(u32::MAX),
+                    cpus,
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_packages_by_physical_id() {
+        // Two sockets of two cores with SMT, interleaved as the kernel lists them
+        let cpuinfo = "processor\t: 0\nphysical id\t: 0\ncore id\t\t: 0\n\n\
+                       processor\t: 1\nphysical id\t: 1\ncore id\t\t: 0\n\n\
+                       processor\t: 2\nphysical id\t: 0\ncore id\t\t: 1\n\n\
+                       processor\t: 3\nphysical id\t: 1\ncore id\t\t: 1\n\n\
+                       processor\t: 4\nphysical id\t: 0\ncore id\t\t: 0\n\n\
+                       processor\t: 5\nphysical id\t: 1\ncore id\t\t: 0\n\n\
+                       processor\t: 6\nphysical id\t: 0\ncore id\t\t: 1\n\n\
+                       processor\t: 7\nphysical id\t: 1\ncore id\t\t: 1\n";
+        let packages = parse_packages(cpuinfo, None).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].id, 0);
+        assert_eq!(packages[0].cpus, vec![0, 2, 4, 6]);
+        assert_eq!(packages[0].physical_cores, 2);
+        assert_eq!(packages[1].cpus, vec![1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_parse_packages_by_apic_id() {
+        // 16 logical processors per package: APIC IDs 0-15 and 16-31
+        let shift = apic_package_shift(16);
+        assert_eq!(shift, 4);
+        let cpuinfo = "processor\t: 0\napicid\t\t: 0\n\nprocessor\t: 1\napicid\t\t: 16\n\n\
+                       processor\t: 2\napicid\t\t: 2\n";
+        let packages = parse_packages(cpuinfo, Some(shift)).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].cpus, vec![0, 2]);
+        assert_eq!(packages[1].id, 1);
+        assert_eq!(packages[1].physical_cores, 1);
+
+        // Neither field, or APIC IDs without a package width: no grouping
+        assert_eq!(parse_packages("processor\t: 0\nBogoMIPS\t: 50.00\n", Some(4)), None);
+        assert_eq!(parse_packages(cpuinfo, None), None);
+    }
+}
@@ -0,0 +1,137 @@
+//! CPU package power readout.
+//!
+//! Energy counters (Intel RAPL, and AMD's RAPL-compatible MSRs) only report
+//! accumulated microjoules, so the average power is derived from two readings
+//! taken a sampling interval apart. Detection therefore blocks for that
+//! interval, which is why it is not part of the default [`crate::detect`] path.
+//!
+//! Counters are frequently unavailable — inside VMs, on unsupported hardware,
+//! or because recent kernels restrict `energy_uj` to root — so absence is
+//! reported as `None` rather than as an error.
+
+use std::time::Duration;
+
+/// Default gap between the two energy readings.
+///
+/// Long enough for the counter (updated roughly every millisecond) to give a
+/// stable average, short enough not to noticeably delay the output.
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Package energy counter exposed by the powercap framework (Intel, and AMD since Linux 5.8)
+#[cfg(target_os = "linux")]
+const LINUX_RAPL_PACKAGE_ZONE: &str = "/sys/class/powercap/intel-rapl:0";
+
+/// Measures the average CPU package power in watts over `interval`
+///
+/// Blocks the calling thread for `interval`. Returns `None` when the platform
+/// exposes no readable energy counter.
+#[must_use]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub fn detect_power(interval: Duration) -> Option<f32> {
+    #[cfg(target_os = "linux")]
+    return detect_power_linux(interval);
+
+    #[cfg(not(target_os = "linux"))]
+    return None;
+}
+
+#[cfg(target_os = "linux")]
+fn detect_power_linux(interval: Duration) -> Option<f32> {
+    let counter = EnergyCounter::find()?;
+    let before = counter.read()?;
+    std::thread::sleep(interval);
+    let after = counter.read()?;
+
+    #[allow(clippy::cast_precision_loss)]
+    let joules = energy_delta_uj(before, after, counter.max_uj) as f64 / 1_000_000.0;
+    #[allow(clippy::cast_possible_truncation)]
+    Some((joules / interval.as_secs_f64()) as f32)
+}
+
+/// A cumulative package energy counter in microjoules.
+#[cfg(target_os = "linux")]
+struct EnergyCounter {
+    path: std::path::PathBuf,
+    /// Value at which the counter wraps back to zero, if known
+    max_uj: Option<u64>,
+}
+
+#[cfg(target_os = "linux")]
+impl EnergyCounter {
+    /// Locate a readable package energy counter: powercap RAPL first, then the `amd_energy` hwmon driver.
+    fn find() -> Option<Self> {
+        let zone = std::path::Path::new(LINUX_RAPL_PACKAGE_ZONE);
+        let rapl = Self {
+            path: zone.join("energy_uj"),
+            max_uj: read_u64(&zone.join("max_energy_range_uj")),
+        };
+        if rapl.read().is_some() {
+            return Some(rapl);
+        }
+        debug_log!("RAPL powercap counter unavailable, trying amd_energy hwmon");
+        Self::find_amd_energy()
+    }
+
+    /// The socket counter of the `amd_energy` driver (labelled `Esocket0`).
+    fn find_amd_energy() -> Option<Self> {
+        use std::fs::read_to_string;
+
+        let hwmon = std::fs::read_dir("/sys/class/hwmon")
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| read_to_string(path.join("name")).is_ok_and(|name| name.trim() == "amd_energy"))?;
+
+        let label = std::fs::read_dir(&hwmon).ok()?.flatten().find_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let index = name.strip_prefix("energy")?.strip_suffix("_label")?.to_string();
+            (read_to_string(entry.path()).ok()?.trim() == "Esocket0").then_some(index)
+        })?;
+
+        let counter = Self {
+            path: hwmon.join(format!("energy{label}_input")),
+            max_uj: None,
+        };
+        counter.read().map(|_| counter)
+    }
+
+    fn read(&self) -> Option<u64> {
+        read_u64(&self.path)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_u64(path: &std::path::Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Energy consumed between two counter readings, allowing for a single wrap-around.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn energy_delta_uj(before: u64, after: u64, max_uj: Option<u64>) -> u64 {
+    if after >= before {
+        after - before
+    } else {
+        max_uj.map_or(0, |max| max - before + after)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_energy_delta() {
+        assert_eq!(energy_delta_uj(1_000, 6_000, None), 5_000);
+        // Counter wrapped past its maximum between the two readings
+        assert_eq!(energy_delta_uj(9_000, 1_000, Some(10_000)), 2_000);
+        // Wrapped with an unknown range: report nothing rather than garbage
+        assert_eq!(energy_delta_uj(9_000, 1_000, None), 0);
+    }
+
+    #[test]
+    fn test_detect_power_never_panics() {
+        if let Some(watts) = detect_power(Duration::from_millis(10)) {
+            assert!(watts >= 0.0);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! CPU temperature readout.
+//!
+//! Reports the current package (or hottest core) temperature in degrees
+//! Celsius. Sensors are frequently missing — inside VMs, containers, or on
+//! hardware without a supported driver — so absence is reported as `None`
+//! rather than as an error.
+
+use crate::Error;
+
+/// hwmon driver names that report CPU package or die temperatures, in order of preference.
+#[cfg(target_os = "linux")]
+const LINUX_CPU_HWMON_DRIVERS: &[&str] = &["coretemp", "k10temp", "zenpower", "cpu_thermal", "soc_thermal"];
+
+/// Detects the current CPU temperature using platform-specific methods
+///
+/// Returns `Ok(None)` when no usable sensor is exposed by the platform.
+///
+/// # Errors
+///
+/// Returns `Error::Thermal` if the platform sensor interface itself cannot be initialised.
+pub fn detect_temperature() -> Result<Option<f32>, Error> {
+    #[cfg(target_os = "linux")]
+    return Ok(detect_temperature_linux());
+
+    #[cfg(target_os = "windows")]
+    return detect_temperature_windows();
+
+    #[cfg(target_os = "macos")]
+    return Ok(detect_temperature_macos());
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    return Ok(None);
+}
+
+#[cfg(target_os = "linux")]
+fn detect_temperature_linux() -> Option<f32> {
+    use std::fs::read_to_string;
+
+    let entries = std::fs::read_dir("/sys/class/hwmon").ok()?;
+
+    // Collect (driver name, hwmon path) pairs so the preferred driver wins
+    // regardless of the kernel's enumeration order.
+    let sensors: Vec<(String, std::path::PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = read_to_string(entry.path().join("name")).ok()?;
+            Some((name.trim().to_string(), entry.path()))
+        })
+        .collect();
+
+    LINUX_CPU_HWMON_DRIVERS.iter().find_map(|driver| {
+        let (_, path) = sensors.iter().find(|(name, _)| name == driver)?;
+        // temp1 is the package / Tctl sensor for all supported drivers; values are millidegrees.
+        let millideg = read_to_string(path.join("temp1_input"))
+            .ok()?
+            .trim()
+            .parse::<f32>()
+            .ok()?;
+        Some(millideg / 1000.0)
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn detect_temperature_windows() -> Result<Option<f32>, Error> {
+    #[cfg(feature = "windows")]
+    {
+        use serde::Deserialize;
+        use wmi::{COMLibrary, WMIConnection};
+
+        // Define a structure that matches the MSAcpi_ThermalZoneTemperature WMI class
+        #[allow(non_camel_case_types, non_snake_case)]
+        #[derive(Deserialize, Debug)]
+        struct MSAcpi_ThermalZoneTemperature {
+            CurrentTemperature: Option<u32>,
+        }
+
+        let com_lib =
+            COMLibrary::new().map_err(|e| Error::Thermal(format!("Failed to initialise COM library: {e}")))?;
+
+        // The thermal zone class lives in root\WMI rather than the default root\cimv2 namespace.
+        let Ok(wmi_con) = WMIConnection::with_namespace_path("root\\WMI", com_lib) else {
+            return Ok(None);
+        };
+
+        // Access to this class usually requires elevation; treat denial as "no sensor".
+        let Ok(zones) = wmi_con.query::<MSAcpi_ThermalZoneTemperature>() else {
+            return Ok(None);
+        };
+
+        // Values are in tenths of a kelvin; report the hottest zone.
+        #[allow(clippy::cast_precision_loss)]
+        let hottest = zones
+            .iter()
+            .filter_map(|zone| zone.CurrentTemperature)
+            .map(|deci_kelvin| deci_kelvin as f32 / 10.0 - 273.15)
+            .reduce(f32::max);
+
+        Ok(hottest)
+    }
+
+    #[cfg(not(feature = "windows"))]
+    {
+        Ok(None)
+    }
+}
+
+// macOS has no public sysctl carrying a Celsius die temperature: Apple Silicon
+// and Intel Macs alike only expose sensors through the SMC via IOKit, which
+// would require unsafe FFI. Report "no sensor" until a safe binding exists.
+#[cfg(target_os = "macos")]
+fn detect_temperature_macos() -> Option<f32> {
+    None
+}
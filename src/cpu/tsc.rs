@@ -0,0 +1,80 @@
+//! Time Stamp Counter details from the operating system.
+//!
+//! CPUID leaf 0x15 is absent on AMD and older Intel parts and often zeroed
+//! under hypervisors, but Linux calibrates the TSC at boot either way.
+//! Kernels carrying the `tsc_freq_khz` patch publish the calibrated rate in
+//! sysfs, and every kernel lists `constant_tsc` and `nonstop_tsc` among the
+//! `/proc/cpuinfo` flags when the counter is invariant. Other platforms
+//! report neither.
+
+/// Detect the TSC frequency in MHz the kernel calibrated at boot.
+///
+/// Read from `/sys/devices/system/cpu/cpu0/tsc_freq_khz`, which only some
+/// kernels provide. Returns `None` otherwise, on non-Linux platforms, or
+/// without the `std` feature.
+#[must_use]
+pub fn detect_kernel_tsc_frequency() -> Option<f64> {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        let mhz = std::fs::read_to_string("/sys/devices/system/cpu/cpu0/tsc_freq_khz")
+            .ok()
+            .and_then(|khz| khz.trim().parse::<f64>().ok())
+            .filter(|&khz| khz > 0.0)
+            .map(|khz| khz / 1000.0);
+        debug_log!("sysfs tsc_freq_khz: {mhz:?} MHz");
+        mhz
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        None
+    }
+}
+
+/// Whether the kernel reports an invariant TSC.
+///
+/// True when the first `flags` line of `/proc/cpuinfo` has both
+/// `constant_tsc` and `nonstop_tsc`, Linux's spelling of the invariant-TSC
+/// bit. False when either is missing, on non-Linux platforms, or without the
+/// `std` feature.
+#[must_use]
+pub fn detect_kernel_invariant_tsc() -> bool {
+    #[cfg(all(target_os = "linux", feature = "std"))]
+    {
+        std::fs::read_to_string("/proc/cpuinfo").is_ok_and(|cpuinfo| cpuinfo_invariant_tsc(&cpuinfo))
+    }
+
+    #[cfg(not(all(target_os = "linux", feature = "std")))]
+    {
+        false
+    }
+}
+
+/// Whether the first `flags` line of `/proc/cpuinfo` content lists `constant_tsc` and `nonstop_tsc`.
+#[cfg_attr(not(all(target_os = "linux", feature = "std")), allow(dead_code))]
+fn cpuinfo_invariant_tsc(cpuinfo: &str) -> bool {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "flags")
+        .is_some_and(|(_, flags)| {
+            let mut flags = flags.split_whitespace();
+            flags.clone().any(|flag| flag == "constant_tsc") && flags.any(|flag| flag == "nonstop_tsc")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpuinfo_invariant_tsc() {
+        let invariant = "processor\t: 0\nflags\t\t: fpu tsc msr constant_tsc arch_perfmon nonstop_tsc cpuid\n";
+        assert!(cpuinfo_invariant_tsc(invariant));
+
+        // A VM that keeps the rate constant but may stop it in deep C-states
+        let constant_only = "flags\t\t: fpu tsc constant_tsc\nbugs\t\t: spectre_v1\n";
+        assert!(!cpuinfo_invariant_tsc(constant_only));
+        assert!(!cpuinfo_invariant_tsc("processor\t: 0\n"));
+    }
+}
@@ -1,14 +1,19 @@
 //! CPU microarchitecture detection.
 //!
 //! Lookup tables mapping (vendor, family, model) → microarchitecture name and process node.
+//!
+//! None of this is reported by the hardware: it is a static database of
+//! known parts, so new or unusual models are simply not recognised.
 
 use crate::cpu::info::Vendor;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// CPU microarchitecture identifier
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Microarch {
     // Intel (NetBurst)
     Willamette,
@@ -57,37 +62,43 @@ pub enum Microarch {
 }
 
 impl Microarch {
-    /// Process technology in nm, if known
+    /// Manufacturing process, e.g. "Intel 7" or "7 nm", if known.
+    ///
+    /// Intel parts since Alder Lake use Intel's own node names; older ones and
+    /// AMD use the nanometre figure the vendor quoted. This is one figure per
+    /// microarchitecture, so a core built on several nodes reports the one of
+    /// its first (desktop or server) die.
     #[must_use]
-    pub fn process_nm(&self) -> Option<u32> {
+    pub fn process_node(&self) -> Option<&'static str> {
         match self {
-            Microarch::Willamette => Some(180),
-            Microarch::Northwood | Microarch::K8 => Some(130),
-            Microarch::Prescott => Some(90),
-            Microarch::Nehalem => Some(45),
-            Microarch::K10 => Some(65),
-            Microarch::Bobcat => Some(40),
-            Microarch::Westmere | Microarch::SandyBridge | Microarch::Bulldozer | Microarch::Piledriver => Some(32),
-            Microarch::IvyBridge | Microarch::Haswell => Some(22),
-            Microarch::Steamroller | Microarch::Excavator | Microarch::Jaguar => Some(28),
+            Microarch::Willamette => Some("180 nm"),
+            Microarch::Northwood | Microarch::K8 => Some("130 nm"),
+            Microarch::Prescott => Some("90 nm"),
+            Microarch::Nehalem => Some("45 nm"),
+            Microarch::K10 => Some("65 nm"),
+            Microarch::Bobcat => Some("40 nm"),
+            Microarch::Westmere | Microarch::SandyBridge | Microarch::Bulldozer | Microarch::Piledriver => {
+                Some("32 nm")
+            },
+            Microarch::IvyBridge | Microarch::Haswell => Some("22 nm"),
+            Microarch::Steamroller | Microarch::Excavator | Microarch::Jaguar => Some("28 nm"),
             Microarch::Broadwell
             | Microarch::Skylake
             | Microarch::KabyLake
             | Microarch::CometLake
             | Microarch::Zen
-            | Microarch::Hygon => Some(14),
-            Microarch::CannonLake
-            | Microarch::IceLake
-            | Microarch::TigerLake
-            | Microarch::AlderLake
-            | Microarch::RaptorLake
-            | Microarch::SapphireRapids => Some(10),
-            Microarch::ZenPlus => Some(12),
-            Microarch::Zen2 | Microarch::Zen3 => Some(7),
-            Microarch::Zen3Plus => Some(6),
-            Microarch::Zen4 | Microarch::AppleM1 | Microarch::AppleM2 => Some(5), // N5/N4P
-            Microarch::MeteorLake | Microarch::Zen5 => Some(4),
-            Microarch::GraniteRapids | Microarch::AppleM3 | Microarch::AppleM4 => Some(3), // N3E
+            | Microarch::Hygon => Some("14 nm"),
+            Microarch::CannonLake | Microarch::IceLake => Some("10 nm"),
+            Microarch::TigerLake => Some("10 nm SuperFin"),
+            Microarch::AlderLake | Microarch::RaptorLake | Microarch::SapphireRapids => Some("Intel 7"),
+            Microarch::MeteorLake => Some("Intel 4"),
+            Microarch::GraniteRapids => Some("Intel 3"),
+            Microarch::ZenPlus => Some("12 nm"),
+            Microarch::Zen2 | Microarch::Zen3 => Some("7 nm"),
+            Microarch::Zen3Plus => Some("6 nm"),
+            Microarch::Zen4 | Microarch::AppleM1 | Microarch::AppleM2 => Some("5 nm"), // N5/N4P
+            Microarch::Zen5 => Some("4 nm"),
+            Microarch::AppleM3 | Microarch::AppleM4 => Some("3 nm"), // N3E
         }
     }
 
@@ -156,7 +167,8 @@ impl fmt::Display for Microarch {
 pub fn detect_uarch(vendor: &Vendor, family: u8, model: u8) -> Option<Microarch> {
     match vendor {
         Vendor::Intel => detect_intel_uarch(family, model),
-        Vendor::AMD => detect_amd_uarch(family, model),
+        // Hygon Dhyana shares AMD's family numbering (family 0x18)
+        Vendor::AMD | Vendor::Hygon => detect_amd_uarch(family, model),
         _ => None,
     }
 }
@@ -273,6 +285,11 @@ mod tests {
         assert_eq!(detect_uarch(&Vendor::AMD, 25, 0x21), Some(Microarch::Zen3));
     }
 
+    #[test]
+    fn test_amd_zen4() {
+        assert_eq!(detect_uarch(&Vendor::AMD, 0x19, 0x61), Some(Microarch::Zen4));
+    }
+
     #[test]
     fn test_amd_zen5() {
         assert_eq!(detect_uarch(&Vendor::AMD, 26, 0), Some(Microarch::Zen5));
@@ -283,6 +300,11 @@ mod tests {
         assert_eq!(detect_uarch(&Vendor::AMD, 24, 0), Some(Microarch::Hygon));
     }
 
+    #[test]
+    fn test_hygon_vendor() {
+        assert_eq!(detect_uarch(&Vendor::Hygon, 24, 0), Some(Microarch::Hygon));
+    }
+
     #[test]
     fn test_microarch_display() {
         assert_eq!(Microarch::Zen3.to_string(), "Zen 3");
@@ -291,11 +313,19 @@ mod tests {
     }
 
     #[test]
-    fn test_process_nm() {
-        assert_eq!(Microarch::Zen3.process_nm(), Some(7));
-        assert_eq!(Microarch::Zen5.process_nm(), Some(4));
-        assert_eq!(Microarch::Haswell.process_nm(), Some(22));
-        assert_eq!(Microarch::RaptorLake.process_nm(), Some(10));
+    fn test_process_node() {
+        assert_eq!(Microarch::Zen3.process_node(), Some("7 nm"));
+        assert_eq!(Microarch::Zen5.process_node(), Some("4 nm"));
+        assert_eq!(Microarch::Haswell.process_node(), Some("22 nm"));
+        assert_eq!(Microarch::RaptorLake.process_node(), Some("Intel 7"));
+        assert_eq!(Microarch::SapphireRapids.process_node(), Some("Intel 7"));
+        assert_eq!(Microarch::MeteorLake.process_node(), Some("Intel 4"));
+    }
+
+    #[test]
+    fn test_unknown_model_returns_none() {
+        assert_eq!(detect_uarch(&Vendor::Intel, 6, 0x01), None);
+        assert_eq!(detect_uarch(&Vendor::AMD, 0x30, 0), None);
     }
 
     #[test]
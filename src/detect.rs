@@ -0,0 +1,58 @@
+//! Top-level detection entry point.
+//!
+//! [`CpuInfo::new`] quietly leaves fields empty when an optional source
+//! (cache leaves, frequency, temperature sensor) fails. [`detect`] runs the
+//! same detection but also reports those partial failures, so callers can
+//! explain a missing field instead of just showing nothing.
+
+use crate::Error;
+use crate::cpu::CpuInfo;
+
+/// Result of [`detect`]: the CPU information plus any non-fatal problems
+#[derive(Debug, Clone)]
+pub struct Detection {
+    /// The detected CPU information
+    pub info: CpuInfo,
+    /// Human-readable descriptions of sub-detections that failed or fell back,
+    /// e.g. "cache detection failed, cache sizes unavailable: ..."
+    pub warnings: Vec<String>,
+}
+
+/// Detect the current CPU, collecting partial failures as warnings.
+///
+/// # Errors
+///
+/// Returns `Error::Cpu` if the core detection fails (for example CPUID is
+/// unavailable).
+pub fn detect() -> Result<Detection, Error> {
+    let mut warnings = Vec::new();
+
+    #[cfg(target_arch = "x86_64")]
+    let info = crate::arch::x86_64::detect_cpu_reporting(&mut warnings)?;
+
+    #[cfg(target_arch = "aarch64")]
+    let info = crate::arch::aarch64::detect_cpu_reporting(&mut warnings)?;
+
+    #[cfg(target_arch = "wasm32")]
+    let info = crate::arch::wasm32::detect_cpu_reporting(&mut warnings)?;
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "wasm32")))]
+    let info = crate::arch::generic::detect_cpu_reporting(&mut warnings)?;
+
+    Ok(Detection { info, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    fn test_detect_matches_cpu_info_new() {
+        let detection = detect().unwrap();
+        let info = CpuInfo::new().unwrap();
+        assert_eq!(detection.info.brand_string, info.brand_string);
+        assert_eq!(detection.info.vendor, info.vendor);
+        assert!(detection.warnings.iter().all(|w| !w.is_empty()));
+    }
+}
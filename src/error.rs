@@ -22,19 +22,34 @@ pub enum Error {
     #[error("Unsupported architecture")]
     UnsupportedArchitecture,
 
+    #[error("Unknown field '{0}' (valid fields: {1}, or a feature name such as avx2)")]
+    UnknownField(String, String),
+
     // Feature-specific errors
     #[cfg(feature = "frequency")]
     #[error("Frequency detection error: {0}")]
     Frequency(String),
 
+    #[cfg(feature = "thermal")]
+    #[error("Temperature detection error: {0}")]
+    Thermal(String),
+
     #[cfg(feature = "display")]
     #[error("Display error: {0}")]
     Display(String),
 
-    #[cfg(feature = "json")]
+    #[cfg(any(feature = "json", feature = "toml"))]
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[cfg(feature = "yaml")]
+    #[error("YAML serialization error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[cfg(feature = "toml")]
+    #[error("TOML serialization error: {0}")]
+    Toml(#[from] toml::ser::Error),
+
     #[cfg(feature = "config")]
     #[error("Configuration error: {0}")]
     Config(String),
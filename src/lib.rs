@@ -2,14 +2,39 @@
 //!
 //! This library provides functionality to fetch and display detailed CPU information
 //! across different architectures and platforms.
+//!
+//! Detection works with any feature set, down to `--no-default-features
+//! --features cpu`; `Serialize`/`Deserialize` for [`CpuInfo`] and the types
+//! it holds need the `serde` feature (implied by `json`, `yaml` and `toml`).
+//!
+//! ```
+//! let info = cpufetch_rs::CpuInfo::new().expect("detection never fails outright");
+//! assert!(!info.brand_string.is_empty());
+//!
+//! #[cfg(feature = "json")]
+//! assert!(serde_json::to_string(&info).unwrap().contains("brand_string"));
+//! ```
+
+#[macro_use]
+mod logging;
 
 pub mod arch;
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "config")]
+pub mod config;
 pub mod cpu;
+mod detect;
 pub mod error;
 pub mod printer;
+mod provider;
 pub mod utils;
 
 // Re-export commonly used types
-pub use cpu::info::{CpuError, CpuInfo, Frequency, Vendor, Version};
+pub use cpu::info::{CpuError, CpuInfo, Frequency, JSON_SCHEMA_VERSION, Vendor, Version};
+pub use detect::{Detection, detect};
 pub use error::Error;
+pub use provider::{CpuInfoProvider, NativeProvider};
+
+#[cfg(feature = "cli")]
+pub use cli::run;
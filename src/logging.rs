@@ -0,0 +1,28 @@
+//! Crate-internal logging macros.
+//!
+//! Detection code logs which CPUID leaves were read and which fallbacks
+//! fired. The macros forward to the `log` crate when the `log` feature is
+//! enabled and compile to nothing otherwise, so call sites need no `cfg`.
+//! The arguments are still type-checked in both configurations.
+
+/// Emit a `log::debug!` record when the `log` feature is enabled.
+macro_rules! debug_log {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+/// Emit a `log::trace!` record when the `log` feature is enabled.
+// Only the x86 CPUID paths trace today
+#[cfg_attr(not(any(target_arch = "x86", target_arch = "x86_64")), allow(unused_macros))]
+macro_rules! trace_log {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::trace!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
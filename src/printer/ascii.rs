@@ -14,33 +14,47 @@ pub fn max_width(ascii_art: &str) -> usize {
     ascii_art.lines().map(|line| line.chars().count()).max().unwrap_or(0)
 }
 
-/// Frame an ASCII art string with a Unicode box border.
+/// Frame an ASCII art string with a box border.
 ///
-/// Each content line is padded to the same width so that the right border
-/// aligns consistently.  Returns a string where every line has the same
-/// visual width: `content_max_width + 2 * padding + 2` (two border chars).
+/// The border is drawn with Unicode box-drawing characters, or with `+`, `-`
+/// and `|` when `unicode` is false, for terminals without UTF-8. Each content
+/// line is padded to the same width so that the right border aligns
+/// consistently.  Returns a string where every line has the same visual
+/// width: `content_max_width + 2 * padding + 2` (two border chars).
 #[cfg(feature = "display")]
 #[allow(dead_code)]
-pub fn frame(ascii_art: &str, padding: usize) -> String {
+pub fn frame(ascii_art: &str, padding: usize, unicode: bool) -> String {
+    let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = if unicode {
+        ['┌', '┐', '└', '┘', '─', '│']
+    } else {
+        ['+', '+', '+', '+', '-', '|']
+    };
     let lines: Vec<&str> = ascii_art.lines().collect();
     // Visual width of the widest content line (ASCII-only logos, so len == chars)
     let max_w = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
     let inner_width = max_w + padding * 2;
+    let rule = horizontal.to_string().repeat(inner_width);
 
     let mut result = String::new();
 
     // Top border
-    let _ = writeln!(result, "┌{}┐", "─".repeat(inner_width));
+    let _ = writeln!(result, "{top_left}{rule}{top_right}");
 
     // Content with left and right padding to reach consistent width
     for line in &lines {
         let line_chars = line.chars().count();
         let pad_right = max_w - line_chars + padding;
-        let _ = writeln!(result, "│{}{}{}│", " ".repeat(padding), line, " ".repeat(pad_right));
+        let _ = writeln!(
+            result,
+            "{vertical}{}{}{}{vertical}",
+            " ".repeat(padding),
+            line,
+            " ".repeat(pad_right)
+        );
     }
 
     // Bottom border
-    let _ = write!(result, "└{}┘", "─".repeat(inner_width));
+    let _ = write!(result, "{bottom_left}{rule}{bottom_right}");
 
     result
 }
@@ -52,7 +66,6 @@ pub fn frame(ascii_art: &str, padding: usize) -> String {
 /// This is necessary when the left string contains ANSI colour codes (which
 /// inflate `.len()` but have zero visual width in a terminal).
 #[cfg(feature = "display")]
-#[allow(dead_code)]
 pub fn combine_horizontal(left: &str, right: &str, left_visual_width: usize, spacing: usize) -> String {
     let left_lines: Vec<&str> = left.lines().collect();
     let right_lines: Vec<&str> = right.lines().collect();
@@ -76,3 +89,22 @@ pub fn combine_horizontal(left: &str, right: &str, left_visual_width: usize, spa
 
     result
 }
+
+#[cfg(all(test, feature = "display"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_ascii_has_no_multibyte_chars() {
+        let framed = frame("/\\\n||||", 1, false);
+        assert!(framed.is_ascii(), "{framed}");
+        assert_eq!(framed, "+------+\n| /\\   |\n| |||| |\n+------+");
+    }
+
+    #[test]
+    fn test_frame_unicode_widths_align() {
+        let framed = frame("ab\nabcd", 2, true);
+        assert!(framed.starts_with('┌') && framed.ends_with('┘'));
+        assert!(framed.lines().all(|line| line.chars().count() == 10), "{framed}");
+    }
+}
@@ -4,13 +4,130 @@
 //! CPU information in the terminal.
 
 use colored::control::set_override;
+use std::ffi::OsStr;
 
-/// Set up the terminal display based on command-line options.
+/// Set up the terminal display based on command-line options and the
+/// environment, returning whether output should be coloured.
+///
+/// `--no-color` wins, then `NO_COLOR` (see <https://no-color.org>), then
+/// `CLICOLOR_FORCE`; with none of them set, output to a file (`to_file`) is
+/// plain and otherwise `colored`'s own terminal detection decides.
+#[cfg(feature = "display")]
+pub fn setup_display(no_color: bool, to_file: bool) -> bool {
+    let no_color_env = std::env::var_os("NO_COLOR");
+    let force_env = std::env::var_os("CLICOLOR_FORCE");
+    match color_override(no_color, no_color_env.as_deref(), force_env.as_deref()) {
+        Some(enabled) => {
+            set_override(enabled);
+            enabled
+        },
+        None if to_file => {
+            set_override(false);
+            false
+        },
+        None => colored::control::SHOULD_COLORIZE.should_colorize(),
+    }
+}
+
+/// Explicit colour choice implied by the flag and environment, if any.
+#[cfg(feature = "display")]
+fn color_override(no_color_flag: bool, no_color_env: Option<&OsStr>, force_env: Option<&OsStr>) -> Option<bool> {
+    if no_color_flag || no_color_env.is_some_and(|value| !value.is_empty()) {
+        Some(false)
+    } else if force_env.is_some_and(|value| !value.is_empty() && value != "0") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Width of the terminal in columns, or 80 when stdout is not a terminal.
+#[cfg(feature = "display")]
+pub fn get_terminal_width() -> usize {
+    crossterm::terminal::size().map_or(80, |(width, _)| usize::from(width))
+}
+
+/// Visible width of a line that may contain ANSI colour or OSC 8 hyperlink escape sequences.
 #[cfg(feature = "display")]
-pub fn setup_display(no_color: bool) {
-    if no_color {
-        set_override(false);
+pub fn visible_len(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' && chars.peek() == Some(&']') {
+            // Skip the OSC sequence up to its BEL or ESC-backslash terminator
+            while let Some(c) = chars.next() {
+                if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+        } else if ch == '\x1b' {
+            // Skip the CSI sequence up to and including its final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
     }
+    width
+}
+
+/// Render `rows` under `header` as a plain table, one string per line.
+///
+/// Every column is padded to its widest cell (ignoring colour codes), columns
+/// are separated by two spaces, and a dashed rule sits under the header.
+#[cfg(feature = "display")]
+pub fn table(header: &[&str], rows: &[Vec<String>]) -> Vec<String> {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| visible_len(cell))
+                .chain([header[column].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let render = |cells: &mut dyn Iterator<Item = &str>| {
+        let line: Vec<String> = cells
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - visible_len(cell))))
+            .collect();
+        line.join("  ").trim_end().to_string()
+    };
+
+    let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut lines = vec![
+        render(&mut header.iter().copied()),
+        render(&mut rule.iter().map(String::as_str)),
+    ];
+    lines.extend(rows.iter().map(|row| render(&mut row.iter().map(String::as_str))));
+    lines
+}
+
+/// Wrap `text` in an OSC 8 escape sequence so supporting terminals render it as a link to `url`.
+#[cfg(feature = "display")]
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Whether stdout is a terminal likely to understand OSC 8 hyperlinks.
+///
+/// Terminals without support generally ignore the sequence, but pipes and
+/// `TERM=dumb` would show it verbatim.
+#[cfg(feature = "display")]
+pub fn supports_hyperlinks() -> bool {
+    use std::io::IsTerminal;
+
+    hyperlinks_supported(std::io::stdout().is_terminal(), std::env::var_os("TERM").as_deref())
+}
+
+#[cfg(feature = "display")]
+fn hyperlinks_supported(is_terminal: bool, term: Option<&OsStr>) -> bool {
+    is_terminal && term != Some(OsStr::new("dumb"))
 }
 
 /// Format a key-value display line with aligned columns.
@@ -28,3 +145,80 @@ pub fn format_kv(label: &str, value: &str, label_width: usize) -> String {
     let labelled = format!("{label}:");
     format!("{labelled:<label_width$}  {value}")
 }
+
+#[cfg(test)]
+#[cfg(feature = "display")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_override_precedence() {
+        let set = Some(OsStr::new("1"));
+        let empty = Some(OsStr::new(""));
+
+        assert_eq!(color_override(false, None, None), None);
+        assert_eq!(color_override(true, None, set), Some(false));
+        assert_eq!(color_override(false, set, None), Some(false));
+        assert_eq!(color_override(false, set, set), Some(false));
+        assert_eq!(color_override(false, None, set), Some(true));
+        // Empty values count as unset
+        assert_eq!(color_override(false, empty, None), None);
+        assert_eq!(color_override(false, empty, set), Some(true));
+        assert_eq!(color_override(false, None, Some(OsStr::new("0"))), None);
+    }
+
+    #[test]
+    fn test_no_color_flag_disables_colored() {
+        assert!(!setup_display(true, false));
+        assert!(!colored::control::SHOULD_COLORIZE.should_colorize());
+    }
+
+    #[test]
+    fn test_visible_len_ignores_ansi_codes() {
+        assert_eq!(visible_len("\x1b[1;32mAMD\x1b[0m"), 3);
+        assert_eq!(visible_len("Vendor:  Intel"), 14);
+        assert_eq!(visible_len(""), 0);
+    }
+
+    #[test]
+    fn test_table_pads_to_widest_cell() {
+        let rows = vec![
+            vec!["L1".to_string(), "Instruction".to_string(), "32 KB".to_string()],
+            vec![
+                "L3".to_string(),
+                "\x1b[32mUnified\x1b[0m".to_string(),
+                "32 MB".to_string(),
+            ],
+        ];
+        let lines = table(&["Level", "Type", "Size"], &rows);
+
+        assert_eq!(
+            lines,
+            [
+                "Level  Type         Size",
+                "-----  -----------  -----",
+                "L1     Instruction  32 KB",
+                "L3     \x1b[32mUnified\x1b[0m      32 MB",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hyperlink_is_invisible_to_width() {
+        let link = hyperlink("Ryzen 9 5950X", "https://example.com/?q=5950X");
+        assert_eq!(
+            link,
+            "\x1b]8;;https://example.com/?q=5950X\x1b\\Ryzen 9 5950X\x1b]8;;\x1b\\"
+        );
+        assert_eq!(visible_len(&link), 13);
+        assert_eq!(visible_len("\x1b]8;;https://a.b\x07link\x1b]8;;\x07"), 4);
+    }
+
+    #[test]
+    fn test_hyperlinks_supported() {
+        assert!(hyperlinks_supported(true, Some(OsStr::new("xterm-256color"))));
+        assert!(hyperlinks_supported(true, None));
+        assert!(!hyperlinks_supported(true, Some(OsStr::new("dumb"))));
+        assert!(!hyperlinks_supported(false, Some(OsStr::new("xterm-256color"))));
+    }
+}
@@ -7,7 +7,7 @@
 //! Each vendor has a SHORT and optionally a LONG variant. Terminal width
 //! detection picks the best fit automatically.
 
-use crate::cpu::Vendor;
+use crate::cpu::{CpuInfo, Vendor};
 use colored::Color;
 
 // ── Types ────────────────────────────────────────────────────────────────────
@@ -28,6 +28,42 @@ pub struct LogoColors {
 
 // ── Public API ───────────────────────────────────────────────────────────────
 
+/// Return the raw ASCII art (with colour markers) that best fits the given CPU.
+///
+/// `custom` is user-supplied art from the config file and wins outright;
+/// otherwise a model-specific logo (see [`logo_for_model`]) takes precedence
+/// over the vendor logo for the requested size.
+#[cfg(feature = "display")]
+pub fn get_logo<'a>(cpu_info: &CpuInfo, size: LogoSize, custom: Option<&'a str>) -> &'a str {
+    custom
+        .or_else(|| logo_for_model(&cpu_info.brand_string))
+        .unwrap_or_else(|| get_raw_logo(&cpu_info.vendor, size))
+}
+
+/// Select a product-family logo from the marketing brand string, if one exists.
+///
+/// Recognises Ryzen (including Threadripper), Xeon, Core i-series / Core Ultra
+/// and Apple M-series brand strings.
+#[cfg(feature = "display")]
+pub fn logo_for_model(brand_string: &str) -> Option<&'static str> {
+    let brand = brand_string.to_ascii_lowercase();
+    if brand.contains("ryzen") {
+        Some(RYZEN_LOGO)
+    } else if brand.contains("xeon") {
+        Some(XEON_LOGO)
+    } else if brand.contains("core(tm) i")
+        || brand.contains("core i")
+        || brand.contains("core(tm) ultra")
+        || brand.contains("core ultra")
+    {
+        Some(INTEL_CORE_LOGO)
+    } else if brand.starts_with("apple m") {
+        Some(APPLE_M_LOGO)
+    } else {
+        None
+    }
+}
+
 /// Return the raw ASCII art (with colour markers) for the given vendor and size.
 ///
 /// Falls back to `Short` if no `Long` variant exists for the vendor.
@@ -38,10 +74,11 @@ pub fn get_raw_logo(vendor: &Vendor, size: LogoSize) -> &'static str {
         (Vendor::Intel, LogoSize::Short) => INTEL_LOGO,
         (Vendor::AMD, LogoSize::Long) => AMD_LOGO_LONG,
         (Vendor::AMD, LogoSize::Short) => AMD_LOGO,
-        (Vendor::ARM, LogoSize::Long) => ARM_LOGO_LONG,
-        (Vendor::ARM, LogoSize::Short) => ARM_LOGO,
+        // Qualcomm and NVIDIA cores are Arm-architecture designs without a logo of their own
+        (Vendor::ARM | Vendor::Qualcomm | Vendor::NVIDIA, LogoSize::Long) => ARM_LOGO_LONG,
+        (Vendor::ARM | Vendor::Qualcomm | Vendor::NVIDIA, LogoSize::Short) => ARM_LOGO,
         (Vendor::Apple, _) => APPLE_LOGO,
-        (Vendor::Unknown, _) => GENERIC_LOGO,
+        (Vendor::Hygon | Vendor::VIA | Vendor::Zhaoxin | Vendor::Hypervisor(_) | Vendor::Unknown, _) => GENERIC_LOGO,
     }
 }
 
@@ -49,7 +86,7 @@ pub fn get_raw_logo(vendor: &Vendor, size: LogoSize) -> &'static str {
 #[cfg(feature = "display")]
 pub fn get_logo_colors(vendor: &Vendor) -> LogoColors {
     match vendor {
-        Vendor::Intel | Vendor::ARM => LogoColors {
+        Vendor::Intel | Vendor::ARM | Vendor::Qualcomm | Vendor::NVIDIA => LogoColors {
             colors: [Some(Color::Cyan), None, None, None],
         },
         Vendor::AMD => LogoColors {
@@ -58,7 +95,7 @@ pub fn get_logo_colors(vendor: &Vendor) -> LogoColors {
         Vendor::Apple => LogoColors {
             colors: [Some(Color::White), None, None, None],
         },
-        Vendor::Unknown => LogoColors {
+        Vendor::Hygon | Vendor::VIA | Vendor::Zhaoxin | Vendor::Hypervisor(_) | Vendor::Unknown => LogoColors {
             colors: [Some(Color::Yellow), None, None, None],
         },
     }
@@ -70,8 +107,8 @@ pub fn get_logo_colors(vendor: &Vendor) -> LogoColors {
 pub fn get_text_color(vendor: &Vendor) -> Color {
     match vendor {
         Vendor::AMD => Color::Green,
-        Vendor::Intel | Vendor::ARM | Vendor::Apple => Color::Cyan,
-        Vendor::Unknown => Color::Yellow,
+        Vendor::Intel | Vendor::ARM | Vendor::Apple | Vendor::Qualcomm | Vendor::NVIDIA => Color::Cyan,
+        Vendor::Hygon | Vendor::VIA | Vendor::Zhaoxin | Vendor::Hypervisor(_) | Vendor::Unknown => Color::Yellow,
     }
 }
 
@@ -278,6 +315,45 @@ $C1 ####       #####    ####         ####      ####      ###\n\
 $C1  ###############    ####         ####      ####      ###\n\
 $C1   ########  ####    ####         ####      ####      ###";
 
+// ── Model-specific logos ─────────────────────────────────────────────────────
+// Used when the brand string identifies a well-known product family; a single
+// variant serves both sizes.
+
+#[cfg(feature = "display")]
+const RYZEN_LOGO: &str = "\
+$C1#####   #   #  #####  #####  #   #   $C2  ######\n\
+$C1#    #   # #      #   #      ##  #   $C2    ####\n\
+$C1#####     #      #    ####   # # #   $C2  #  ###\n\
+$C1#   #     #     #     #      #  ##   $C2 ###  ##\n\
+$C1#    #    #    #####  #####  #   #   $C2####   #";
+
+#[cfg(feature = "display")]
+const INTEL_CORE_LOGO: &str = "\
+$C1             intel         \n\
+$C1 ###    ###   ####   ##### \n\
+$C1#      #   #  #   #  #     \n\
+$C1#      #   #  ####   ####  \n\
+$C1#      #   #  #  #   #     \n\
+$C1 ###    ###   #   #  ##### ";
+
+#[cfg(feature = "display")]
+const XEON_LOGO: &str = "\
+$C1#   #  #####   ###   #   #\n\
+$C1 # #   #      #   #  ##  #\n\
+$C1  #    ####   #   #  # # #\n\
+$C1 # #   #      #   #  #  ##\n\
+$C1#   #  #####   ###   #   #";
+
+#[cfg(feature = "display")]
+const APPLE_M_LOGO: &str = "\
+$C1.-------------.\n\
+$C1|  #       #  |\n\
+$C1|  ##     ##  |\n\
+$C1|  # #   # #  |\n\
+$C1|  #  # #  #  |\n\
+$C1|  #   #   #  |\n\
+$C1'-------------'";
+
 // ── Tests ────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -327,6 +403,39 @@ mod tests {
         assert_eq!(short, long);
     }
 
+    #[test]
+    fn test_logo_for_model() {
+        assert_eq!(
+            logo_for_model("AMD Ryzen Threadripper 3970X 32-Core Processor"),
+            Some(RYZEN_LOGO)
+        );
+        assert_eq!(logo_for_model("AMD Ryzen 7 5800X 8-Core Processor"), Some(RYZEN_LOGO));
+        assert_eq!(
+            logo_for_model("Intel(R) Xeon(R) Gold 6338 CPU @ 2.00GHz"),
+            Some(XEON_LOGO)
+        );
+        assert_eq!(
+            logo_for_model("Intel(R) Core(TM) i7-9700K CPU @ 3.60GHz"),
+            Some(INTEL_CORE_LOGO)
+        );
+        assert_eq!(logo_for_model("Intel(R) Core(TM) Ultra 7 155H"), Some(INTEL_CORE_LOGO));
+        assert_eq!(logo_for_model("Apple M2 Pro"), Some(APPLE_M_LOGO));
+        // Unrecognised families fall back to the vendor logo
+        assert_eq!(logo_for_model("AMD Athlon 3000G"), None);
+        assert_eq!(logo_for_model("Intel(R) Celeron(R) N4020"), None);
+    }
+
+    #[test]
+    fn test_get_logo_falls_back_to_vendor() {
+        let info = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD Athlon 3000G".to_string(),
+            ..CpuInfo::default()
+        };
+        assert_eq!(get_logo(&info, LogoSize::Short, None), AMD_LOGO);
+        assert_eq!(get_logo(&info, LogoSize::Short, Some("$C1custom")), "$C1custom");
+    }
+
     #[test]
     fn test_logo_line_counts() {
         // Verify logos have expected line counts
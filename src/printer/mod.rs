@@ -10,13 +10,39 @@ mod ascii;
 mod layout;
 #[cfg(feature = "display")]
 mod logo;
+#[cfg(feature = "cli")]
+mod plain;
+#[cfg(feature = "cli")]
+mod render;
+#[cfg(all(feature = "cli", feature = "json", feature = "frequency"))]
+mod stream;
+#[cfg(feature = "display")]
+mod theme;
+#[cfg(all(feature = "display", feature = "frequency"))]
+mod watch;
+
+#[cfg(feature = "cli")]
+pub use plain::render_plain;
+#[cfg(feature = "cli")]
+pub use render::{
+    CsvRenderer, JsonRenderer, PrometheusRenderer, Renderer, TerminalRenderer, TomlRenderer, YamlRenderer, renderer_for,
+};
+
+#[cfg(all(feature = "cli", feature = "json", feature = "frequency"))]
+pub use stream::stream_json;
+#[cfg(feature = "display")]
+pub use theme::{Level, Theme};
+#[cfg(all(feature = "display", feature = "frequency"))]
+pub use watch::watch_loop;
 
 #[cfg(feature = "display")]
 use crate::cli::Args;
 #[cfg(feature = "display")]
-use crate::cpu::CpuInfo;
+use crate::cpu::{CacheInfo, CpuInfo};
 #[cfg(feature = "display")]
 use colored::Colorize;
+#[cfg(feature = "display")]
+use std::io::Write;
 
 /// Width of the label column (including the trailing colon).
 /// "Microarchitecture:" is 18 chars — use 20 for a comfortable margin.
@@ -27,47 +53,624 @@ const LABEL_WIDTH: usize = 20;
 #[cfg(feature = "display")]
 const LOGO_INFO_GAP: usize = 3;
 
-/// Print CPU information with an optional ASCII art logo in a side-by-side layout.
+/// Appended to the model name when an x86-64 binary runs translated on Apple Silicon.
+#[cfg(feature = "cli")]
+const ROSETTA_NOTE: &str = "(running under Rosetta 2)";
+
+/// Category under which an x86 feature flag is listed in the features block
+#[cfg(all(feature = "cli", any(target_arch = "x86", target_arch = "x86_64")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeatureCategory {
+    Simd,
+    Avx512,
+    Crypto,
+    BitManipulation,
+}
+
+#[cfg(all(feature = "cli", any(target_arch = "x86", target_arch = "x86_64")))]
+impl FeatureCategory {
+    /// Display order of the feature groups
+    const ALL: [Self; 4] = [Self::Simd, Self::Avx512, Self::Crypto, Self::BitManipulation];
+}
+
+#[cfg(all(feature = "cli", any(target_arch = "x86", target_arch = "x86_64")))]
+impl std::fmt::Display for FeatureCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Simd => write!(f, "SIMD"),
+            Self::Avx512 => write!(f, "AVX-512"),
+            Self::Crypto => write!(f, "Crypto"),
+            Self::BitManipulation => write!(f, "Bit Manipulation"),
+        }
+    }
+}
+
+/// Display name and category of every x86 feature flag, in display order.
+///
+/// Adding a flag to the features block only needs a new entry here.
+#[cfg(all(feature = "cli", any(target_arch = "x86", target_arch = "x86_64")))]
+const X86_FEATURE_TABLE: &[(&str, crate::cpu::X86Features, FeatureCategory)] = {
+    use crate::cpu::X86Features;
+    &[
+        ("SSE", X86Features::SSE, FeatureCategory::Simd),
+        ("SSE2", X86Features::SSE2, FeatureCategory::Simd),
+        ("SSE3", X86Features::SSE3, FeatureCategory::Simd),
+        ("SSSE3", X86Features::SSSE3, FeatureCategory::Simd),
+        ("SSE4.1", X86Features::SSE4_1, FeatureCategory::Simd),
+        ("SSE4.2", X86Features::SSE4_2, FeatureCategory::Simd),
+        ("AVX", X86Features::AVX, FeatureCategory::Simd),
+        ("AVX2", X86Features::AVX2, FeatureCategory::Simd),
+        ("FMA", X86Features::FMA, FeatureCategory::Simd),
+        ("F16C", X86Features::F16C, FeatureCategory::Simd),
+        ("AVX-512F", X86Features::AVX512F, FeatureCategory::Avx512),
+        ("AVX-512BW", X86Features::AVX512BW, FeatureCategory::Avx512),
+        ("AVX-512CD", X86Features::AVX512CD, FeatureCategory::Avx512),
+        ("AVX-512DQ", X86Features::AVX512DQ, FeatureCategory::Avx512),
+        ("AVX-512VL", X86Features::AVX512VL, FeatureCategory::Avx512),
+        ("AES", X86Features::AES, FeatureCategory::Crypto),
+        ("SHA", X86Features::SHA, FeatureCategory::Crypto),
+        ("GFNI", X86Features::GFNI, FeatureCategory::Crypto),
+        ("BMI1", X86Features::BMI1, FeatureCategory::BitManipulation),
+        ("BMI2", X86Features::BMI2, FeatureCategory::BitManipulation),
+        ("POPCNT", X86Features::POPCNT, FeatureCategory::BitManipulation),
+    ]
+};
+
+/// Display names of the flags in `category` that the CPU has, in table order.
+///
+/// Flags CPUID advertises but the OS has disabled are listed with a note, so
+/// users debugging VMs and containers can see what was masked.
+#[cfg(all(feature = "cli", any(target_arch = "x86", target_arch = "x86_64")))]
+fn feature_labels(cpu_info: &crate::cpu::CpuInfo, category: FeatureCategory) -> Vec<String> {
+    X86_FEATURE_TABLE
+        .iter()
+        .filter(|(_, _, cat)| *cat == category)
+        .filter_map(|(name, flag, _)| {
+            if cpu_info.features.contains(*flag) {
+                Some((*name).to_string())
+            } else if cpu_info.available_but_disabled.contains(*flag) {
+                Some(format!("{name} (present, disabled by OS)"))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// One line of the info block.
+///
+/// Fields keep their label and value apart until the block is complete, so
+/// the value column can line up just past the longest label actually shown.
+#[cfg(feature = "display")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum InfoLine {
+    /// A `label:  value` pair
+    Field(String, String),
+    /// A heading, feature list or blank line, printed as is
+    Text(String),
+}
+
+#[cfg(feature = "display")]
+impl InfoLine {
+    fn field(label: &str, value: impl std::fmt::Display) -> Self {
+        Self::Field(label.to_string(), value.to_string())
+    }
+}
+
+/// Render `lines` with every field value starting in the same column.
+///
+/// The label column is as wide as the longest label among the fields, plus its colon.
+#[cfg(feature = "display")]
+fn align_fields(lines: &[InfoLine], theme: &Theme) -> Vec<String> {
+    let width = lines
+        .iter()
+        .filter_map(|line| match line {
+            InfoLine::Field(label, _) => Some(label.chars().count() + 1),
+            InfoLine::Text(_) => None,
+        })
+        .max()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| match line {
+            InfoLine::Field(label, value) => theme.kv_aligned(label, value, width),
+            InfoLine::Text(text) => text.clone(),
+        })
+        .collect()
+}
+
+/// Logical CPUs shown per row of the `--per-core` table
+#[cfg(all(feature = "display", feature = "frequency"))]
+const PER_CORE_COLUMNS: usize = 4;
+
+/// Format per-core frequencies as a compact table, `PER_CORE_COLUMNS` cores per row.
+///
+/// Rows are labelled with the CPU ids they hold, so a gap left by an offline
+/// CPU shows up as e.g. "Cores 0,2-4".
+#[cfg(all(feature = "display", feature = "frequency"))]
+fn per_core_lines(frequencies: &[(u32, f64)], theme: &Theme) -> Vec<InfoLine> {
+    frequencies
+        .chunks(PER_CORE_COLUMNS)
+        .map(|chunk| {
+            let label = if let [(cpu, _)] = chunk {
+                format!("Core {cpu}")
+            } else {
+                let cpus: Vec<u32> = chunk.iter().map(|(cpu, _)| *cpu).collect();
+                format!("Cores {}", crate::cpu::numa::cpulist(&cpus))
+            };
+            let values: Vec<String> = chunk.iter().map(|(_, mhz)| format!("{mhz:>5.0}")).collect();
+            InfoLine::field(&label, theme.value(&format!("{} MHz", values.join(" "))))
+        })
+        .collect()
+}
+
+/// Narrowest and widest `--bars` frequency bar, in cells
+#[cfg(feature = "display")]
+const BAR_WIDTH_RANGE: (usize, usize) = (10, 40);
+
+/// Cells of the frequency bar for a terminal `columns` wide, leaving room for the logo and label.
+#[cfg(feature = "display")]
+fn bar_width(columns: usize) -> usize {
+    columns.saturating_sub(60).clamp(BAR_WIDTH_RANGE.0, BAR_WIDTH_RANGE.1)
+}
+
+/// Filled cells of a `width`-cell bar for `current` out of `max`.
+#[cfg(feature = "display")]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn bar_filled(current: f64, max: f64, width: usize) -> usize {
+    if max <= 0.0 {
+        return 0;
+    }
+    ((current / max).clamp(0.0, 1.0) * width as f64).round() as usize
+}
+
+/// "████████░░ 3.2/4.0 GHz", coloured from the theme's low level when well below `max` to high near it.
+///
+/// Without `unicode` the bar is drawn as "########--".
+#[cfg(feature = "display")]
+fn frequency_bar(current: f64, max: f64, width: usize, unicode: bool, theme: &Theme) -> String {
+    let filled = bar_filled(current, max, width);
+    let (full, empty) = if unicode { ("█", "░") } else { ("#", "-") };
+    let bar = format!("{}{}", full.repeat(filled), empty.repeat(width - filled));
+    format!(
+        "{} {:.1}/{:.1} GHz",
+        theme.level(&bar, Level::of_ratio(current / max)),
+        current / 1000.0,
+        max / 1000.0
+    )
+}
+
+/// Whether output may use characters beyond ASCII (box drawing, bar blocks, the degree sign).
+///
+/// False with `--ascii`, or when the locale names a character set other than
+/// UTF-8 (e.g. `LANG=C`); see [`locale_is_utf8`].
+#[cfg(feature = "cli")]
+fn unicode_output(args: &crate::cli::Args) -> bool {
+    !args.ascii
+        && locale_is_utf8(
+            std::env::var_os("LC_ALL").as_deref(),
+            std::env::var_os("LC_CTYPE").as_deref(),
+            std::env::var_os("LANG").as_deref(),
+        )
+}
+
+/// Whether the locale in effect is UTF-8.
+///
+/// The first non-empty of `LC_ALL`, `LC_CTYPE` and `LANG` decides, as for
+/// the C library. With none set (as on Windows and in many containers)
+/// UTF-8 is assumed, since that is what current terminals speak.
+#[cfg(feature = "cli")]
+fn locale_is_utf8(
+    lc_all: Option<&std::ffi::OsStr>,
+    lc_ctype: Option<&std::ffi::OsStr>,
+    lang: Option<&std::ffi::OsStr>,
+) -> bool {
+    [lc_all, lc_ctype, lang]
+        .into_iter()
+        .flatten()
+        .find(|value| !value.is_empty())
+        .is_none_or(|locale| {
+            let locale = locale.to_string_lossy().to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+/// "~3.412 GHz (busy-loop estimate)", marking a benchmarked clock as approximate.
+#[cfg(feature = "cli")]
+fn estimate_value(mhz: f64) -> String {
+    format!("~{:.3} GHz (busy-loop estimate)", mhz / 1000.0)
+}
+
+/// "45.0 °C", or "45.0 C" when the output is limited to ASCII.
+#[cfg(feature = "cli")]
+fn celsius_value(celsius: f32, unicode: bool) -> String {
+    format!("{celsius:.1} {}C", if unicode { "°" } else { "" })
+}
+
+/// "CPU Information", naming the package when the report covers a single socket of several.
+#[cfg(feature = "cli")]
+fn report_title(cpu_info: &crate::cpu::CpuInfo) -> String {
+    match cpu_info.package_id {
+        Some(id) => format!("CPU Information (Package {id})"),
+        None => "CPU Information".to_string(),
+    }
+}
+
+/// The microarchitecture name, followed with `--uarch` by its process node, e.g. "Raptor Lake (Intel 7)".
+#[cfg(feature = "cli")]
+fn uarch_value(uarch: &crate::cpu::Microarch, cpu_info: &crate::cpu::CpuInfo, args: &crate::cli::Args) -> String {
+    match &cpu_info.process_node {
+        Some(node) if args.uarch => format!("{uarch} ({node})"),
+        _ => uarch.to_string(),
+    }
+}
+
+/// "2 sockets" for the socket line, when `--sockets` asks for it or the system has several.
+#[cfg(feature = "cli")]
+fn sockets_value(cpu_info: &crate::cpu::CpuInfo, args: &crate::cli::Args) -> Option<String> {
+    match cpu_info.sockets? {
+        1 if args.sockets => Some("1 socket".to_string()),
+        count if count > 1 => Some(format!("{count} sockets")),
+        _ => None,
+    }
+}
+
+/// "Family 0x19, Model 0x21, Stepping 0" for the family/model line, when `--uarch` or `--version-info` asks for it.
+#[cfg(feature = "cli")]
+fn version_value(cpu_info: &crate::cpu::CpuInfo, args: &crate::cli::Args) -> Option<String> {
+    let version = &cpu_info.version;
+    (args.uarch || args.version_info).then(|| {
+        format!(
+            "Family 0x{:x}, Model 0x{:x}, Stepping {}",
+            version.family, version.model, version.stepping
+        )
+    })
+}
+
+/// The TSC frequency, when known, and whether it is invariant, e.g. "2.592 GHz, invariant".
+#[cfg(feature = "cli")]
+fn tsc_value(cpu_info: &crate::cpu::CpuInfo) -> String {
+    let invariance = if cpu_info.invariant_tsc {
+        "invariant"
+    } else {
+        "not invariant"
+    };
+    match cpu_info.tsc_frequency_mhz {
+        Some(mhz) => format!("{:.3} GHz, {invariance}", mhz / 1000.0),
+        None => invariance.to_string(),
+    }
+}
+
+/// The mitigation status at the theme's low level when not affected, medium when mitigated and high when vulnerable.
+#[cfg(feature = "display")]
+fn exposure_value(mitigation: &crate::cpu::Mitigation, theme: &Theme) -> colored::ColoredString {
+    use crate::cpu::Exposure;
+    let level = match mitigation.exposure() {
+        Exposure::NotAffected => Level::Low,
+        Exposure::Mitigated => Level::Medium,
+        Exposure::Vulnerable => Level::High,
+    };
+    theme.level(&mitigation.status, level)
+}
+
+/// The governor, followed by the EPP hint when the driver has one, e.g. "performance, EPP: `balance_performance`".
+#[cfg(feature = "cli")]
+fn governor_value(cpu_info: &crate::cpu::CpuInfo) -> Option<String> {
+    let governor = cpu_info.governor.as_deref()?;
+    Some(match &cpu_info.epp {
+        Some(epp) => format!("{governor}, EPP: {epp}"),
+        None => governor.to_string(),
+    })
+}
+
+/// "48 bits physical, 48 bits virtual" for the address sizes line, when both widths are known.
+#[cfg(feature = "cli")]
+fn address_sizes_value(cpu_info: &crate::cpu::CpuInfo) -> Option<String> {
+    let physical = cpu_info.physical_address_bits?;
+    let virtual_bits = cpu_info.virtual_address_bits?;
+    Some(format!("{physical} bits physical, {virtual_bits} bits virtual"))
+}
+
+/// Format one line per detected cache level.
+///
+/// With `detail`, the associativity, line size and sharing from the full
+/// cache topology are appended, e.g. "256 KB, 8-way, 64B line, shared by 1 core".
+/// On a hybrid CPU, `detail` also splits each level into one line per core
+/// kind, e.g. "L2 (P-core)" and "L2 (E-core)".
+#[cfg(feature = "display")]
+fn cache_lines(cpu_info: &CpuInfo, detail: bool, theme: &Theme) -> Vec<InfoLine> {
+    let topology = cpu_info.cache_topology.as_ref();
+    let cache_labels = ["L1i Cache", "L1d Cache", "L2 Cache", "L3 Cache"];
+    let mut lines = Vec::new();
+    for (index, (label, size)) in cache_labels.iter().zip(cpu_info.cache_sizes.iter()).enumerate() {
+        let Some(kb) = *size else {
+            continue;
+        };
+        let cache = topology.and_then(|topology| topology.caches.get(index).copied().flatten());
+        if !detail {
+            lines.push(InfoLine::field(label, theme.value(&cache_size(kb))));
+            continue;
+        }
+
+        let per_kind: Vec<&CacheInfo> = match (topology, cache) {
+            (Some(topology), Some(cache)) => topology
+                .by_core_kind
+                .iter()
+                .filter(|other| other.level == cache.level && other.cache_type == cache.cache_type)
+                .collect(),
+            _ => Vec::new(),
+        };
+        if per_kind.is_empty() {
+            let display = cache.map_or_else(|| cache_size(kb), |cache| cache_detail(kb, &cache));
+            lines.push(InfoLine::field(label, theme.value(&display)));
+        }
+        for cache in per_kind {
+            let kind = cache.core_kind.map(|kind| kind.to_string()).unwrap_or_default();
+            let short_label = label.trim_end_matches(" Cache");
+            lines.push(InfoLine::field(
+                &format!("{short_label} ({kind})"),
+                theme.value(&cache_detail(cache.size_kb, cache)),
+            ));
+        }
+    }
+
+    // Levels past L3, such as an eDRAM L4, are only known from the full topology
+    for cache in topology.map_or(&[][..], |topology| &topology.outer_levels) {
+        let display = if detail {
+            cache_detail(cache.size_kb, cache)
+        } else {
+            cache_size(cache.size_kb)
+        };
+        lines.push(InfoLine::field(
+            &format!("L{} Cache", cache.level),
+            theme.value(&display),
+        ));
+    }
+    lines
+}
+
+/// "48 KB" or "30 MB"
+#[cfg(feature = "display")]
+fn cache_size(kb: u32) -> String {
+    if kb >= 1024 {
+        format!("{} MB", kb / 1024)
+    } else {
+        format!("{kb} KB")
+    }
+}
+
+/// The size of a `kb` KB cache followed by its geometry, sharing and inclusivity.
+///
+/// Sharing of a per-core-kind cache counts cores rather than threads, so one is shown as "private".
+#[cfg(feature = "display")]
+fn cache_detail(kb: u32, cache: &CacheInfo) -> String {
+    use std::fmt::Write as FmtWrite;
+
+    let mut display = cache_size(kb);
+    if cache.associativity > 0 {
+        let _ = write!(display, ", {}-way", cache.associativity);
+    }
+    if cache.line_size > 0 {
+        let _ = write!(display, ", {}B line", cache.line_size);
+    }
+    match cache.shared_by {
+        0 => {},
+        1 if cache.core_kind.is_some() => display.push_str(", private"),
+        1 => display.push_str(", shared by 1 core"),
+        n => {
+            let _ = write!(display, ", shared by {n} cores");
+        },
+    }
+    match cache.inclusive {
+        Some(true) => display.push_str(", inclusive"),
+        Some(false) => display.push_str(", non-inclusive"),
+        None => {},
+    }
+    if cache.self_initializing {
+        display.push_str(", self-initialising");
+    }
+    display
+}
+
+/// Format the detected caches as a Level / Type / Size / Assoc / Line / Shared table.
+///
+/// Returns `None` when the platform only reported cache sizes, in which case
+/// the plain list from [`cache_lines`] is the best available.
+#[cfg(feature = "display")]
+fn cache_table(cpu_info: &CpuInfo, theme: &Theme) -> Option<Vec<String>> {
+    // A hybrid CPU's per-kind list covers every level for both kinds of core
+    let topology = cpu_info.cache_topology.as_ref()?;
+    let caches: Vec<_> = if topology.by_core_kind.is_empty() {
+        topology.iter_present().collect()
+    } else {
+        topology.by_core_kind.iter().collect()
+    };
+    if caches.is_empty() {
+        return None;
+    }
+
+    let unknown_if_zero = |value: u32, unit: &str| {
+        if value == 0 {
+            "-".to_string()
+        } else {
+            format!("{value}{unit}")
+        }
+    };
+    let rows: Vec<Vec<String>> = caches
+        .iter()
+        .map(|cache| {
+            vec![
+                match cache.core_kind {
+                    Some(kind) => format!("L{} ({kind})", cache.level),
+                    None => format!("L{}", cache.level),
+                },
+                cache.cache_type.to_string(),
+                cache_size(cache.size_kb),
+                unknown_if_zero(u32::from(cache.associativity), "-way"),
+                unknown_if_zero(u32::from(cache.line_size), "B"),
+                unknown_if_zero(u32::from(cache.shared_by), ""),
+            ]
+        })
+        .collect();
+
+    let mut lines = vec![theme.header("Caches").to_string()];
+    lines.extend(
+        layout::table(&["Level", "Type", "Size", "Assoc", "Line", "Shared"], &rows)
+            .into_iter()
+            .map(|line| format!("  {line}")),
+    );
+    Some(lines)
+}
+
+/// Spec-page searches by vendor and product family, as (vendor, family word in brand string, URL prefix).
+///
+/// The model number from the brand string is appended to the prefix.
+#[cfg(feature = "display")]
+const SPEC_LINKS: &[(crate::cpu::Vendor, &str, &str)] = {
+    use crate::cpu::Vendor;
+
+    const INTEL_ARK: &str = "https://ark.intel.com/content/www/us/en/ark/search.html?q=";
+    const AMD_SEARCH: &str = "https://www.amd.com/en/search/site-search.html#q=";
+    &[
+        (Vendor::Intel, "Core", INTEL_ARK),
+        (Vendor::Intel, "Xeon", INTEL_ARK),
+        (Vendor::Intel, "Pentium", INTEL_ARK),
+        (Vendor::Intel, "Celeron", INTEL_ARK),
+        (Vendor::Intel, "Atom", INTEL_ARK),
+        (Vendor::AMD, "Ryzen", AMD_SEARCH),
+        (Vendor::AMD, "EPYC", AMD_SEARCH),
+        (Vendor::AMD, "Athlon", AMD_SEARCH),
+    ]
+};
+
+/// Spec page for a recognised model, e.g. ark.intel.com for "Core(TM) i7-9750H".
+#[cfg(feature = "display")]
+fn spec_url(cpu_info: &CpuInfo) -> Option<String> {
+    let brand = cpu_info.brand_string.as_str();
+    let words = brand
+        .split_whitespace()
+        .map(|word| word.trim_end_matches("(TM)").trim_end_matches("(R)"));
+    let (_, _, prefix) = SPEC_LINKS
+        .iter()
+        .find(|(vendor, family, _)| *vendor == cpu_info.vendor && words.clone().any(|word| word == *family))?;
+    Some(format!("{prefix}{}", model_number(brand)?))
+}
+
+/// The model number in a brand string: "i7-9750H", "5950X", "8375C".
+///
+/// Taken as the first word before any " @ " clock suffix that has a digit
+/// and at least four characters, skipping core counts ("16-Core") and
+/// generation prefixes ("12th").
+#[cfg(feature = "display")]
+fn model_number(brand: &str) -> Option<&str> {
+    let name = brand.split(" @ ").next().unwrap_or(brand);
+    name.split_whitespace().find(|word| {
+        word.len() >= 4
+            && word.chars().any(|c| c.is_ascii_digit())
+            && !word.contains("Core")
+            && !word.ends_with("th")
+            && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+/// Write CPU information with an optional ASCII art logo in a side-by-side layout to `out`.
 ///
 /// # Errors
 ///
-/// Returns an error if writing to stdout fails.
+/// Returns an error if writing to `out` fails.
 #[cfg(feature = "display")]
-pub fn print_cpu_info(cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
-    layout::setup_display(args.no_color);
+pub fn print_cpu_info(out: &mut dyn Write, cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
+    let use_color = layout::setup_display(args.no_color, args.output.is_some());
+    let unicode = unicode_output(args);
+    let theme = selected_theme(args)?;
 
     // ── Build info lines ────────────────────────────────────────────────────
-    let mut info_lines: Vec<String> = Vec::new();
+    let mut info_lines = vec![InfoLine::Text(theme.header(&report_title(cpu_info)).to_string())];
 
     // Always-visible core information
-    info_lines.push(layout::format_kv(
+    info_lines.push(InfoLine::field(
         "Vendor",
-        &cpu_info.vendor.to_string().green().bold().to_string(),
-        LABEL_WIDTH,
-    ));
-    info_lines.push(layout::format_kv(
-        "Model",
-        &cpu_info.brand_string.green().to_string(),
-        LABEL_WIDTH,
+        theme.value(&cpu_info.vendor.to_string()).bold(),
     ));
+    let mut model = theme.value(&cpu_info.brand_string).to_string();
+    if args.links
+        && use_color
+        && args.output.is_none()
+        && layout::supports_hyperlinks()
+        && let Some(url) = spec_url(cpu_info)
+    {
+        model = layout::hyperlink(&model, &url);
+    }
+    if cpu_info.translated {
+        model = format!("{model} {}", theme.accent(ROSETTA_NOTE));
+    }
+    info_lines.push(InfoLine::field("Model", model));
 
     if let Some(ref uarch) = cpu_info.microarch {
-        info_lines.push(layout::format_kv(
+        info_lines.push(InfoLine::field(
             "Microarchitecture",
-            &uarch.to_string().green().to_string(),
-            LABEL_WIDTH,
+            theme.value(&uarch_value(uarch, cpu_info, args)),
         ));
-        if let Some(nm) = uarch.process_nm() {
-            info_lines.push(layout::format_kv(
-                "Technology",
-                &format!("{nm} nm").green().to_string(),
-                LABEL_WIDTH,
-            ));
+        if let Some(ref node) = cpu_info.process_node {
+            info_lines.push(InfoLine::field("Technology", theme.value(node)));
+        }
+    } else if args.uarch {
+        info_lines.push(InfoLine::field("Microarchitecture", theme.accent("Unknown")));
+    }
+
+    // Raw identifiers, so an unrecognised part can be looked up or reported
+    if let Some(version) = version_value(cpu_info, args) {
+        info_lines.push(InfoLine::field("Family/Model", theme.value(&version)));
+    }
+    if args.microcode {
+        match cpu_info.microcode {
+            Some(revision) => info_lines.push(InfoLine::field("Microcode", theme.value(&format!("{revision:#x}")))),
+            None => info_lines.push(InfoLine::field("Microcode", theme.accent("Unknown"))),
+        }
+    }
+    if args.governor {
+        match governor_value(cpu_info) {
+            Some(policy) => info_lines.push(InfoLine::field("Governor", theme.value(&policy))),
+            None => info_lines.push(InfoLine::field("Governor", theme.accent("Unknown"))),
         }
     }
+    if args.tsc {
+        info_lines.push(InfoLine::field("TSC", theme.value(&tsc_value(cpu_info))));
+    }
+    if args.pmu {
+        let pmu = match cpu_info.pmu {
+            Some(pmu) => theme.value(&pmu.to_string()),
+            None => theme.accent("Unknown"),
+        };
+        info_lines.push(InfoLine::field("PMU", pmu));
+    }
+    if args.address_sizes {
+        match address_sizes_value(cpu_info) {
+            Some(sizes) => info_lines.push(InfoLine::field("Address Sizes", theme.value(&sizes))),
+            None => info_lines.push(InfoLine::field("Address Sizes", theme.accent("Unknown"))),
+        }
+        info_lines.push(InfoLine::field(
+            "Byte Order",
+            theme.value(&cpu_info.endianness.to_string()),
+        ));
+    }
 
     if let Some(ref hv) = cpu_info.hypervisor {
-        info_lines.push(layout::format_kv("Hypervisor", &hv.yellow().to_string(), LABEL_WIDTH));
+        info_lines.push(InfoLine::field("Hypervisor", theme.accent(hv)));
+    }
+    if args.vm {
+        let virtualized = if cpu_info.is_virtualized { "Yes" } else { "No" };
+        info_lines.push(InfoLine::field("Virtualized", theme.value(virtualized)));
+        if let Some(ref signature) = cpu_info.hypervisor_vendor {
+            info_lines.push(InfoLine::field(
+                "Hypervisor ID",
+                theme.value(&format!("\"{signature}\"")),
+            ));
+        }
     }
 
     // ── Cores (with P/E breakdown for hybrid CPUs) ─────────────────────────
@@ -83,7 +686,20 @@ pub fn print_cpu_info(cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
             cpu_info.physical_cores, cpu_info.logical_cores
         ),
     };
-    info_lines.push(layout::format_kv("Cores", &cores_str.green().to_string(), LABEL_WIDTH));
+    info_lines.push(InfoLine::field("Cores", theme.value(&cores_str)));
+    if let Some(sockets) = sockets_value(cpu_info, args) {
+        info_lines.push(InfoLine::field("Sockets", theme.value(&sockets)));
+    }
+
+    if let Some(ref topo) = cpu_info.amd_topology {
+        info_lines.push(InfoLine::field(
+            "Chiplets",
+            theme.value(&format!(
+                "{} cores/CCX, {} CCX/CCD",
+                topo.cores_per_ccx, topo.ccx_per_ccd
+            )),
+        ));
+    }
 
     // ── Frequency (always shown when data is available) ─────────────────────
     if let Some(max) = cpu_info.frequency.max {
@@ -92,39 +708,90 @@ pub fn print_cpu_info(cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
         } else {
             "Frequency"
         };
-        info_lines.push(layout::format_kv(
-            label,
-            &format!("{:.3} GHz", max / 1000.0).green().to_string(),
-            LABEL_WIDTH,
-        ));
+        info_lines.push(InfoLine::field(label, theme.value(&format!("{:.3} GHz", max / 1000.0))));
     }
     if let Some(base) = cpu_info.frequency.base {
-        info_lines.push(layout::format_kv(
+        info_lines.push(InfoLine::field(
             "Base Frequency",
-            &format!("{:.3} GHz", base / 1000.0).green().to_string(),
-            LABEL_WIDTH,
+            theme.value(&format!("{:.3} GHz", base / 1000.0)),
         ));
     }
-    if args.frequency {
+    if args.frequency || args.bars {
         if let Some(cur) = cpu_info.frequency.current {
-            info_lines.push(layout::format_kv(
-                "Current Frequency",
-                &format!("{:.3} GHz", cur / 1000.0).green().to_string(),
-                LABEL_WIDTH,
+            let value = match cpu_info.frequency.max {
+                Some(max) if args.bars => {
+                    frequency_bar(cur, max, bar_width(layout::get_terminal_width()), unicode, &theme)
+                },
+                _ => theme.value(&format!("{:.3} GHz", cur / 1000.0)).to_string(),
+            };
+            info_lines.push(InfoLine::field("Current Frequency", value));
+        }
+    }
+    if args.frequency {
+        if let Some(average) = cpu_info.frequency.average {
+            info_lines.push(InfoLine::field(
+                "Average Frequency",
+                theme.value(&format!("{:.3} GHz", average / 1000.0)),
+            ));
+        }
+    }
+    // Only present with --benchmark-freq
+    if let Some(estimated) = cpu_info.frequency.estimated {
+        info_lines.push(InfoLine::field(
+            "Estimated Frequency",
+            theme.value(&estimate_value(estimated)),
+        ));
+    }
+
+    #[cfg(feature = "frequency")]
+    if args.per_core
+        && let Ok(frequencies) = crate::cpu::detect_per_core_frequencies()
+    {
+        info_lines.extend(per_core_lines(&frequencies, &theme));
+    }
+    if (args.clusters || args.per_core)
+        && let Some(clusters) = &cpu_info.clusters
+    {
+        for (index, cluster) in clusters.iter().enumerate() {
+            info_lines.push(InfoLine::field(
+                &format!("Cluster {index}"),
+                theme.value(&cluster.to_string()),
             ));
         }
     }
 
+    // ── Temperature (only when a sensor reported a value) ───────────────────
+    if args.temp
+        && let Some(celsius) = cpu_info.temperature_celsius
+    {
+        info_lines.push(InfoLine::field(
+            "Temperature",
+            theme.value(&celsius_value(celsius, unicode)),
+        ));
+    }
+
+    // ── Power (only when an energy counter was readable) ────────────────────
+    if args.power
+        && let Some(watts) = cpu_info.power_watts
+    {
+        info_lines.push(InfoLine::field("Package Power", theme.value(&format!("{watts:.1} W"))));
+    }
+
     // ── Cache (always shown when data is available) ──────────────────────
-    let cache_labels = ["L1i Cache", "L1d Cache", "L2 Cache", "L3 Cache"];
-    for (label, size) in cache_labels.iter().zip(cpu_info.cache_sizes.iter()) {
-        if let Some(kb) = size {
-            let display = if *kb >= 1024 {
-                format!("{} MB", kb / 1024)
-            } else {
-                format!("{kb} KB")
-            };
-            info_lines.push(layout::format_kv(label, &display.green().to_string(), LABEL_WIDTH));
+    match cache_table(cpu_info, &theme) {
+        Some(table) if args.table => info_lines.extend(table.into_iter().map(InfoLine::Text)),
+        _ => info_lines.extend(cache_lines(cpu_info, args.cache_detail, &theme)),
+    }
+
+    // ── NUMA topology ───────────────────────────────────────────────────────
+    if args.numa
+        && let Some(ref nodes) = cpu_info.numa_nodes
+    {
+        for node in nodes {
+            info_lines.push(InfoLine::field(
+                &format!("NUMA Node {}", node.id),
+                theme.value(&format!("CPUs {node}")),
+            ));
         }
     }
 
@@ -132,44 +799,40 @@ pub fn print_cpu_info(cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
     if let Some(flops) = cpu_info.peak_flops
         && flops > 0.0
     {
-        info_lines.push(layout::format_kv(
+        info_lines.push(InfoLine::field(
             "Peak Performance",
-            &format!("{flops:.2} GFLOP/s").green().to_string(),
-            LABEL_WIDTH,
+            theme.value(&format!("{flops:.2} GFLOP/s")),
         ));
     }
 
+    // ── Vulnerabilities ─────────────────────────────────────────────────────
+    if args.mitigations && !cpu_info.mitigations.is_empty() {
+        info_lines.push(InfoLine::Text(String::new())); // blank separator
+        info_lines.push(InfoLine::Text(theme.header("Vulnerabilities").to_string()));
+        let width = cpu_info
+            .mitigations
+            .iter()
+            .map(|m| m.vulnerability.len() + 1)
+            .max()
+            .unwrap_or(0);
+        for mitigation in &cpu_info.mitigations {
+            info_lines.push(InfoLine::Text(format!(
+                "  {}",
+                theme.kv_aligned(&mitigation.vulnerability, exposure_value(mitigation, &theme), width)
+            )));
+        }
+    }
+
     // ── CPU features ────────────────────────────────────────────────────────
     if args.features {
-        info_lines.push(String::new()); // blank separator
+        info_lines.push(InfoLine::Text(String::new())); // blank separator
 
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-        {
-            use crate::cpu::X86Features;
-            let flag_names: &[(&str, X86Features)] = &[
-                ("SSE", X86Features::SSE),
-                ("SSE2", X86Features::SSE2),
-                ("SSE3", X86Features::SSE3),
-                ("SSSE3", X86Features::SSSE3),
-                ("SSE4.1", X86Features::SSE4_1),
-                ("SSE4.2", X86Features::SSE4_2),
-                ("AVX", X86Features::AVX),
-                ("AVX2", X86Features::AVX2),
-                ("AVX-512F", X86Features::AVX512F),
-                ("FMA", X86Features::FMA),
-                ("AES", X86Features::AES),
-                ("BMI1", X86Features::BMI1),
-                ("BMI2", X86Features::BMI2),
-                ("F16C", X86Features::F16C),
-                ("POPCNT", X86Features::POPCNT),
-            ];
-            let active: Vec<&str> = flag_names
-                .iter()
-                .filter(|(_, flag)| cpu_info.features.contains(*flag))
-                .map(|(name, _)| *name)
-                .collect();
+        for category in FeatureCategory::ALL {
+            let active = feature_labels(cpu_info, category);
             if !active.is_empty() {
-                info_lines.push(format!("  {}", active.join("  ").green()));
+                info_lines.push(InfoLine::Text(theme.header(&category.to_string()).to_string()));
+                info_lines.push(InfoLine::Text(format!("  {}", theme.value(&active.join("  ")))));
             }
         }
 
@@ -186,6 +849,8 @@ pub fn print_cpu_info(cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
                 ("ATOMICS", ArmFeatures::ATOMICS),
                 ("FP", ArmFeatures::FP),
                 ("ASIMD", ArmFeatures::ASIMD),
+                ("SVE", ArmFeatures::SVE),
+                ("SVE2", ArmFeatures::SVE2),
             ];
             let active: Vec<&str> = flag_names
                 .iter()
@@ -193,19 +858,35 @@ pub fn print_cpu_info(cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
                 .map(|(name, _)| *name)
                 .collect();
             if !active.is_empty() {
-                info_lines.push(format!("  {}", active.join("  ").green()));
+                info_lines.push(InfoLine::Text(format!("  {}", theme.value(&active.join("  ")))));
             }
         }
+
+        if let Some(bits) = cpu_info.sve_vector_bits {
+            info_lines.push(InfoLine::field(
+                "SVE Vector Length",
+                theme.value(&format!("{bits} bits")),
+            ));
+        }
     }
 
     // ── Render ──────────────────────────────────────────────────────────────
-    if args.no_logo {
-        for line in &info_lines {
-            println!("{line}");
-        }
+    let info_lines = align_fields(&info_lines, &theme);
+    let info_block = info_lines.join("\n");
+    if args.no_logo || args.info_only {
+        writeln!(out, "{info_block}")?;
         return Ok(());
     }
 
+    // User-supplied art from the config file overrides the built-in logos
+    #[cfg(feature = "config")]
+    let custom_logo = crate::config::Config::load()?.custom_logo(&cpu_info.vendor)?;
+    #[cfg(not(feature = "config"))]
+    let custom_logo: Option<String> = None;
+
+    let term_width = layout::get_terminal_width();
+    let info_width = info_lines.iter().map(|l| layout::visible_len(l)).max().unwrap_or(0);
+
     // Determine logo size: auto-detect from terminal width, or use CLI override
     let logo_size = if args.logo_short {
         logo::LogoSize::Short
@@ -213,70 +894,256 @@ pub fn print_cpu_info(cpu_info: &CpuInfo, args: &Args) -> anyhow::Result<()> {
         logo::LogoSize::Long
     } else {
         // Auto-detect: try LONG first, fall back to SHORT if terminal is too narrow
-        let term_width = crossterm::terminal::size().map_or(80, |(w, _)| u32::from(w));
-        let long_logo = logo::get_raw_logo(&cpu_info.vendor, logo::LogoSize::Long);
+        let long_logo = logo::get_logo(cpu_info, logo::LogoSize::Long, custom_logo.as_deref());
         let long_width = long_logo.lines().map(visible_width).max().unwrap_or(0);
-        #[allow(clippy::cast_possible_truncation)]
-        let needed = (long_width + LOGO_INFO_GAP + LABEL_WIDTH + 40) as u32;
-        if term_width >= needed {
+        if term_width >= long_width + LOGO_INFO_GAP + LABEL_WIDTH + 40 {
             logo::LogoSize::Long
         } else {
             logo::LogoSize::Short
         }
     };
 
-    let raw_logo = logo::get_raw_logo(&cpu_info.vendor, logo_size);
+    let raw_logo = logo::get_logo(cpu_info, logo_size, custom_logo.as_deref());
     let logo_colors = logo::get_logo_colors(&cpu_info.vendor);
 
     // Compute visual width from the raw logo (before adding colour codes)
     let logo_visual_width = raw_logo.lines().map(visible_width).max().unwrap_or(0);
 
     // Colourize each logo line and pad to uniform visual width
-    let logo_lines: Vec<String> = raw_logo
+    let logo_block = raw_logo
         .lines()
         .map(|l| {
             let vis_w = visible_width(l);
             let pad = logo_visual_width.saturating_sub(vis_w);
-            if args.no_color {
-                format!("{}{}", strip_color_markers(l), " ".repeat(pad))
-            } else {
+            if use_color {
                 format!("{}{}", logo::colorize_logo_line(l, &logo_colors), " ".repeat(pad))
+            } else {
+                format!("{}{}", strip_color_markers(l), " ".repeat(pad))
             }
         })
-        .collect();
+        .collect::<Vec<_>>()
+        .join("\n");
 
-    // Print side-by-side: logo on the left, info on the right
-    let max_rows = logo_lines.len().max(info_lines.len());
-    let blank_left = " ".repeat(logo_visual_width);
+    // Logo on the left and info on the right, like the original cpufetch;
+    // stack them instead when the pair would wrap
+    if args.logo_only {
+        writeln!(out, "{logo_block}")?;
+    } else if logo_visual_width + LOGO_INFO_GAP + info_width <= term_width {
+        write!(
+            out,
+            "{}",
+            ascii::combine_horizontal(&logo_block, &info_block, logo_visual_width, LOGO_INFO_GAP)
+        )?;
+    } else {
+        writeln!(out, "{logo_block}\n\n{info_block}")?;
+    }
 
-    for i in 0..max_rows {
-        let right = info_lines.get(i).map_or("", String::as_str);
-        if let Some(left) = logo_lines.get(i) {
-            println!("{left}{}{right}", " ".repeat(LOGO_INFO_GAP));
-        } else {
-            println!("{blank_left}{}{right}", " ".repeat(LOGO_INFO_GAP));
+    Ok(())
+}
+
+/// Write a side-by-side comparison of a saved `snapshot` against `current` to `out`.
+///
+/// Only the fields that differ are listed; feature flags are shown as those
+/// gained and lost relative to the snapshot.
+///
+/// # Errors
+///
+/// Returns an error if writing to `out` fails.
+#[cfg(feature = "display")]
+pub fn print_comparison(out: &mut dyn Write, snapshot: &CpuInfo, current: &CpuInfo, args: &Args) -> anyhow::Result<()> {
+    use crate::cpu::FieldDiff;
+
+    layout::setup_display(args.no_color, args.output.is_some());
+    let theme = selected_theme(args)?;
+    let diffs = snapshot.diff(current);
+    if diffs.is_empty() {
+        writeln!(out, "{}", theme.value("No differences"))?;
+        return Ok(());
+    }
+
+    let left_width = diffs
+        .iter()
+        .filter_map(|diff| match diff {
+            FieldDiff::Value { left, .. } => Some(left.chars().count()),
+            FieldDiff::Features { .. } => None,
+        })
+        .chain([SNAPSHOT_HEADER.len()])
+        .max()
+        .unwrap_or(0);
+
+    let header = format!("{SNAPSHOT_HEADER:<left_width$}  This machine");
+    writeln!(
+        out,
+        "{}",
+        theme.header(&layout::format_kv("Field", &header, LABEL_WIDTH))
+    )?;
+    for diff in &diffs {
+        match diff {
+            FieldDiff::Value { field, left, right } => {
+                // Pad outside the colour codes, which would otherwise count towards the width
+                let padding = " ".repeat(left_width - left.chars().count());
+                let columns = format!("{}{padding}  {}", left.red(), right.green());
+                writeln!(out, "{}", theme.kv(field, columns))?;
+            },
+            FieldDiff::Features { gained, lost } => {
+                if !gained.is_empty() {
+                    writeln!(out, "{}", theme.kv("Features Gained", gained.join("  ").green()))?;
+                }
+                if !lost.is_empty() {
+                    writeln!(out, "{}", theme.kv("Features Lost", lost.join("  ").red()))?;
+                }
+            },
         }
     }
+    Ok(())
+}
+
+/// The theme named by `--theme`, else by the config file, else the default.
+///
+/// # Errors
+///
+/// Returns an error for an unknown theme name or an unreadable config file.
+#[cfg(feature = "display")]
+fn selected_theme(args: &Args) -> anyhow::Result<Theme> {
+    #[cfg(feature = "config")]
+    let name = match &args.theme {
+        Some(name) => Some(name.clone()),
+        None => crate::config::Config::load()?.theme,
+    };
+    #[cfg(not(feature = "config"))]
+    let name = args.theme.clone();
+
+    let Some(name) = name else {
+        return Ok(Theme::default());
+    };
+    Theme::named(&name)
+        .ok_or_else(|| anyhow::anyhow!("unknown theme '{name}' (expected one of: {})", Theme::NAMES.join(", ")))
+}
+
+/// Column heading for the saved side of `--compare`.
+#[cfg(feature = "display")]
+const SNAPSHOT_HEADER: &str = "Snapshot";
+
+/// Write CPU information in JSON format to `out`.
+///
+/// # Errors
+///
+/// Returns an error if serialisation or writing to `out` fails, or if the
+/// `json` feature is disabled.
+#[cfg(feature = "display")]
+pub fn print_json(out: &mut dyn Write, cpu_info: &CpuInfo) -> anyhow::Result<()> {
+    print_serialized(out, cpu_info, crate::cli::OutputFormat::Json)
+}
+
+/// Write CPU information in the given machine-readable format to `out`.
+///
+/// JSON, YAML and TOML are each backed by their own cargo feature; requesting
+/// a format whose feature was compiled out is reported as an error rather
+/// than silently falling back to another format. CSV and Prometheus are always available.
+///
+/// # Errors
+///
+/// Returns an error if serialisation or writing to `out` fails, or the format's
+/// feature is disabled.
+#[cfg(feature = "cli")]
+pub fn print_serialized(
+    out: &mut dyn std::io::Write,
+    cpu_info: &crate::cpu::CpuInfo,
+    format: crate::cli::OutputFormat,
+) -> anyhow::Result<()> {
+    use crate::cli::OutputFormat;
 
+    let output: anyhow::Result<String> = match format {
+        OutputFormat::Json => {
+            #[cfg(feature = "json")]
+            {
+                cpu_info.to_json_pretty().map_err(anyhow::Error::from)
+            }
+            #[cfg(not(feature = "json"))]
+            {
+                Err(format_feature_disabled("json"))
+            }
+        },
+        OutputFormat::Yaml => {
+            #[cfg(feature = "yaml")]
+            {
+                cpu_info.to_yaml().map_err(anyhow::Error::from)
+            }
+            #[cfg(not(feature = "yaml"))]
+            {
+                Err(format_feature_disabled("yaml"))
+            }
+        },
+        OutputFormat::Toml => {
+            #[cfg(feature = "toml")]
+            {
+                cpu_info.to_toml().map_err(anyhow::Error::from)
+            }
+            #[cfg(not(feature = "toml"))]
+            {
+                Err(format_feature_disabled("toml"))
+            }
+        },
+        OutputFormat::Csv => Ok(render::to_csv(cpu_info)),
+        OutputFormat::Prometheus => Ok(render::to_prometheus(cpu_info)),
+    };
+    writeln!(out, "{}", output?.trim_end())?;
     Ok(())
 }
 
-/// Print CPU information in JSON format.
+/// Write the `--fields` selection as `key=value` lines, or as a JSON object in the order requested.
 ///
 /// # Errors
 ///
-/// Returns an error if serialisation or writing to stdout fails.
-#[cfg(all(feature = "display", feature = "json"))]
-pub fn print_json(cpu_info: &CpuInfo) -> anyhow::Result<()> {
-    let json = serde_json::to_string_pretty(cpu_info)?;
-    println!("{json}");
+/// Returns an error for a format other than JSON, or if writing fails.
+#[cfg(feature = "cli")]
+pub fn print_fields(
+    out: &mut dyn std::io::Write,
+    fields: &[(String, crate::cpu::FieldValue)],
+    format: Option<crate::cli::OutputFormat>,
+) -> anyhow::Result<()> {
+    match format {
+        None => {
+            for (key, value) in fields {
+                writeln!(out, "{key}={value}")?;
+            }
+        },
+        Some(crate::cli::OutputFormat::Json) => {
+            #[cfg(feature = "json")]
+            {
+                /// Serialises as a map so the keys keep the requested order
+                struct Ordered<'a>(&'a [(String, crate::cpu::FieldValue)]);
+
+                impl serde::Serialize for Ordered<'_> {
+                    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+                    }
+                }
+
+                writeln!(out, "{}", serde_json::to_string_pretty(&Ordered(fields))?)?;
+            }
+            #[cfg(not(feature = "json"))]
+            {
+                let _ = fields;
+                return Err(format_feature_disabled("json"));
+            }
+        },
+        Some(format) => {
+            return Err(anyhow::anyhow!(
+                "--fields supports only --format json, not {}",
+                format!("{format:?}").to_lowercase()
+            ));
+        },
+    }
     Ok(())
 }
 
-/// No-op stub when the json feature is disabled.
-#[cfg(all(feature = "display", not(feature = "json")))]
-pub fn print_json(_cpu_info: &CpuInfo) -> anyhow::Result<()> {
-    Err(anyhow::anyhow!("JSON feature not enabled"))
+/// Error for an output format whose cargo feature was compiled out.
+#[cfg(all(feature = "cli", not(all(feature = "json", feature = "yaml", feature = "toml"))))]
+fn format_feature_disabled(name: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{name} output was requested but the '{name}' feature is not enabled (recompile with --features={name})"
+    )
 }
 
 /// Compute the visible width of a logo line (excluding `$C1`–`$C4` and `$CR` markers).
@@ -329,3 +1196,388 @@ fn strip_color_markers(line: &str) -> String {
     }
     result
 }
+
+#[cfg(test)]
+#[cfg(feature = "display")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sockets_value() {
+        let with_sockets = |sockets| CpuInfo {
+            sockets,
+            ..CpuInfo::default()
+        };
+        let flag = Args {
+            sockets: true,
+            ..Args::default()
+        };
+
+        assert_eq!(
+            sockets_value(&with_sockets(Some(2)), &Args::default()).as_deref(),
+            Some("2 sockets")
+        );
+        assert_eq!(sockets_value(&with_sockets(Some(1)), &Args::default()), None);
+        assert_eq!(
+            sockets_value(&with_sockets(Some(1)), &flag).as_deref(),
+            Some("1 socket")
+        );
+        assert_eq!(sockets_value(&with_sockets(None), &flag), None);
+    }
+
+    #[test]
+    #[cfg(feature = "frequency")]
+    fn test_per_core_lines() {
+        colored::control::set_override(false);
+
+        let theme = Theme::default();
+        let lines = align_fields(
+            &per_core_lines(
+                &[(0, 3200.0), (1, 3150.0), (2, 800.0), (3, 4800.0), (4, 2000.0)],
+                &theme,
+            ),
+            &theme,
+        );
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Cores 0-3:"));
+        assert!(lines[0].contains(" 3200  3150   800  4800 MHz"));
+        assert!(lines[1].starts_with("Core 4:"));
+
+        // CPU 1 offline: the labels follow the ids, not the positions
+        let lines = align_fields(
+            &per_core_lines(
+                &[(0, 3200.0), (2, 800.0), (3, 4800.0), (4, 2000.0), (5, 1200.0)],
+                &theme,
+            ),
+            &theme,
+        );
+        assert!(lines[0].starts_with("Cores 0,2-4:"));
+        assert!(lines[1].starts_with("Core 5:"));
+    }
+
+    #[test]
+    fn test_frequency_bar_proportional() {
+        colored::control::set_override(false);
+
+        assert_eq!(bar_filled(3200.0, 4000.0, 10), 8);
+        assert_eq!(bar_filled(2000.0, 4000.0, 20), 10);
+        // Turbo readings above the reported maximum fill the bar and no more
+        assert_eq!(bar_filled(4400.0, 4000.0, 10), 10);
+        assert_eq!(bar_filled(1000.0, 0.0, 10), 0);
+
+        let theme = Theme::default();
+        assert_eq!(
+            frequency_bar(3200.0, 4000.0, 10, true, &theme),
+            "████████░░ 3.2/4.0 GHz"
+        );
+        assert_eq!(
+            frequency_bar(3200.0, 4000.0, 10, false, &theme),
+            "########-- 3.2/4.0 GHz"
+        );
+        assert_eq!(bar_width(80), 20);
+        assert_eq!(bar_width(40), 10);
+        assert_eq!(bar_width(300), 40);
+    }
+
+    #[test]
+    fn test_locale_is_utf8() {
+        use std::ffi::OsStr;
+
+        let utf8 = Some(OsStr::new("en_US.UTF-8"));
+        assert!(locale_is_utf8(None, None, utf8));
+        assert!(locale_is_utf8(None, None, Some(OsStr::new("de_DE.utf8"))));
+        assert!(locale_is_utf8(None, None, None));
+        assert!(!locale_is_utf8(None, None, Some(OsStr::new("C"))));
+        assert!(!locale_is_utf8(Some(OsStr::new("POSIX")), None, utf8));
+        assert!(!locale_is_utf8(None, Some(OsStr::new("en_US.ISO-8859-1")), utf8));
+        // An empty variable is treated as unset
+        assert!(locale_is_utf8(Some(OsStr::new("")), None, utf8));
+
+        assert_eq!(celsius_value(45.0, true), "45.0 °C");
+        assert_eq!(celsius_value(45.0, false), "45.0 C");
+    }
+
+    fn mock_cache_info() -> CpuInfo {
+        use crate::cpu::{CacheInfo, CacheTopology, CacheType};
+
+        let mut l2 = CacheInfo::new(2, CacheType::Unified, 256);
+        l2.associativity = 8;
+        l2.line_size = 64;
+        l2.shared_by = 1;
+        let mut l3 = CacheInfo::new(3, CacheType::Unified, 16384);
+        l3.associativity = 16;
+        l3.line_size = 64;
+        l3.shared_by = 8;
+        l3.inclusive = Some(true);
+        l3.self_initializing = true;
+
+        let mut topology = CacheTopology::default();
+        topology.caches[2] = Some(l2);
+        topology.caches[3] = Some(l3);
+
+        CpuInfo {
+            cache_sizes: [None, Some(32), Some(256), Some(16384)],
+            cache_topology: Some(topology),
+            ..CpuInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_print_comparison() {
+        let snapshot = CpuInfo {
+            physical_cores: 8,
+            cache_sizes: [None, None, None, Some(16384)],
+            ..CpuInfo::default()
+        };
+        let current = CpuInfo {
+            physical_cores: 16,
+            cache_sizes: [None, None, None, Some(32768)],
+            ..CpuInfo::default()
+        };
+        let args = Args {
+            no_color: true,
+            ..Args::default()
+        };
+
+        let mut buffer = Vec::new();
+        print_comparison(&mut buffer, &snapshot, &current, &args).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "Field:                Snapshot  This machine");
+        assert_eq!(lines[1], "Physical Cores:       8         16");
+        assert_eq!(lines[2], "L3 Cache:             16384 KB  32768 KB");
+
+        buffer = Vec::new();
+        print_comparison(&mut buffer, &current, &current, &args).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "No differences\n");
+    }
+
+    #[test]
+    fn test_align_fields_to_longest_label() {
+        colored::control::set_override(false);
+
+        let theme = Theme::default();
+        let fields = [
+            ("Vendor", "AMD"),
+            ("Model", "AMD Ryzen 9 5950X 16-Core Processor"),
+            ("Microarchitecture", "Zen 3"),
+            ("Cores", "16 physical, 32 logical"),
+            ("L3 Cache", "32 MB"),
+        ];
+        let mut lines = vec![InfoLine::Text("CPU Information".to_string())];
+        lines.extend(fields.iter().map(|(label, value)| InfoLine::field(label, value)));
+
+        let rendered = align_fields(&lines, &theme);
+        assert_eq!(rendered[0], "CPU Information");
+        for (line, (_, value)) in rendered[1..].iter().zip(fields) {
+            assert!(line.ends_with(value), "{line}");
+            assert_eq!(line.len() - value.len(), "Microarchitecture:  ".len(), "{line}");
+        }
+
+        // Without the long label the column narrows
+        let rendered = align_fields(
+            &[InfoLine::field("Vendor", "AMD"), InfoLine::field("Model", "x")],
+            &theme,
+        );
+        assert_eq!(rendered, ["Vendor:  AMD", "Model:   x"]);
+    }
+
+    #[test]
+    fn test_model_number() {
+        assert_eq!(
+            model_number("Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz"),
+            Some("i7-9750H")
+        );
+        assert_eq!(model_number("AMD Ryzen 9 5950X 16-Core Processor"), Some("5950X"));
+        assert_eq!(model_number("12th Gen Intel(R) Core(TM) i7-12700K"), Some("i7-12700K"));
+        assert_eq!(
+            model_number("Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz"),
+            Some("8375C")
+        );
+        assert_eq!(model_number("Apple M1 Pro"), None);
+    }
+
+    #[test]
+    fn test_spec_url() {
+        use crate::cpu::Vendor;
+
+        let mut cpu_info = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD Ryzen 9 5950X 16-Core Processor".to_string(),
+            ..CpuInfo::default()
+        };
+        assert_eq!(
+            spec_url(&cpu_info).as_deref(),
+            Some("https://www.amd.com/en/search/site-search.html#q=5950X")
+        );
+
+        // The family must belong to the detected vendor
+        cpu_info.vendor = Vendor::Intel;
+        assert_eq!(spec_url(&cpu_info), None);
+    }
+
+    #[test]
+    fn test_cache_lines_compact() {
+        colored::control::set_override(false);
+
+        let theme = Theme::default();
+        let lines = align_fields(&cache_lines(&mock_cache_info(), false, &theme), &theme);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("L1d Cache:"));
+        assert!(lines[1].ends_with("  256 KB"));
+        assert!(lines[2].ends_with("  16 MB"));
+    }
+
+    #[test]
+    fn test_cache_table_alignment() {
+        colored::control::set_override(false);
+
+        let lines = cache_table(&mock_cache_info(), &Theme::default()).expect("mock has a topology");
+        assert_eq!(lines[0], "Caches");
+        assert_eq!(
+            &lines[1..],
+            [
+                "  Level  Type     Size    Assoc   Line  Shared",
+                "  -----  -------  ------  ------  ----  ------",
+                "  L2     Unified  256 KB  8-way   64B   1",
+                "  L3     Unified  16 MB   16-way  64B   8",
+            ]
+        );
+
+        // Sizes without geometry fall back to the plain list
+        let sizes_only = CpuInfo {
+            cache_sizes: [None, Some(32), None, None],
+            ..CpuInfo::default()
+        };
+        assert!(cache_table(&sizes_only, &Theme::default()).is_none());
+    }
+
+    #[test]
+    fn test_cache_lines_detail() {
+        colored::control::set_override(false);
+
+        let theme = Theme::default();
+        let lines = align_fields(&cache_lines(&mock_cache_info(), true, &theme), &theme);
+        // L1d has a size but no topology entry, so it stays compact
+        assert!(lines[0].ends_with("  32 KB"));
+        assert!(lines[1].ends_with("256 KB, 8-way, 64B line, shared by 1 core"));
+        assert!(lines[2].ends_with("16 MB, 16-way, 64B line, shared by 8 cores, inclusive, self-initialising"));
+    }
+
+    #[test]
+    fn test_cache_lines_detail_hybrid() {
+        use crate::cpu::{CacheInfo, CacheType, CoreKind};
+        colored::control::set_override(false);
+
+        let l2 = |kind, size_kb, shared_by| CacheInfo {
+            associativity: 10,
+            line_size: 64,
+            shared_by,
+            core_kind: Some(kind),
+            ..CacheInfo::new(2, CacheType::Unified, size_kb)
+        };
+        let mut info = mock_cache_info();
+        if let Some(topology) = &mut info.cache_topology {
+            topology.by_core_kind = vec![l2(CoreKind::Performance, 2048, 1), l2(CoreKind::Efficiency, 4096, 4)];
+        }
+
+        let theme = Theme::default();
+        let lines = align_fields(&cache_lines(&info, true, &theme), &theme);
+        assert!(lines[1].starts_with("L2 (P-core):"));
+        assert!(lines[1].ends_with("2 MB, 10-way, 64B line, private"));
+        assert!(lines[2].starts_with("L2 (E-core):"));
+        assert!(lines[2].ends_with("4 MB, 10-way, 64B line, shared by 4 cores"));
+        assert!(lines[3].starts_with("L3 Cache:"));
+
+        // The compact list keeps one line per level
+        assert_eq!(cache_lines(&info, false, &theme).len(), 3);
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_cache_lines_detail_smt() {
+        use crate::cpu::{CpuidRegisters, CpuidWrapper};
+        colored::control::set_override(false);
+
+        // An 8-core, 16-thread Intel part: leaf 4 counts logical processors, leaf 0xB gives 2 per core
+        let reader = |leaf: u32, subleaf: u32| {
+            let regs = |eax, ebx, ecx| CpuidRegisters { eax, ebx, ecx, edx: 0 };
+            match (leaf, subleaf) {
+                // "GenuineIntel"
+                (0x0, _) => CpuidRegisters {
+                    edx: 0x4965_6E69,
+                    ..regs(0x16, 0x756E_6547, 0x6C65_746E)
+                },
+                (0x1, _) => regs(0x0009_06A3, 0, 0),
+                // L2: 2 logical processors, 16-way, 1024 sets; L3: 16 logical processors, 16-way, 16384 sets
+                (0x4, 0) => regs(0x0000_4143, 0x03C0_003F, 1023),
+                (0x4, 1) => regs(0x0003_C163, 0x03C0_003F, 16383),
+                (0xB, 0) => regs(1, 2, 0x100),
+                (0xB, 1) => regs(4, 16, 0x201),
+                _ => CpuidRegisters::default(),
+            }
+        };
+        let topology = CpuidWrapper::with_reader(reader)
+            .get_cache_topology()
+            .expect("mocked leaf 4 has caches");
+        let info = CpuInfo {
+            cache_sizes: [None, None, Some(1024), Some(16384)],
+            cache_topology: Some(topology),
+            ..CpuInfo::default()
+        };
+
+        let theme = Theme::default();
+        let lines = align_fields(&cache_lines(&info, true, &theme), &theme);
+        assert!(
+            lines[0].contains("1 MB, 16-way, 64B line, shared by 1 core,"),
+            "{lines:?}"
+        );
+        assert!(
+            lines[1].contains("16 MB, 16-way, 64B line, shared by 8 cores,"),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn test_cache_lines_l4() {
+        use crate::cpu::{CacheInfo, CacheType};
+        colored::control::set_override(false);
+
+        // Crystal Well's 128 MB eDRAM
+        let mut info = mock_cache_info();
+        if let Some(topology) = &mut info.cache_topology {
+            topology.outer_levels.push(CacheInfo {
+                associativity: 16,
+                line_size: 64,
+                ..CacheInfo::new(4, CacheType::Unified, 131_072)
+            });
+        }
+
+        let theme = Theme::default();
+        let lines = align_fields(&cache_lines(&info, false, &theme), &theme);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[3].starts_with("L4 Cache:"));
+        assert!(lines[3].ends_with("  128 MB"));
+
+        let detail = align_fields(&cache_lines(&info, true, &theme), &theme);
+        assert!(detail[3].ends_with("128 MB, 16-way, 64B line"));
+
+        let table = cache_table(&info, &theme).expect("mock has a topology");
+        assert!(
+            table
+                .last()
+                .is_some_and(|row| row.starts_with("  L4     Unified  128 MB"))
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_every_x86_flag_has_one_category() {
+        use crate::cpu::X86Features;
+
+        for flag in X86Features::all().iter() {
+            let entries = X86_FEATURE_TABLE.iter().filter(|(_, f, _)| *f == flag).count();
+            assert_eq!(entries, 1, "{flag:?} must appear exactly once in X86_FEATURE_TABLE");
+        }
+    }
+}
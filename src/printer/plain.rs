@@ -0,0 +1,425 @@
+//! Plain-text rendering for every `cli` build.
+//!
+//! Builds without the `display` feature print this in place of the coloured
+//! layout, so it carries no colour codes, logo or terminal queries.
+
+use crate::cli::Args;
+use crate::cpu::CpuInfo;
+use std::fmt::Write;
+
+/// Render the fields selected by `args` as uncoloured `Label: value` lines.
+///
+/// Covers the same options as the coloured output: caches are always listed
+/// when known, and frequency, temperature, power, NUMA and feature lines
+/// follow their flags. The result ends with a newline.
+#[must_use]
+pub fn render_plain(cpu_info: &CpuInfo, args: &Args) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}:", super::report_title(cpu_info));
+    let _ = writeln!(out, "Vendor: {}", cpu_info.vendor);
+    if cpu_info.translated {
+        let _ = writeln!(out, "Model: {} {}", cpu_info.brand_string, super::ROSETTA_NOTE);
+    } else {
+        let _ = writeln!(out, "Model: {}", cpu_info.brand_string);
+    }
+    if let Some(uarch) = &cpu_info.microarch {
+        let _ = writeln!(out, "Microarchitecture: {}", super::uarch_value(uarch, cpu_info, args));
+    }
+    if let Some(version) = super::version_value(cpu_info, args) {
+        let _ = writeln!(out, "Family/Model: {version}");
+    }
+    if args.microcode {
+        let revision = cpu_info
+            .microcode
+            .map_or_else(|| "Unknown".to_string(), |revision| format!("{revision:#x}"));
+        let _ = writeln!(out, "Microcode: {revision}");
+    }
+    if args.governor
+        && let Some(policy) = super::governor_value(cpu_info)
+    {
+        let _ = writeln!(out, "Governor: {policy}");
+    }
+    if args.tsc {
+        let _ = writeln!(out, "TSC: {}", super::tsc_value(cpu_info));
+    }
+    if args.pmu {
+        let pmu = cpu_info
+            .pmu
+            .map_or_else(|| "Unknown".to_string(), |pmu| pmu.to_string());
+        let _ = writeln!(out, "PMU: {pmu}");
+    }
+    if args.address_sizes {
+        let sizes = super::address_sizes_value(cpu_info).unwrap_or_else(|| "Unknown".to_string());
+        let _ = writeln!(out, "Address Sizes: {sizes}");
+        let _ = writeln!(out, "Byte Order: {}", cpu_info.endianness);
+    }
+    let _ = writeln!(
+        out,
+        "Cores: {} physical, {} logical",
+        cpu_info.physical_cores, cpu_info.logical_cores
+    );
+    if let Some(sockets) = super::sockets_value(cpu_info, args) {
+        let _ = writeln!(out, "Sockets: {sockets}");
+    }
+
+    let cache_labels = ["L1i Cache", "L1d Cache", "L2 Cache", "L3 Cache"];
+    for (label, size) in cache_labels.iter().zip(cpu_info.cache_sizes) {
+        if let Some(kb) = size {
+            let _ = writeln!(out, "{label}: {kb} KB");
+        }
+    }
+    if let Some(kb) = cpu_info.l4() {
+        let _ = writeln!(out, "L4 Cache: {kb} KB");
+    }
+
+    let frequency = &cpu_info.frequency;
+    if args.frequency && (frequency.base.is_some() || frequency.current.is_some() || frequency.max.is_some()) {
+        let _ = writeln!(out, "Frequency: {frequency}");
+    }
+    if let Some(estimated) = frequency.estimated {
+        let _ = writeln!(out, "Estimated Frequency: {}", super::estimate_value(estimated));
+    }
+
+    if (args.clusters || args.per_core)
+        && let Some(clusters) = &cpu_info.clusters
+    {
+        for (index, cluster) in clusters.iter().enumerate() {
+            let _ = writeln!(out, "Cluster {index}: {cluster}");
+        }
+    }
+
+    if args.temp
+        && let Some(celsius) = cpu_info.temperature_celsius
+    {
+        let _ = writeln!(
+            out,
+            "Temperature: {}",
+            super::celsius_value(celsius, super::unicode_output(args))
+        );
+    }
+
+    if args.power
+        && let Some(watts) = cpu_info.power_watts
+    {
+        let _ = writeln!(out, "Package Power: {watts:.1} W");
+    }
+
+    if args.numa
+        && let Some(nodes) = &cpu_info.numa_nodes
+    {
+        for node in nodes {
+            let _ = writeln!(out, "NUMA Node {}: CPUs {node}", node.id);
+        }
+    }
+
+    if args.mitigations {
+        for mitigation in &cpu_info.mitigations {
+            let _ = writeln!(out, "Vulnerability {}: {}", mitigation.vulnerability, mitigation.status);
+        }
+    }
+
+    if args.features {
+        write_features(&mut out, cpu_info);
+        if let Some(bits) = cpu_info.sve_vector_bits {
+            let _ = writeln!(out, "SVE Vector Length: {bits} bits");
+        }
+    }
+
+    out
+}
+
+/// One line per feature category, listing the flags the CPU has and any the OS disabled.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn write_features(out: &mut String, cpu_info: &CpuInfo) {
+    for category in super::FeatureCategory::ALL {
+        let active = super::feature_labels(cpu_info, category);
+        if !active.is_empty() {
+            let _ = writeln!(out, "{category}: {}", active.join(" "));
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+fn write_features(out: &mut String, cpu_info: &CpuInfo) {
+    let active: Vec<&str> = cpu_info.features.iter_names().map(|(name, _)| name).collect();
+    if !active.is_empty() {
+        let _ = writeln!(out, "Features: {}", active.join(" "));
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+fn write_features(_out: &mut String, _cpu_info: &CpuInfo) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_cpu_info() -> CpuInfo {
+        CpuInfo {
+            vendor: crate::cpu::Vendor::AMD,
+            brand_string: "AMD Ryzen 9 5950X 16-Core Processor".to_string(),
+            physical_cores: 16,
+            logical_cores: 32,
+            cache_sizes: [Some(32), Some(32), Some(512), Some(32768)],
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            features: crate::cpu::X86Features::SSE2 | crate::cpu::X86Features::AVX2 | crate::cpu::X86Features::AES,
+            ..CpuInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_render_plain_default() {
+        let output = render_plain(&mock_cpu_info(), &Args::default());
+        assert_eq!(
+            output,
+            "CPU Information:\nVendor: AMD\nModel: AMD Ryzen 9 5950X 16-Core Processor\n\
+             Cores: 16 physical, 32 logical\nL1i Cache: 32 KB\nL1d Cache: 32 KB\nL2 Cache: 512 KB\n\
+             L3 Cache: 32768 KB\n"
+        );
+    }
+
+    #[test]
+    fn test_render_plain_microcode() {
+        let args = Args {
+            microcode: true,
+            ..Args::default()
+        };
+        let known = CpuInfo {
+            microcode: Some(0xa20_1210),
+            ..mock_cpu_info()
+        };
+        assert!(render_plain(&known, &args).contains("\nMicrocode: 0xa201210\n"));
+        // Shown as unknown, like the terminal output, rather than left out
+        assert!(render_plain(&mock_cpu_info(), &args).contains("\nMicrocode: Unknown\n"));
+    }
+
+    #[test]
+    fn test_render_plain_address_sizes() {
+        let cpu_info = CpuInfo {
+            physical_address_bits: Some(48),
+            virtual_address_bits: Some(48),
+            endianness: crate::cpu::Endianness::Little,
+            ..mock_cpu_info()
+        };
+        let args = Args {
+            address_sizes: true,
+            ..Args::default()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output.contains("\nAddress Sizes: 48 bits physical, 48 bits virtual\nByte Order: Little-endian\n"),
+            "{output}"
+        );
+
+        let output = render_plain(&mock_cpu_info(), &args);
+        assert!(output.contains("\nAddress Sizes: Unknown\nByte Order: "), "{output}");
+    }
+
+    #[test]
+    fn test_render_plain_governor() {
+        let cpu_info = CpuInfo {
+            governor: Some("performance".to_string()),
+            epp: Some("balance_performance".to_string()),
+            ..mock_cpu_info()
+        };
+        let args = Args {
+            governor: true,
+            ..Args::default()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output.contains("\nGovernor: performance, EPP: balance_performance\n"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_render_plain_process_node() {
+        let cpu_info = CpuInfo {
+            microarch: Some(crate::cpu::Microarch::RaptorLake),
+            process_node: Some("Intel 7".to_string()),
+            ..mock_cpu_info()
+        };
+        let output = render_plain(&cpu_info, &Args::default());
+        assert!(output.contains("\nMicroarchitecture: Raptor Lake\n"), "{output}");
+
+        let args = Args {
+            uarch: true,
+            ..Args::default()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output.contains("\nMicroarchitecture: Raptor Lake (Intel 7)\n"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_render_plain_tsc() {
+        let args = Args {
+            tsc: true,
+            ..Args::default()
+        };
+        let cpu_info = CpuInfo {
+            tsc_frequency_mhz: Some(2592.0),
+            invariant_tsc: true,
+            ..mock_cpu_info()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(output.contains("\nTSC: 2.592 GHz, invariant\n"), "{output}");
+
+        let output = render_plain(&mock_cpu_info(), &args);
+        assert!(output.contains("\nTSC: not invariant\n"), "{output}");
+    }
+
+    #[test]
+    fn test_render_plain_clusters() {
+        let cpu_info = CpuInfo {
+            clusters: Some(vec![
+                crate::cpu::ClusterFrequency {
+                    policy: 0,
+                    cpus: vec![0, 1, 2, 3],
+                    current: Some(1200.0),
+                    max: Some(1800.0),
+                },
+                crate::cpu::ClusterFrequency {
+                    policy: 4,
+                    cpus: vec![4, 5, 6, 7],
+                    current: Some(2400.0),
+                    max: Some(2400.0),
+                },
+            ]),
+            ..mock_cpu_info()
+        };
+        let args = Args {
+            clusters: true,
+            ..Args::default()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output.contains(
+                "\nCluster 0: CPUs 0-3: 1.200 GHz (max 1.800 GHz)\nCluster 1: CPUs 4-7: 2.400 GHz (max 2.400 GHz)\n"
+            ),
+            "{output}"
+        );
+        assert!(!render_plain(&cpu_info, &Args::default()).contains("Cluster"));
+    }
+
+    #[test]
+    fn test_render_plain_mitigations() {
+        let cpu_info = CpuInfo {
+            mitigations: vec![
+                crate::cpu::Mitigation {
+                    vulnerability: "meltdown".to_string(),
+                    status: "Not affected".to_string(),
+                },
+                crate::cpu::Mitigation {
+                    vulnerability: "spectre_v2".to_string(),
+                    status: "Mitigation: Retpolines".to_string(),
+                },
+            ],
+            ..mock_cpu_info()
+        };
+        let args = Args {
+            mitigations: true,
+            ..Args::default()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output
+                .contains("\nVulnerability meltdown: Not affected\nVulnerability spectre_v2: Mitigation: Retpolines\n"),
+            "{output}"
+        );
+        assert!(!render_plain(&cpu_info, &Args::default()).contains("Vulnerability"));
+    }
+
+    #[test]
+    fn test_render_plain_rosetta() {
+        let cpu_info = CpuInfo {
+            translated: true,
+            ..mock_cpu_info()
+        };
+        let output = render_plain(&cpu_info, &Args::default());
+        assert!(
+            output.contains("\nModel: AMD Ryzen 9 5950X 16-Core Processor (running under Rosetta 2)\n"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_render_plain_pmu() {
+        let args = Args {
+            pmu: true,
+            ..Args::default()
+        };
+        let cpu_info = CpuInfo {
+            pmu: crate::cpu::PmuInfo::from_raw(5, 8, 48, 4),
+            ..mock_cpu_info()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output.contains("\nPMU: v5, 8 general + 4 fixed counters, 48-bit\n"),
+            "{output}"
+        );
+        assert!(render_plain(&mock_cpu_info(), &args).contains("\nPMU: Unknown\n"));
+    }
+
+    #[test]
+    fn test_render_plain_package_header() {
+        let cpu_info = CpuInfo {
+            package_id: Some(1),
+            ..mock_cpu_info()
+        };
+        let output = render_plain(&cpu_info, &Args::default());
+        assert!(output.starts_with("CPU Information (Package 1):\n"), "{output}");
+    }
+
+    #[test]
+    fn test_render_plain_version_info() {
+        let cpu_info = CpuInfo {
+            version: crate::cpu::Version::new(0x19, 0x21, 0),
+            ..mock_cpu_info()
+        };
+        let args = Args {
+            version_info: true,
+            ..Args::default()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output.contains("\nFamily/Model: Family 0x19, Model 0x21, Stepping 0\n"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_render_plain_features_by_category() {
+        let args = Args {
+            features: true,
+            ..Args::default()
+        };
+        let output = render_plain(&mock_cpu_info(), &args);
+        assert!(
+            output.ends_with("L3 Cache: 32768 KB\nSIMD: SSE2 AVX2\nCrypto: AES\n"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_render_plain_features_disabled_by_os() {
+        let cpu_info = CpuInfo {
+            available_but_disabled: crate::cpu::X86Features::AVX512F,
+            ..mock_cpu_info()
+        };
+        let args = Args {
+            features: true,
+            ..Args::default()
+        };
+        let output = render_plain(&cpu_info, &args);
+        assert!(
+            output.contains("AVX-512: AVX-512F (present, disabled by OS)\n"),
+            "{output}"
+        );
+    }
+}
@@ -0,0 +1,415 @@
+//! Output renderers selected with `--format`.
+//!
+//! Each output format implements [`Renderer`], and [`renderer_for`] maps the
+//! command line's choice to one. Adding a format means adding a renderer and
+//! an [`OutputFormat`] variant; [`crate::cli::run`] does not change.
+
+use crate::cli::{Args, OutputFormat};
+use crate::cpu::CpuInfo;
+use std::borrow::Cow;
+use std::io::Write;
+
+/// Writes a CPU report in one output format
+pub trait Renderer {
+    /// Write the report for `info` to `out`, honouring the display flags in `args`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be produced or written to `out`.
+    fn render(&self, info: &CpuInfo, args: &Args, out: &mut dyn Write) -> anyhow::Result<()>;
+}
+
+/// The human-readable report: logo and colours with the `display` feature, plain text without
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalRenderer;
+
+impl Renderer for TerminalRenderer {
+    fn render(&self, info: &CpuInfo, args: &Args, out: &mut dyn Write) -> anyhow::Result<()> {
+        for (i, package) in report_packages(info, args).iter().enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            #[cfg(feature = "display")]
+            super::print_cpu_info(out, package, args)?;
+            #[cfg(not(feature = "display"))]
+            write!(out, "{}", super::render_plain(package, args))?;
+        }
+        Ok(())
+    }
+}
+
+/// Pretty-printed JSON of the whole [`CpuInfo`] (requires the `json` feature)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn render(&self, info: &CpuInfo, _args: &Args, out: &mut dyn Write) -> anyhow::Result<()> {
+        super::print_serialized(out, info, OutputFormat::Json)
+    }
+}
+
+/// YAML of the whole [`CpuInfo`] (requires the `yaml` feature)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YamlRenderer;
+
+impl Renderer for YamlRenderer {
+    fn render(&self, info: &CpuInfo, _args: &Args, out: &mut dyn Write) -> anyhow::Result<()> {
+        super::print_serialized(out, info, OutputFormat::Yaml)
+    }
+}
+
+/// TOML of the whole [`CpuInfo`] (requires the `toml` feature)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TomlRenderer;
+
+impl Renderer for TomlRenderer {
+    fn render(&self, info: &CpuInfo, _args: &Args, out: &mut dyn Write) -> anyhow::Result<()> {
+        super::print_serialized(out, info, OutputFormat::Toml)
+    }
+}
+
+/// A header row of the `--fields` keys and one row of values, for spreadsheets
+///
+/// Undetected fields are empty cells; values containing commas, quotes or
+/// line breaks are quoted as RFC 4180 describes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvRenderer;
+
+impl Renderer for CsvRenderer {
+    fn render(&self, info: &CpuInfo, _args: &Args, out: &mut dyn Write) -> anyhow::Result<()> {
+        write!(out, "{}", to_csv(info))?;
+        Ok(())
+    }
+}
+
+/// Gauges in the Prometheus text exposition format, for the node exporter's textfile collector
+///
+/// Every family has `# HELP` and `# TYPE` lines; families whose values were
+/// not detected are left out. Each feature flag of the architecture is a
+/// `cpufetch_feature{name="..."}` sample of 1 or 0.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrometheusRenderer;
+
+impl Renderer for PrometheusRenderer {
+    fn render(&self, info: &CpuInfo, _args: &Args, out: &mut dyn Write) -> anyhow::Result<()> {
+        write!(out, "{}", to_prometheus(info))?;
+        Ok(())
+    }
+}
+
+/// The renderer for `format`, or the terminal report when none is chosen.
+#[must_use]
+pub fn renderer_for(format: Option<OutputFormat>) -> Box<dyn Renderer> {
+    match format {
+        None => Box::new(TerminalRenderer),
+        Some(OutputFormat::Json) => Box::new(JsonRenderer),
+        Some(OutputFormat::Yaml) => Box::new(YamlRenderer),
+        Some(OutputFormat::Toml) => Box::new(TomlRenderer),
+        Some(OutputFormat::Csv) => Box::new(CsvRenderer),
+        Some(OutputFormat::Prometheus) => Box::new(PrometheusRenderer),
+    }
+}
+
+/// The CPUs the terminal report shows: one per package with `--all-packages`, else the whole system.
+fn report_packages(cpu_info: &CpuInfo, args: &Args) -> Vec<CpuInfo> {
+    if args.all_packages {
+        cpu_info.split_packages()
+    } else {
+        vec![cpu_info.clone()]
+    }
+}
+
+/// The two CSV lines for `info`, each ending in a newline.
+pub(super) fn to_csv(info: &CpuInfo) -> String {
+    let fields = info.named_fields();
+    let header: Vec<&str> = fields.iter().map(|(key, _)| *key).collect();
+    let values: Vec<String> = fields
+        .iter()
+        .map(|(_, value)| csv_cell(&value.to_string()).into_owned())
+        .collect();
+    format!("{}\n{}\n", header.join(","), values.join(","))
+}
+
+/// The Prometheus exposition of `info`, one metric family after another.
+pub(super) fn to_prometheus(info: &CpuInfo) -> String {
+    let mut metrics = Metrics::default();
+
+    let identity = [
+        ("vendor", info.vendor.to_string()),
+        ("brand", info.brand_string.clone()),
+        (
+            "microarch",
+            info.microarch.as_ref().map(ToString::to_string).unwrap_or_default(),
+        ),
+    ];
+    metrics.family(
+        "cpufetch_info",
+        "CPU identity as labels; the value is always 1",
+        [(identity.as_slice(), Some(1.0))],
+    );
+    metrics.gauge(
+        "cpufetch_physical_cores",
+        "Physical CPU cores",
+        Some(f64::from(info.physical_cores)),
+    );
+    metrics.gauge(
+        "cpufetch_logical_cores",
+        "Logical CPUs (hardware threads)",
+        Some(f64::from(info.logical_cores)),
+    );
+    metrics.gauge(
+        "cpufetch_sockets",
+        "CPU packages (sockets)",
+        info.sockets.map(f64::from),
+    );
+
+    let levels = [
+        ("l1i", info.l1i()),
+        ("l1d", info.l1d()),
+        ("l2", info.l2()),
+        ("l3", info.l3()),
+    ];
+    let caches: Vec<_> = levels
+        .iter()
+        .map(|(level, kb)| ([("level", (*level).to_string())], kb.map(f64::from)))
+        .collect();
+    metrics.family(
+        "cpufetch_cache_kb",
+        "Cache size in KB by level",
+        caches.iter().map(|(labels, kb)| (labels.as_slice(), *kb)),
+    );
+
+    let frequency = &info.frequency;
+    let kinds = [
+        ("base", frequency.base),
+        ("max", frequency.max),
+        ("current", frequency.current),
+    ];
+    let clocks: Vec<_> = kinds
+        .iter()
+        .map(|(kind, mhz)| ([("kind", (*kind).to_string())], *mhz))
+        .collect();
+    metrics.family(
+        "cpufetch_frequency_mhz",
+        "CPU frequency in MHz",
+        clocks.iter().map(|(labels, mhz)| (labels.as_slice(), *mhz)),
+    );
+
+    metrics.gauge(
+        "cpufetch_temperature_celsius",
+        "CPU package temperature in degrees Celsius",
+        info.temperature_celsius.map(f64::from),
+    );
+    metrics.gauge(
+        "cpufetch_power_watts",
+        "Average CPU package power in watts",
+        info.power_watts.map(f64::from),
+    );
+    metrics.gauge(
+        "cpufetch_peak_gflops",
+        "Theoretical peak double-precision GFLOP/s",
+        info.peak_flops,
+    );
+
+    let features: Vec<_> = crate::cpu::feature_names()
+        .into_iter()
+        .map(|name| {
+            let present = info.has_feature_named(name).unwrap_or(false);
+            (
+                [("name", name.to_ascii_lowercase())],
+                Some(if present { 1.0 } else { 0.0 }),
+            )
+        })
+        .collect();
+    metrics.family(
+        "cpufetch_feature",
+        "Whether the CPU supports the instruction set feature (1) or not (0)",
+        features.iter().map(|(labels, value)| (labels.as_slice(), *value)),
+    );
+
+    metrics.0
+}
+
+/// Prometheus text being built, one gauge family at a time
+#[derive(Default)]
+struct Metrics(String);
+
+impl Metrics {
+    /// Add an unlabelled gauge, unless its value is unknown.
+    fn gauge(&mut self, name: &str, help: &str, value: Option<f64>) {
+        self.family(name, help, [([].as_slice(), value)]);
+    }
+
+    /// Add a gauge family from `(labels, value)` samples, skipping unknown values
+    /// and the whole family when none is known.
+    fn family<'a>(
+        &mut self,
+        name: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (&'a [(&'a str, String)], Option<f64>)>,
+    ) {
+        use std::fmt::Write as _;
+
+        let mut samples = samples
+            .into_iter()
+            .filter_map(|(labels, value)| Some((labels, value?)))
+            .peekable();
+        if samples.peek().is_none() {
+            return;
+        }
+
+        let _ = writeln!(self.0, "# HELP {name} {help}");
+        let _ = writeln!(self.0, "# TYPE {name} gauge");
+        for (labels, value) in samples {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", label_value(value)))
+                .collect();
+            if labels.is_empty() {
+                let _ = writeln!(self.0, "{name} {value}");
+            } else {
+                let _ = writeln!(self.0, "{name}{{{}}} {value}", labels.join(","));
+            }
+        }
+    }
+}
+
+/// Escape a label value for the Prometheus text format.
+fn label_value(text: &str) -> Cow<'_, str> {
+    if text.contains(['\\', '"', '\n']) {
+        Cow::Owned(text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Quote `text` for a CSV cell when it contains a separator, quote or line break.
+fn csv_cell(text: &str) -> Cow<'_, str> {
+    if text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Vendor;
+
+    fn mock_cpu_info() -> CpuInfo {
+        CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD EPYC 7763 64-Core Processor, \"Milan\"".to_string(),
+            physical_cores: 64,
+            logical_cores: 128,
+            cache_sizes: [Some(32), Some(32), Some(512), Some(262_144)],
+            ..CpuInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_csv_renderer() {
+        let mut out = Vec::new();
+        CsvRenderer
+            .render(&mock_cpu_info(), &Args::default(), &mut out)
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2, "{text}");
+        assert!(lines[0].starts_with("vendor,brand,microarch,"), "{}", lines[0]);
+        assert!(
+            lines[1].starts_with("AMD,\"AMD EPYC 7763 64-Core Processor, \"\"Milan\"\"\",,"),
+            "{}",
+            lines[1]
+        );
+
+        // Header and row line up column for column
+        let header: Vec<&str> = lines[0].split(',').collect();
+        let cores = header.iter().position(|key| *key == "cores").unwrap();
+        let l3 = header.iter().position(|key| *key == "l3").unwrap();
+        let row: Vec<&str> = lines[1].split(',').collect();
+        // The quoted brand spans two pieces of the naive split
+        let offset = row.len() - header.len();
+        assert_eq!(row[cores + offset], "64");
+        assert_eq!(row[l3 + offset], "262144");
+    }
+
+    #[test]
+    fn test_csv_cell_quoting() {
+        assert_eq!(csv_cell("plain"), "plain");
+        assert_eq!(csv_cell("a,b"), "\"a,b\"");
+        assert_eq!(csv_cell("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_cell(""), "");
+    }
+
+    #[test]
+    fn test_prometheus_renderer_is_well_formed() {
+        let info = CpuInfo {
+            frequency: crate::cpu::Frequency::new(Some(2450.0), Some(3500.0), None),
+            ..mock_cpu_info()
+        };
+        let mut out = Vec::new();
+        PrometheusRenderer.render(&info, &Args::default(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        // Every sample belongs to the family announced by the HELP and TYPE lines before it
+        let mut family = None;
+        for line in text.lines() {
+            if let Some(help) = line.strip_prefix("# HELP ") {
+                let (name, doc) = help.split_once(' ').expect("HELP has a name and text");
+                assert!(!doc.is_empty());
+                family = Some(name);
+            } else if let Some(kind) = line.strip_prefix("# TYPE ") {
+                assert_eq!(kind, format!("{} gauge", family.unwrap()));
+            } else {
+                let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+                let name = series.split('{').next().unwrap();
+                assert_eq!(Some(name), family, "{line}");
+                assert!(name.chars().all(|c| c.is_ascii_lowercase() || c == '_'), "{line}");
+                if let Some(labels) = series.strip_prefix(name).filter(|labels| !labels.is_empty()) {
+                    assert!(labels.starts_with('{') && labels.ends_with('}'), "{line}");
+                }
+                value.parse::<f64>().expect("sample value is a number");
+            }
+        }
+
+        assert!(text.contains("\ncpufetch_logical_cores 128\n"), "{text}");
+        assert!(text.contains("\ncpufetch_cache_kb{level=\"l3\"} 262144\n"), "{text}");
+        assert!(text.contains("\ncpufetch_frequency_mhz{kind=\"max\"} 3500\n"), "{text}");
+        // Unknown values are left out rather than reported as zero
+        assert!(!text.contains("kind=\"current\""), "{text}");
+        assert!(!text.contains("cpufetch_sockets"), "{text}");
+        assert!(
+            text.contains(r#"brand="AMD EPYC 7763 64-Core Processor, \"Milan\"""#),
+            "{text}"
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_prometheus_feature_gauges() {
+        let info = CpuInfo {
+            features: crate::cpu::X86Features::AVX2,
+            ..mock_cpu_info()
+        };
+        let text = to_prometheus(&info);
+        assert!(text.contains("\ncpufetch_feature{name=\"avx2\"} 1\n"), "{text}");
+        assert!(text.contains("\ncpufetch_feature{name=\"avx512f\"} 0\n"), "{text}");
+    }
+
+    #[test]
+    fn test_renderer_for_terminal_by_default() {
+        let mut out = Vec::new();
+        let args = Args {
+            no_logo: true,
+            no_color: true,
+            ..Args::default()
+        };
+        renderer_for(None).render(&mock_cpu_info(), &args, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("AMD EPYC 7763"), "{text}");
+        assert!(!text.starts_with("vendor,"), "{text}");
+    }
+}
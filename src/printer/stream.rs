@@ -0,0 +1,121 @@
+//! JSON Lines streaming for telemetry collectors.
+//!
+//! Emits one compact JSON object per refresh, newline-delimited, so tools
+//! such as `jq` or a log shipper can consume the readings as they arrive.
+//! The static fields come from the initial detection; only the volatile ones
+//! (frequency, temperature, per-core clocks) are re-read each tick.
+
+use crate::cli::Args;
+use crate::cpu::CpuInfo;
+use serde::Serialize;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// One line of the stream: the usual JSON report plus the sample time and per-core clocks.
+#[derive(Serialize)]
+struct StreamLine<'a> {
+    schema_version: u32,
+    /// Milliseconds since the Unix epoch when the sample was taken
+    timestamp_ms: u64,
+    #[serde(flatten)]
+    info: &'a CpuInfo,
+    /// Per-core frequencies in MHz, with `--per-core`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    per_core_mhz: Option<&'a [f64]>,
+}
+
+/// Write one JSON object per `interval` to `out` until it is closed.
+///
+/// Each line is flushed as soon as it is written. A closed pipe (the reader
+/// exiting) ends the stream without an error; otherwise it runs until the
+/// process is interrupted.
+///
+/// # Errors
+///
+/// Returns an error if frequency sampling cannot start or writing to `out` fails.
+pub fn stream_json(out: &mut dyn Write, cpu_info: &CpuInfo, args: &Args, interval: Duration) -> anyhow::Result<()> {
+    let mut monitor = crate::cpu::FrequencyMonitor::new()?;
+    let per_core = args.per_core;
+    stream_lines(out, cpu_info, interval, None, |info| {
+        info.frequency = monitor.refresh();
+        #[cfg(feature = "thermal")]
+        {
+            info.temperature_celsius = crate::cpu::detect_temperature().ok().flatten();
+        }
+        per_core
+            .then(|| crate::cpu::detect_per_core_frequencies().ok())
+            .flatten()
+            .map(|cores| cores.into_iter().map(|(_, mhz)| mhz).collect())
+    })
+}
+
+/// The stream loop, stopping after `limit` lines when given.
+///
+/// `sample` refreshes the volatile fields of the cached info and returns the
+/// per-core frequencies, if they are wanted.
+fn stream_lines(
+    out: &mut dyn Write,
+    cpu_info: &CpuInfo,
+    interval: Duration,
+    limit: Option<usize>,
+    mut sample: impl FnMut(&mut CpuInfo) -> Option<Vec<f64>>,
+) -> anyhow::Result<()> {
+    let mut info = cpu_info.clone();
+    let mut written = 0;
+    loop {
+        let per_core = sample(&mut info);
+        let line = serde_json::to_string(&StreamLine {
+            schema_version: crate::JSON_SCHEMA_VERSION,
+            timestamp_ms: timestamp_ms(),
+            info: &info,
+            per_core_mhz: per_core.as_deref(),
+        })?;
+        match writeln!(out, "{line}").and_then(|()| out.flush()) {
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+
+        written += 1;
+        if limit.is_some_and(|limit| written >= limit) {
+            return Ok(());
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Milliseconds since the Unix epoch, or 0 if the clock is set before it.
+fn timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Frequency;
+
+    #[test]
+    fn test_stream_lines_are_json() {
+        let mut out = Vec::new();
+        let mut mhz = 3000.0;
+        stream_lines(&mut out, &CpuInfo::default(), Duration::ZERO, Some(2), |info| {
+            mhz += 100.0;
+            info.frequency = Frequency::new(None, Some(4000.0), Some(mhz));
+            Some(vec![mhz, 800.0])
+        })
+        .unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line is one JSON object"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["frequency"]["current"], 3100.0);
+        assert_eq!(lines[1]["frequency"]["current"], 3200.0);
+        assert_eq!(lines[1]["per_core_mhz"][1], 800.0);
+        assert!(lines[0]["timestamp_ms"].as_u64().is_some());
+        assert_eq!(lines[0]["schema_version"], crate::JSON_SCHEMA_VERSION);
+    }
+}
@@ -0,0 +1,207 @@
+//! Colour themes for the terminal output.
+//!
+//! The printer asks the theme for a role (label, value, header, accent, or a
+//! [`Level`] on a low/medium/high scale) instead of naming a colour, so a
+//! preset can restyle every line at once.
+//! Colour can still be switched off entirely with `--no-color` or `NO_COLOR`,
+//! whatever the theme. The red/green of `--compare` and the vendor logos keep
+//! their own colours, as those carry meaning.
+
+use super::{LABEL_WIDTH, layout};
+use colored::{Color, ColoredString, Colorize};
+use std::fmt::Display;
+
+/// Colours for each role in the output; `None` leaves the terminal's default colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Field labels such as "Vendor:"
+    pub label: Option<Color>,
+    /// Field values
+    pub value: Option<Color>,
+    /// Section headings such as "CPU Information" (always bold)
+    pub header: Option<Color>,
+    /// Values that deserve attention, e.g. "Unknown" or a hypervisor name
+    pub accent: Option<Color>,
+    /// Low, medium and high [`Level`] colours, for the `--bars` frequency bar and vulnerability status
+    pub levels: Option<[Color; 3]>,
+}
+
+/// A step on the scale [`Theme::level`] colours: traffic-light green, yellow and red by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Nothing to worry about
+    Low,
+    /// Worth a look
+    Medium,
+    /// Close to a limit, or a problem
+    High,
+}
+
+impl Level {
+    /// The level of `ratio` of a limit: low below 60%, medium below 85%, high from there on.
+    #[must_use]
+    pub fn of_ratio(ratio: f64) -> Self {
+        match ratio {
+            ratio if ratio < 0.6 => Self::Low,
+            ratio if ratio < 0.85 => Self::Medium,
+            _ => Self::High,
+        }
+    }
+}
+
+impl Default for Theme {
+    /// The original cpufetch-rs colours: plain labels, green values and cyan headings
+    fn default() -> Self {
+        Self {
+            label: None,
+            value: Some(Color::Green),
+            header: Some(Color::Cyan),
+            accent: Some(Color::Yellow),
+            levels: Some([Color::Green, Color::Yellow, Color::Red]),
+        }
+    }
+}
+
+impl Theme {
+    /// Names accepted by [`Theme::named`], `--theme` and the config file's `theme` key
+    pub const NAMES: &[&str] = &["default", "mono", "solarized", "nord"];
+
+    /// The preset called `name` (case-insensitive), or `None` if there is no such preset.
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self> {
+        let color = |r, g, b| Color::TrueColor { r, g, b };
+        let rgb = |r, g, b| Some(color(r, g, b));
+        match name.to_ascii_lowercase().as_str() {
+            "default" => Some(Self::default()),
+            // Bold headings only, for terminals with a carefully chosen palette
+            "mono" => Some(Self {
+                label: None,
+                value: None,
+                header: None,
+                accent: None,
+                levels: None,
+            }),
+            // https://ethanschoonover.com/solarized/
+            "solarized" => Some(Self {
+                label: rgb(0x93, 0xA1, 0xA1),
+                value: rgb(0x85, 0x99, 0x00),
+                header: rgb(0x26, 0x8B, 0xD2),
+                accent: rgb(0xB5, 0x89, 0x00),
+                levels: Some([
+                    color(0x85, 0x99, 0x00),
+                    color(0xB5, 0x89, 0x00),
+                    color(0xDC, 0x32, 0x2F),
+                ]),
+            }),
+            // https://www.nordtheme.com/docs/colors-and-palettes
+            "nord" => Some(Self {
+                label: rgb(0xD8, 0xDE, 0xE9),
+                value: rgb(0xA3, 0xBE, 0x8C),
+                header: rgb(0x88, 0xC0, 0xD0),
+                accent: rgb(0xEB, 0xCB, 0x8B),
+                levels: Some([
+                    color(0xA3, 0xBE, 0x8C),
+                    color(0xEB, 0xCB, 0x8B),
+                    color(0xBF, 0x61, 0x6A),
+                ]),
+            }),
+            _ => None,
+        }
+    }
+
+    /// `text` styled as a field value.
+    #[must_use]
+    pub fn value(&self, text: &str) -> ColoredString {
+        paint(text, self.value)
+    }
+
+    /// `text` styled as a section heading.
+    #[must_use]
+    pub fn header(&self, text: &str) -> ColoredString {
+        paint(text, self.header).bold()
+    }
+
+    /// `text` styled as a value that deserves attention.
+    #[must_use]
+    pub fn accent(&self, text: &str) -> ColoredString {
+        paint(text, self.accent)
+    }
+
+    /// `text` styled for its place on the low/medium/high scale.
+    #[must_use]
+    pub fn level(&self, text: &str, level: Level) -> ColoredString {
+        paint(text, self.levels.map(|colors| colors[level as usize]))
+    }
+
+    /// A `label:  value` line like `layout::format_kv`, with the label in the theme's label colour.
+    ///
+    /// The label is padded before it is coloured so that the escape codes do
+    /// not count towards the column width.
+    #[must_use]
+    pub fn kv(&self, label: &str, value: impl Display) -> String {
+        self.kv_aligned(label, value, LABEL_WIDTH)
+    }
+
+    /// Like [`Theme::kv`], with the label column `label_width` characters wide.
+    #[must_use]
+    pub fn kv_aligned(&self, label: &str, value: impl Display, label_width: usize) -> String {
+        let labelled = format!("{label}:");
+        let padding = " ".repeat(label_width.saturating_sub(labelled.len()));
+        match self.label {
+            Some(color) => format!("{}{padding}  {value}", labelled.color(color)),
+            None => layout::format_kv(label, &value.to_string(), label_width),
+        }
+    }
+}
+
+fn paint(text: &str, color: Option<Color>) -> ColoredString {
+    match color {
+        Some(color) => text.color(color),
+        None => text.normal(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_name_is_a_preset() {
+        for name in Theme::NAMES {
+            assert!(Theme::named(name).is_some(), "no preset for {name}");
+        }
+        assert_eq!(Theme::named("Nord"), Theme::named("nord"));
+        assert_eq!(Theme::named("dracula"), None);
+    }
+
+    #[test]
+    fn test_default_matches_original_colours() {
+        let theme = Theme::default();
+        assert_eq!(theme.value("AMD"), "AMD".green());
+        assert_eq!(theme.header("Caches"), "Caches".cyan().bold());
+        assert_eq!(theme.accent("Unknown"), "Unknown".yellow());
+        assert_eq!(theme.level("####", Level::of_ratio(0.9)), "####".red());
+        assert_eq!(
+            theme.kv("Vendor", "AMD"),
+            layout::format_kv("Vendor", "AMD", LABEL_WIDTH)
+        );
+    }
+
+    #[test]
+    fn test_level_of_ratio() {
+        assert_eq!(Level::of_ratio(0.3), Level::Low);
+        assert_eq!(Level::of_ratio(0.6), Level::Medium);
+        assert_eq!(Level::of_ratio(0.85), Level::High);
+        assert_eq!(Level::of_ratio(1.1), Level::High);
+
+        // The mono preset leaves the scale uncoloured too
+        let mono = Theme::named("mono").unwrap();
+        assert_eq!(mono.level("####", Level::High), "####".normal());
+    }
+
+    #[test]
+    fn test_coloured_label_keeps_alignment() {
+        let line = Theme::named("nord").unwrap().kv("Vendor", "AMD");
+        assert_eq!(layout::visible_len(&line), LABEL_WIDTH + 2 + "AMD".len());
+    }
+}
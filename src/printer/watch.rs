@@ -0,0 +1,128 @@
+//! Live frequency watch mode.
+//!
+//! Prints the static CPU information once, then redraws only the frequency
+//! block in place at a fixed interval so turbo and power-saving behaviour can
+//! be observed without the rest of the output scrolling away.
+
+use super::Theme;
+use crate::cli::Args;
+use crate::cpu::{CpuInfo, Frequency};
+use colored::Colorize;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
+use std::io::{Write, stdout};
+use std::time::Duration;
+
+/// Restores the terminal on drop, so the cursor reappears even on an early error return.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> anyhow::Result<Self> {
+        execute!(stdout(), cursor::Hide)?;
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+        let _ = execute!(stdout(), cursor::Show);
+    }
+}
+
+/// Print the static CPU information once and refresh the frequency lines every `interval`.
+///
+/// Runs until the user presses Ctrl-C, `q`, or Esc. Raw mode is used so that
+/// Ctrl-C arrives as a key event rather than killing the process with the
+/// cursor still hidden.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be configured or writing to stdout fails.
+pub fn watch_loop(cpu_info: &CpuInfo, args: &Args, interval: Duration) -> anyhow::Result<()> {
+    super::print_cpu_info(&mut stdout(), cpu_info, args)?;
+    println!();
+    let theme = super::selected_theme(args)?;
+
+    let _guard = TerminalGuard::new()?;
+    let mut out = stdout();
+    let mut drawn = 0u16;
+    let mut monitor = crate::cpu::FrequencyMonitor::new()?;
+
+    loop {
+        let frequency = monitor.refresh();
+        let lines = frequency_lines(&frequency, &theme);
+
+        if drawn > 0 {
+            execute!(out, cursor::MoveUp(drawn))?;
+        }
+        for line in &lines {
+            execute!(out, terminal::Clear(terminal::ClearType::CurrentLine))?;
+            // Raw mode disables output post-processing, so emit an explicit carriage return.
+            write!(out, "{line}\r\n")?;
+        }
+        out.flush()?;
+        drawn = u16::try_from(lines.len()).unwrap_or(u16::MAX);
+
+        if event::poll(interval)?
+            && let Event::Key(key) = event::read()?
+            && is_exit_key(key)
+        {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a key event should terminate the watch loop.
+fn is_exit_key(key: KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+        || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+/// Build the frequency block shown in watch mode.
+///
+/// Always returns the same number of lines (missing values are shown as
+/// "Unknown") so that the in-place redraw never leaves stale rows behind.
+fn frequency_lines(frequency: &Frequency, theme: &Theme) -> Vec<String> {
+    let fmt_ghz = |mhz: Option<f64>| mhz.map_or_else(|| "Unknown".to_string(), |v| format!("{:.3} GHz", v / 1000.0));
+
+    vec![
+        theme.kv("Current Frequency", theme.value(&fmt_ghz(frequency.current)).bold()),
+        theme.kv("Average Frequency", theme.value(&fmt_ghz(frequency.average))),
+        theme.kv("Max Frequency", theme.value(&fmt_ghz(frequency.max))),
+        theme.kv("Base Frequency", theme.value(&fmt_ghz(frequency.base))),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequency_lines_constant_height() {
+        colored::control::set_override(false);
+
+        let empty = frequency_lines(&Frequency::default(), &Theme::default());
+        let full = frequency_lines(
+            &Frequency {
+                average: Some(2100.0),
+                ..Frequency::new(Some(2800.0), Some(4000.0), Some(3200.0))
+            },
+            &Theme::default(),
+        );
+
+        assert_eq!(empty.len(), full.len());
+        assert!(empty[0].contains("Unknown"));
+        assert!(full[0].contains("3.200 GHz"));
+    }
+
+    #[test]
+    fn test_exit_keys() {
+        assert!(is_exit_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(is_exit_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert!(!is_exit_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE)));
+    }
+}
@@ -0,0 +1,72 @@
+//! Pluggable source of [`CpuInfo`].
+//!
+//! Everything above detection (the printers and [`crate::run`]) only needs a
+//! `CpuInfo`, so an embedding application can substitute recorded or remote
+//! data, and tests a fixed snapshot, for the real hardware.
+
+use crate::Error;
+use crate::cpu::CpuInfo;
+use crate::detect::Detection;
+
+/// A source of CPU information
+pub trait CpuInfoProvider {
+    /// The CPU information to report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the information cannot be obtained.
+    fn cpu_info(&self) -> Result<CpuInfo, Error>;
+
+    /// The CPU information together with any non-fatal problems met obtaining it.
+    ///
+    /// Defaults to [`CpuInfoProvider::cpu_info`] with no warnings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the information cannot be obtained.
+    fn detect(&self) -> Result<Detection, Error> {
+        Ok(Detection {
+            info: self.cpu_info()?,
+            warnings: Vec::new(),
+        })
+    }
+}
+
+/// Detects the CPU the process is running on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NativeProvider;
+
+impl CpuInfoProvider for NativeProvider {
+    fn cpu_info(&self) -> Result<CpuInfo, Error> {
+        Ok(CpuInfo::new()?)
+    }
+
+    fn detect(&self) -> Result<Detection, Error> {
+        crate::detect()
+    }
+}
+
+/// A fixed snapshot, e.g. one loaded from a `--json` report
+impl CpuInfoProvider for CpuInfo {
+    fn cpu_info(&self) -> Result<CpuInfo, Error> {
+        Ok(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Vendor;
+
+    #[test]
+    fn test_snapshot_provider_returns_itself() {
+        let snapshot = CpuInfo {
+            vendor: Vendor::AMD,
+            brand_string: "AMD Ryzen 9 5950X 16-Core Processor".to_string(),
+            ..CpuInfo::default()
+        };
+        let detection = snapshot.detect().unwrap();
+        assert_eq!(detection.info.brand_string, snapshot.brand_string);
+        assert!(detection.warnings.is_empty());
+    }
+}
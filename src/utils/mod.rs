@@ -1 +1 @@
-
+pub mod platform;
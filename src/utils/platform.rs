@@ -0,0 +1,169 @@
+//! Platform-specific system queries shared by the architecture backends.
+//!
+//! These helpers answer questions that are not architecture-specific (core
+//! counts, package counts) but whose best source differs per operating system.
+
+/// Physical and logical core counts for the whole system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CoreCounts {
+    /// Physical cores across all packages (P-cores and E-cores both count as one)
+    pub physical: u32,
+    /// Hardware threads visible to the OS across all packages
+    pub logical: u32,
+    /// Number of physical processor packages, when the platform reports it
+    pub packages: Option<u32>,
+}
+
+impl CoreCounts {
+    /// Guarantee `logical >= physical`.
+    ///
+    /// Container CPU quotas and some hybrid topologies can make the physical
+    /// count reported by generic APIs exceed the logical count, which is never
+    /// true of real hardware.
+    #[must_use]
+    pub fn normalised(self) -> Self {
+        Self {
+            physical: self.physical.min(self.logical),
+            ..self
+        }
+    }
+}
+
+/// Detect physical and logical core counts.
+///
+/// On Windows, WMI's `Win32_Processor` is queried first because it reports
+/// per-package `NumberOfCores` correctly on hybrid (big.LITTLE) parts, where
+/// `num_cpus::get_physical()` can undercount. (The Win32
+/// `GetLogicalProcessorInformationEx` API would require `unsafe`, which this
+/// crate forbids.) All other platforms, and Windows when WMI fails, fall back
+/// to `num_cpus`; on Linux the package count is then taken from the distinct
+/// `physical id` values in `/proc/cpuinfo`. Without the `std` feature no OS is
+/// queried and all counts are zero.
+#[must_use]
+pub fn detect_core_counts() -> CoreCounts {
+    #[cfg(not(feature = "std"))]
+    return CoreCounts::default();
+
+    #[cfg(all(target_os = "windows", feature = "windows"))]
+    if let Some(counts) = detect_core_counts_windows() {
+        return counts.normalised();
+    }
+
+    #[cfg(feature = "std")]
+    CoreCounts {
+        physical: u32::try_from(num_cpus::get_physical()).unwrap_or(0),
+        logical: u32::try_from(num_cpus::get()).unwrap_or(0),
+        packages: detect_packages(),
+    }
+    .normalised()
+}
+
+#[cfg(feature = "std")]
+fn detect_packages() -> Option<u32> {
+    #[cfg(target_os = "linux")]
+    return std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| count_physical_ids(&cpuinfo));
+
+    #[cfg(not(target_os = "linux"))]
+    None
+}
+
+/// Number of distinct `physical id` values in `/proc/cpuinfo` content.
+///
+/// Each package numbers its own logical CPUs, so repeated ids are the same
+/// socket. `None` when the field is absent, as on most Arm kernels.
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn count_physical_ids(cpuinfo: &str) -> Option<u32> {
+    let ids: std::collections::BTreeSet<&str> = cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(key, _)| key.trim() == "physical id")
+        .map(|(_, value)| value.trim())
+        .collect();
+    u32::try_from(ids.len()).ok().filter(|&count| count > 0)
+}
+
+#[cfg(all(target_os = "windows", feature = "windows"))]
+fn detect_core_counts_windows() -> Option<CoreCounts> {
+    use serde::Deserialize;
+    use wmi::{COMLibrary, WMIConnection};
+
+    // One Win32_Processor instance is returned per physical package
+    #[allow(non_camel_case_types, non_snake_case)]
+    #[derive(Deserialize, Debug)]
+    struct Win32_Processor {
+        NumberOfCores: Option<u32>,
+        NumberOfLogicalProcessors: Option<u32>,
+    }
+
+    let com_lib = COMLibrary::new().ok()?;
+    let wmi_con = WMIConnection::new(com_lib).ok()?;
+    let processors: Vec<Win32_Processor> = wmi_con.query().ok()?;
+    if processors.is_empty() {
+        return None;
+    }
+
+    let physical = processors.iter().map(|p| p.NumberOfCores).sum::<Option<u32>>()?;
+    let logical = processors
+        .iter()
+        .map(|p| p.NumberOfLogicalProcessors)
+        .sum::<Option<u32>>()?;
+
+    Some(CoreCounts {
+        physical,
+        logical,
+        packages: u32::try_from(processors.len()).ok(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalised_clamps_physical() {
+        let counts = CoreCounts {
+            physical: 16,
+            logical: 4,
+            packages: None,
+        }
+        .normalised();
+        assert_eq!(counts.physical, 4);
+        assert_eq!(counts.logical, 4);
+    }
+
+    #[test]
+    fn test_normalised_keeps_smt() {
+        let counts = CoreCounts {
+            physical: 8,
+            logical: 16,
+            packages: Some(1),
+        }
+        .normalised();
+        assert_eq!(counts.physical, 8);
+        assert_eq!(counts.packages, Some(1));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    fn test_count_physical_ids() {
+        // Two sockets, two hardware threads each, listed interleaved as the kernel does
+        let cpuinfo = "processor\t: 0\nphysical id\t: 0\ncore id\t\t: 0\n\n\
+                       processor\t: 1\nphysical id\t: 1\ncore id\t\t: 0\n\n\
+                       processor\t: 2\nphysical id\t: 0\ncore id\t\t: 1\n\n\
+                       processor\t: 3\nphysical id\t: 1\ncore id\t\t: 1\n";
+        assert_eq!(count_physical_ids(cpuinfo), Some(2));
+        assert_eq!(count_physical_ids("processor\t: 0\nphysical id\t: 0\n"), Some(1));
+        // Arm kernels omit the field entirely
+        assert_eq!(count_physical_ids("processor\t: 0\nCPU implementer\t: 0x41\n"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_detect_core_counts() {
+        let counts = detect_core_counts();
+        assert!(counts.logical >= counts.physical);
+        assert!(counts.physical > 0);
+    }
+}
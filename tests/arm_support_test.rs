@@ -16,18 +16,10 @@ mod arm_tests {
     /// Create a mock ARM CPU for testing
     fn create_mock_arm_cpu() -> CpuInfo {
         // Create default frequency with some values
-        let frequency = cpufetch_rs::cpu::info::Frequency {
-            base: Some(2500.0),
-            current: Some(2700.0),
-            max: Some(3200.0),
-        };
+        let frequency = cpufetch_rs::cpu::info::Frequency::new(Some(2500.0), Some(3200.0), Some(2700.0));
 
         // Create default version - not as relevant for ARM
-        let version = Version {
-            family: 0,
-            model: 0,
-            stepping: 0,
-        };
+        let version = Version::new(0, 0, 0);
 
         // Create mock cache sizes [L1i, L1d, L2, L3]
         let cache_sizes = [Some(64), Some(64), Some(512), Some(4096)];
@@ -39,38 +31,25 @@ mod arm_tests {
         features.insert(ArmFeatures::ASIMD);
 
         // Create mock CPU info with ARM values
-        CpuInfo {
-            vendor: Vendor::ARM,
-            brand_string: "ARMv8 Processor @ 2.5GHz".to_string(),
-            version,
-            physical_cores: 4,
-            logical_cores: 4, // ARM often has same physical/logical core count
-            frequency,
-            cache_sizes,
-            features,
-            microarch: None,
-            hypervisor: None,
-            peak_flops: None,
-            p_cores: None,
-            e_cores: None,
-        }
+        let mut info = CpuInfo::default();
+        info.vendor = Vendor::ARM;
+        info.brand_string = "ARMv8 Processor @ 2.5GHz".to_string();
+        info.version = version;
+        info.physical_cores = 4;
+        info.logical_cores = 4; // ARM often has same physical/logical core count
+        info.frequency = frequency;
+        info.cache_sizes = cache_sizes;
+        info.features = features;
+        info
     }
 
     /// Create a mock Apple Silicon CPU for testing
     fn create_mock_apple_silicon() -> CpuInfo {
         // Create default frequency with some values
-        let frequency = cpufetch_rs::cpu::info::Frequency {
-            base: Some(3200.0),
-            current: Some(3200.0),
-            max: Some(3200.0),
-        };
+        let frequency = cpufetch_rs::cpu::info::Frequency::new(Some(3200.0), Some(3200.0), Some(3200.0));
 
         // Create default version - not as relevant for ARM
-        let version = Version {
-            family: 0,
-            model: 0,
-            stepping: 0,
-        };
+        let version = Version::new(0, 0, 0);
 
         // Create mock cache sizes [L1i, L1d, L2, L3]
         // Apple Silicon typically has larger caches
@@ -84,21 +63,16 @@ mod arm_tests {
         // Add more Apple-specific features as identified
 
         // Create mock CPU info with Apple values
-        CpuInfo {
-            vendor: Vendor::Apple,
-            brand_string: "Apple M1 Pro".to_string(),
-            version,
-            physical_cores: 8,
-            logical_cores: 8,
-            frequency,
-            cache_sizes,
-            features,
-            microarch: None,
-            hypervisor: None,
-            peak_flops: None,
-            p_cores: None,
-            e_cores: None,
-        }
+        let mut info = CpuInfo::default();
+        info.vendor = Vendor::Apple;
+        info.brand_string = "Apple M1 Pro".to_string();
+        info.version = version;
+        info.physical_cores = 8;
+        info.logical_cores = 8;
+        info.frequency = frequency;
+        info.cache_sizes = cache_sizes;
+        info.features = features;
+        info
     }
 
     #[cfg(feature = "cli")]
@@ -119,7 +93,7 @@ mod arm_tests {
         let args = create_mock_args();
 
         // Test ARM printing
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info failed for ARM: {:?}", result.err());
     }
 
@@ -131,7 +105,7 @@ mod arm_tests {
         let args = create_mock_args();
 
         // Test Apple Silicon printing
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(
             result.is_ok(),
             "print_cpu_info failed for Apple Silicon: {:?}",
@@ -146,14 +120,14 @@ mod arm_tests {
         let cpu_info = create_mock_arm_cpu();
 
         // Test ARM JSON output
-        let result = printer::print_json(&cpu_info);
+        let result = printer::print_json(&mut std::io::stdout(), &cpu_info);
         assert!(result.is_ok(), "print_json failed for ARM: {:?}", result.err());
 
         // Create mock Apple Silicon data
         let cpu_info = create_mock_apple_silicon();
 
         // Test Apple Silicon JSON output
-        let result = printer::print_json(&cpu_info);
+        let result = printer::print_json(&mut std::io::stdout(), &cpu_info);
         assert!(
             result.is_ok(),
             "print_json failed for Apple Silicon: {:?}",
@@ -14,6 +14,12 @@ fn test_cpu_info_new() {
     assert!(!info.brand_string.is_empty());
     assert!(info.physical_cores > 0);
     assert!(info.logical_cores > 0);
+    assert!(
+        info.logical_cores >= info.physical_cores,
+        "logical cores ({}) must not be fewer than physical cores ({})",
+        info.logical_cores,
+        info.physical_cores
+    );
 
     // Print CPU info for debugging
     println!("Detected CPU: {info:?}");
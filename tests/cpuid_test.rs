@@ -4,7 +4,7 @@
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 mod x86_tests {
-    use cpufetch_rs::cpu::CpuidWrapper;
+    use cpufetch_rs::cpu::{CacheType, CpuidRegisters, CpuidWrapper, X86Features};
 
     #[test]
     fn test_basic_info() {
@@ -55,6 +55,297 @@ mod x86_tests {
             "No caches detected, which is unexpected for modern CPUs"
         );
     }
+
+    /// Canned CPUID results for an AMD Ryzen 9 5950X (Zen 3, 16 cores / 32 threads)
+    fn ryzen_5950x(leaf: u32, subleaf: u32) -> CpuidRegisters {
+        let regs = |eax, ebx, ecx, edx| CpuidRegisters { eax, ebx, ecx, edx };
+        match (leaf, subleaf) {
+            // "AuthenticAMD", highest basic leaf 0x10
+            (0x0, _) => regs(0x10, 0x6874_7541, 0x444D_4163, 0x6974_6E65),
+            // Family 0x19, model 0x21, stepping 0
+            (0x1, _) => regs(0x00A2_0F10, 0x0020_0800, 0, 0),
+            // Extended topology: 2 threads per core (SMT level), 32 per package (core level)
+            (0xB, 0) => regs(1, 2, 0x100, 0),
+            (0xB, 1) => regs(5, 32, 0x201, 0),
+            (0x8000_0000, _) => regs(0x8000_0020, 0, 0, 0),
+            // Deterministic cache parameters: L1d, L1i, L2, L3
+            (0x8000_001D, 0) => regs(0x4121, 0x01C0_003F, 63, 0),
+            (0x8000_001D, 1) => regs(0x4122, 0x01C0_003F, 63, 0),
+            (0x8000_001D, 2) => regs(0x4143, 0x01C0_003F, 1023, 2),
+            (0x8000_001D, 3) => regs(0x3_C163, 0x03C0_003F, 32767, 1),
+            _ => CpuidRegisters::default(),
+        }
+    }
+
+    #[test]
+    fn test_cache_topology_mocked_ryzen_5950x() {
+        let cpuid = CpuidWrapper::with_reader(ryzen_5950x);
+        let topology = cpuid.get_cache_topology().expect("Failed to get cache topology");
+        let sizes: Vec<Option<u32>> = topology.caches.iter().map(|c| c.map(|c| c.size_kb)).collect();
+
+        assert_eq!(sizes, [Some(32), Some(32), Some(512), Some(32768)]);
+
+        let l3 = topology.caches[3].expect("L3 present");
+        assert_eq!(l3.level, 3);
+        assert_eq!(l3.cache_type, CacheType::Unified);
+        assert_eq!(l3.associativity, 16);
+        assert_eq!(l3.line_size, 64);
+        // Leaf 0x8000001D counts 16 logical processors on the CCD: 8 cores with SMT
+        assert_eq!(l3.shared_by, 8);
+
+        let l2 = topology.caches[2].expect("L2 present");
+        assert_eq!(l2.shared_by, 1);
+    }
+
+    #[test]
+    fn test_address_sizes_mocked() {
+        // Leaf 0x80000008 EAX: 48 physical bits (7:0), 48 linear bits (15:8)
+        let reader = |leaf: u32, _subleaf: u32| match leaf {
+            0x8000_0000 => CpuidRegisters {
+                eax: 0x8000_0008,
+                ..CpuidRegisters::default()
+            },
+            0x8000_0008 => CpuidRegisters {
+                eax: 0x3030,
+                ..CpuidRegisters::default()
+            },
+            _ => CpuidRegisters::default(),
+        };
+        let sizes = CpuidWrapper::with_reader(reader)
+            .get_address_sizes()
+            .expect("leaf 0x80000008 present");
+        assert_eq!((sizes.physical_bits, sizes.virtual_bits), (48, 48));
+
+        // Below leaf 0x80000008 there is nothing to report
+        assert!(CpuidWrapper::with_reader(ryzen_5950x).get_address_sizes().is_none());
+    }
+
+    #[test]
+    fn test_tsc_frequency_mocked() {
+        // `crystal_hz` in leaf 0x15 ECX: 0 makes the leaf 0x16 base frequency stand in
+        let reader = |crystal_hz: u32| {
+            move |leaf: u32, _subleaf: u32| {
+                let regs = |eax, ebx, ecx, edx| CpuidRegisters { eax, ebx, ecx, edx };
+                match leaf {
+                    0x0 => regs(0x16, 0x756E_6547, 0x6C65_746E, 0x4965_6E69),
+                    // TSC/crystal ratio 216/2
+                    0x15 => regs(2, 216, crystal_hz, 0),
+                    // Base 2600 MHz, max 3500 MHz, bus 100 MHz
+                    0x16 => regs(2600, 3500, 100, 0),
+                    0x8000_0000 => regs(0x8000_0008, 0, 0, 0),
+                    // Invariant TSC (EDX[8])
+                    0x8000_0007 => regs(0, 0, 0, 1 << 8),
+                    _ => CpuidRegisters::default(),
+                }
+            }
+        };
+
+        // Ice Lake and later enumerate the 24 MHz crystal: 24 * 216 / 2
+        let cpuid = CpuidWrapper::with_reader(reader(24_000_000));
+        assert_eq!(cpuid.get_tsc_frequency(), Some(2592.0));
+        assert!(cpuid.has_invariant_tsc());
+
+        // Skylake client leaves the crystal out
+        assert_eq!(CpuidWrapper::with_reader(reader(0)).get_tsc_frequency(), Some(2600.0));
+
+        // AMD has no leaf 0x15, and this mock no leaf 0x80000007
+        let ryzen = CpuidWrapper::with_reader(ryzen_5950x);
+        assert_eq!(ryzen.get_tsc_frequency(), None);
+        assert!(!ryzen.has_invariant_tsc());
+    }
+
+    #[test]
+    fn test_dump_leaves_native() {
+        let dump = CpuidWrapper::new().dump_leaves();
+        let leaf0 = dump.first().expect("leaf 0 is always dumped");
+        assert_eq!((leaf0.leaf, leaf0.subleaf), (0, 0));
+        assert!(leaf0.registers.eax > 0, "leaf 0 reports no maximum standard leaf");
+    }
+
+    #[test]
+    fn test_dump_leaves_mocked_subleaves() {
+        // Max leaf 7 with one extra subleaf, two leaf 4 caches, extended range to 0x80000001
+        let reader = |leaf: u32, subleaf: u32| match (leaf, subleaf) {
+            (0, _) => CpuidRegisters {
+                eax: 7,
+                ..CpuidRegisters::default()
+            },
+            (4, 0 | 1) => CpuidRegisters {
+                eax: 0x21 + subleaf,
+                ..CpuidRegisters::default()
+            },
+            (7, 0) => CpuidRegisters {
+                eax: 1,
+                ..CpuidRegisters::default()
+            },
+            (0x8000_0000, _) => CpuidRegisters {
+                eax: 0x8000_0001,
+                ..CpuidRegisters::default()
+            },
+            _ => CpuidRegisters::default(),
+        };
+        let dump = CpuidWrapper::with_reader(reader).dump_leaves();
+        let entries: Vec<(u32, u32)> = dump.iter().map(|entry| (entry.leaf, entry.subleaf)).collect();
+        assert_eq!(
+            entries,
+            [
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (4, 0),
+                (4, 1),
+                (5, 0),
+                (6, 0),
+                (7, 0),
+                (7, 1),
+                (0x8000_0000, 0),
+                (0x8000_0001, 0)
+            ]
+        );
+        assert_eq!(
+            dump[5].to_string(),
+            "0x00000004 0x01: eax=0x00000022 ebx=0x00000000 ecx=0x00000000 edx=0x00000000"
+        );
+    }
+
+    #[test]
+    fn test_cache_inclusivity_mocked_ryzen_5950x() {
+        let topology = CpuidWrapper::with_reader(ryzen_5950x)
+            .get_cache_topology()
+            .expect("Failed to get cache topology");
+        let l2 = topology.caches[2].expect("L2 present");
+        let l3 = topology.caches[3].expect("L3 present");
+
+        // Zen 3's L2 is inclusive of L1, while the L3 is a victim cache
+        assert_eq!(l2.inclusive, Some(true));
+        assert_eq!(l3.inclusive, Some(false));
+        assert!(topology.caches.iter().flatten().all(|cache| cache.self_initializing));
+    }
+
+    #[test]
+    fn test_cache_inclusivity_mocked_intel_leaf4() {
+        // Leaf 4 only, as on Intel: a 48 KB L1d and an inclusive 30 MB L3
+        let reader = |leaf: u32, subleaf: u32| {
+            let regs = |eax, ebx, ecx, edx| CpuidRegisters { eax, ebx, ecx, edx };
+            match (leaf, subleaf) {
+                (0x0, _) => regs(0x1F, 0x756E_6547, 0x6C65_746E, 0x4965_6E69),
+                (0x4, 0) => regs(0x121, 0x02C0_003F, 63, 0),
+                (0x4, 1) => regs(0x163, 0x02C0_003F, 40959, 0x2),
+                _ => CpuidRegisters::default(),
+            }
+        };
+        let topology = CpuidWrapper::with_reader(reader)
+            .get_cache_topology()
+            .expect("Failed to get cache topology");
+
+        let l1d = topology.caches[1].expect("L1d present");
+        assert_eq!(l1d.size_kb, 48);
+        assert_eq!(l1d.inclusive, Some(false));
+        let l3 = topology.caches[3].expect("L3 present");
+        assert_eq!(l3.size_kb, 30720);
+        assert_eq!(l3.inclusive, Some(true));
+        assert!(l3.self_initializing);
+    }
+
+    #[test]
+    fn test_cache_l4_mocked_crystal_well() {
+        // Core i7-4770R: 32 KB L1s, 256 KB L2, 6 MB L3 and a 128 MB eDRAM L4 as a fifth leaf 4 entry
+        let reader = |leaf: u32, subleaf: u32| {
+            let regs = |eax, ebx, ecx, edx| CpuidRegisters { eax, ebx, ecx, edx };
+            match (leaf, subleaf) {
+                (0x0, _) => regs(0xD, 0x756E_6547, 0x6C65_746E, 0x4965_6E69),
+                (0x4, 0) => regs(0x121, 0x01C0_003F, 63, 0),
+                (0x4, 1) => regs(0x122, 0x01C0_003F, 63, 0),
+                (0x4, 2) => regs(0x143, 0x01C0_003F, 511, 0),
+                (0x4, 3) => regs(0x163, 0x02C0_003F, 8191, 0x6),
+                (0x4, 4) => regs(0x183, 0x03C0_003F, 131_071, 0),
+                _ => CpuidRegisters::default(),
+            }
+        };
+        let topology = CpuidWrapper::with_reader(reader)
+            .get_cache_topology()
+            .expect("Failed to get cache topology");
+
+        assert_eq!(topology.caches[3].expect("L3 present").size_kb, 6144);
+        let l4 = topology.l4().expect("L4 present");
+        assert_eq!((l4.level, l4.cache_type, l4.size_kb), (4, CacheType::Unified, 131_072));
+        assert_eq!(l4.associativity, 16);
+        assert_eq!(topology.iter_present().count(), 5);
+        assert_eq!(topology.ordered().last().map(|cache| cache.level), Some(4));
+    }
+
+    #[test]
+    fn test_pmu_info_mocked_leaf_0xa() {
+        // Skylake with Hyper-Threading off: version 4, 8 x 48-bit general counters, 3 fixed
+        let skylake = |leaf: u32, _subleaf: u32| {
+            let regs = |eax, ebx, ecx, edx| CpuidRegisters { eax, ebx, ecx, edx };
+            match leaf {
+                0x0 => regs(0x16, 0x756E_6547, 0x6C65_746E, 0x4965_6E69),
+                0xA => regs(0x0730_0804, 0, 0, 0x0000_0603),
+                _ => CpuidRegisters::default(),
+            }
+        };
+        let pmu = CpuidWrapper::with_reader(skylake).get_pmu_info().expect("PMU present");
+        assert_eq!(
+            (pmu.version, pmu.general_counters, pmu.counter_bits, pmu.fixed_counters),
+            (4, 8, 48, 3)
+        );
+        assert_eq!(pmu.to_string(), "v4, 8 general + 3 fixed counters, 48-bit");
+
+        // A hypervisor that hides the PMU zeroes the leaf; Zen 3 lacks it entirely
+        let hidden = |leaf: u32, subleaf: u32| match leaf {
+            0xA => CpuidRegisters::default(),
+            _ => skylake(leaf, subleaf),
+        };
+        assert!(CpuidWrapper::with_reader(hidden).get_pmu_info().is_none());
+        assert!(CpuidWrapper::with_reader(ryzen_5950x).get_pmu_info().is_none());
+    }
+
+    #[test]
+    fn test_basic_info_mocked_ryzen_5950x() {
+        let info = CpuidWrapper::with_reader(ryzen_5950x)
+            .get_basic_info()
+            .expect("Failed to get basic info");
+
+        assert_eq!(info.vendor_string, "AuthenticAMD");
+        assert_eq!(info.family, 0x19);
+        assert_eq!(info.model, 0x21);
+    }
+
+    #[test]
+    fn test_raw_features_mocked_avx512_masked() {
+        // CPUID advertises AVX2 and AVX-512F but OSXSAVE (leaf 1 ECX[27]) is clear,
+        // as in a VM that masks extended register state
+        let reader = |leaf: u32, _subleaf: u32| {
+            let regs = |eax, ebx, ecx, edx| CpuidRegisters { eax, ebx, ecx, edx };
+            match leaf {
+                0x0 => regs(0x7, 0x756E_6547, 0x6C65_746E, 0x4965_6E69),
+                // SSE3, AVX (ECX[28]); SSE, SSE2 (EDX[25..26])
+                0x1 => regs(0x0005_0654, 0, (1 << 0) | (1 << 28), (1 << 25) | (1 << 26)),
+                // AVX2 (EBX[5]), AVX-512F (EBX[16])
+                0x7 => regs(0, (1 << 5) | (1 << 16), 0, 0),
+                _ => CpuidRegisters::default(),
+            }
+        };
+        let raw = CpuidWrapper::with_reader(reader).get_raw_features();
+        assert_eq!(
+            raw,
+            X86Features::SSE
+                | X86Features::SSE2
+                | X86Features::SSE3
+                | X86Features::AVX
+                | X86Features::AVX2
+                | X86Features::AVX512F
+        );
+
+        // The OS only enabled SSE state
+        let usable = X86Features::SSE | X86Features::SSE2 | X86Features::SSE3;
+        assert_eq!(
+            X86Features::disabled_by_os(raw, usable),
+            X86Features::AVX | X86Features::AVX2 | X86Features::AVX512F
+        );
+    }
 }
 
 #[test]
@@ -31,7 +31,7 @@ fn test_full_pipeline_no_panic() {
         };
 
         // Should not panic
-        print_cpu_info(&cpu_info, &args).expect("print_cpu_info should not fail");
+        print_cpu_info(&mut std::io::stdout(), &cpu_info, &args).expect("print_cpu_info should not fail");
     }
 }
 
@@ -42,6 +42,9 @@ fn test_cpu_info_get_singleton() {
     let b = CpuInfo::get();
     // Must be the same allocation (pointer equality)
     assert!(std::ptr::eq(a, b), "CpuInfo::get() must return the same instance");
+
+    let c = CpuInfo::try_get().expect("detection should succeed on a real machine");
+    assert!(std::ptr::eq(a, c), "try_get() must share get()'s instance");
 }
 
 // ── JSON round-trip ──────────────────────────────────────────────────────────
@@ -62,6 +65,39 @@ fn test_json_roundtrip() {
     assert_eq!(cpu_info.cache_sizes, restored.cache_sizes);
 }
 
+// ── Feature combinations ─────────────────────────────────────────────────────
+//
+// The serde derives are gated behind the `serde` feature, which `json`, `yaml`,
+// `toml`, `config` and `windows` imply. CI builds the library both ways:
+//
+//   cargo clippy --no-default-features --features cpu --all-targets        # no serde at all
+//   cargo clippy --no-default-features --features cpu,serde --all-targets  # derives only
+//   cargo clippy --no-default-features --features cli --all-targets        # CLI without output formats
+//   cargo test                                                             # full
+//
+// Without `std` the OS-reported fields (core counts, frequency) stay empty,
+// so only the full build runs every test.
+
+/// Detection needs no serialisation support.
+#[test]
+fn test_detect_without_output_features() {
+    let cpu_info = CpuInfo::new().expect("CpuInfo::new() should succeed");
+    assert!(!cpu_info.brand_string.is_empty());
+}
+
+/// With `serde` on, the detected types serialise and deserialise.
+#[test]
+#[cfg(feature = "serde")]
+fn test_serde_impls() {
+    fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+
+    assert_serde::<CpuInfo>();
+    assert_serde::<cpufetch_rs::Vendor>();
+    assert_serde::<cpufetch_rs::Frequency>();
+    assert_serde::<cpufetch_rs::cpu::CacheTopology>();
+    assert_serde::<cpufetch_rs::cpu::Mitigation>();
+}
+
 // ── CLI integration ──────────────────────────────────────────────────────────
 
 #[test]
@@ -97,6 +133,161 @@ fn test_cli_json_output_is_valid() {
         .stdout(contains("brand_string"));
 }
 
+#[test]
+#[cfg(feature = "cli")]
+fn test_cli_brief_is_one_line() {
+    use assert_cmd::Command;
+
+    let output = Command::cargo_bin("cpufetch")
+        .unwrap()
+        .args(["--brief", "--cache", "--features"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "unexpected --brief output: {stdout:?}");
+    assert!(stdout.ends_with('\n'));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn test_cli_count_is_one_integer() {
+    use assert_cmd::Command;
+
+    for args in [&["--count"][..], &["--count", "logical"], &["--count", "physical"]] {
+        let output = Command::cargo_bin("cpufetch").unwrap().args(args).output().unwrap();
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let number = stdout
+            .strip_suffix('\n')
+            .unwrap_or_else(|| panic!("no trailing newline: {stdout:?}"));
+        assert!(
+            number.parse::<u32>().is_ok_and(|count| count > 0),
+            "{args:?} printed {stdout:?}"
+        );
+    }
+}
+
+#[test]
+#[cfg(all(feature = "cli", target_arch = "x86_64"))]
+fn test_cli_require_present_feature_passes() {
+    use assert_cmd::Command;
+    use predicates::str::starts_with;
+
+    // SSE2 is part of the x86_64 baseline
+    Command::cargo_bin("cpufetch")
+        .unwrap()
+        .args(["--require", "sse2"])
+        .assert()
+        .code(0)
+        .stdout(starts_with("PASS: SSE2"));
+}
+
+#[test]
+#[cfg(all(feature = "cli", target_arch = "x86_64"))]
+fn test_cli_require_absent_feature_fails() {
+    use assert_cmd::Command;
+    use cpufetch_rs::cpu::X86Features;
+
+    // Requiring every known flag fails unless this CPU happens to have them all
+    let features = cpufetch_rs::cpu::detect_features().unwrap();
+    let every: Vec<&str> = X86Features::all().iter_names().map(|(name, _)| name).collect();
+    let output = Command::cargo_bin("cpufetch")
+        .unwrap()
+        .args(["--require", &every.join(",")])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    if features.is_all() {
+        assert_eq!(output.status.code(), Some(0));
+    } else {
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stdout.starts_with("FAIL: missing "), "unexpected output: {stdout}");
+    }
+}
+
+#[test]
+#[cfg(all(feature = "cli", any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn test_cli_require_unknown_feature_exits_2() {
+    use assert_cmd::Command;
+    use predicates::str::contains;
+
+    Command::cargo_bin("cpufetch")
+        .unwrap()
+        .args(["--require", "warp-drive"])
+        .assert()
+        .code(2)
+        .stderr(contains("Unknown feature name 'warp-drive'"));
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn test_cli_fields_prints_key_value_lines() {
+    use assert_cmd::Command;
+
+    let output = Command::cargo_bin("cpufetch")
+        .unwrap()
+        .args(["--fields", "vendor,cores,l3"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let keys: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(
+        keys,
+        ["vendor", "cores", "l3"],
+        "unexpected --fields output: {stdout:?}"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "cli", feature = "json", target_arch = "x86_64"))]
+fn test_cli_fields_as_json() {
+    use assert_cmd::Command;
+
+    let output = Command::cargo_bin("cpufetch")
+        .unwrap()
+        .args(["--fields", "threads,sse2", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let compact: String = stdout.split_whitespace().collect();
+    assert!(
+        compact.starts_with("{\"threads\":"),
+        "unexpected --fields JSON: {stdout}"
+    );
+    // SSE2 is part of the x86_64 baseline
+    assert!(
+        compact.ends_with(",\"sse2\":true}"),
+        "unexpected --fields JSON: {stdout}"
+    );
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn test_cli_fields_unknown_lists_valid_keys() {
+    use assert_cmd::Command;
+    use predicates::str::contains;
+
+    Command::cargo_bin("cpufetch")
+        .unwrap()
+        .args(["--fields", "vendor,warp_drive"])
+        .assert()
+        .failure()
+        .stderr(contains("Unknown field 'warp_drive'"))
+        .stderr(contains("valid fields: vendor, brand"));
+}
+
 // ── Architecture-specific ────────────────────────────────────────────────────
 
 /// On `x86_64`, after the frequency wire-up, at least one frequency field
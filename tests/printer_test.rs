@@ -22,18 +22,10 @@ mod printer_tests {
     /// Create a mock CPU info structure for testing
     fn create_mock_cpu_info() -> CpuInfo {
         // Create default frequency with some values
-        let frequency = cpufetch_rs::cpu::info::Frequency {
-            base: Some(2800.0),
-            current: Some(3200.0),
-            max: Some(4000.0),
-        };
+        let frequency = cpufetch_rs::cpu::info::Frequency::new(Some(2800.0), Some(4000.0), Some(3200.0));
 
         // Create default version
-        let version = Version {
-            family: 10,
-            model: 5,
-            stepping: 2,
-        };
+        let version = Version::new(10, 5, 2);
 
         // Create mock cache sizes [L1i, L1d, L2, L3]
         let cache_sizes = [Some(32), Some(32), Some(256), Some(8192)];
@@ -60,21 +52,16 @@ mod printer_tests {
         let features = ();
 
         // Create mock CPU info with test values
-        CpuInfo {
-            vendor: Vendor::Intel,
-            brand_string: "Mock Intel CPU @ 2.8GHz".to_string(),
-            version,
-            physical_cores: 4,
-            logical_cores: 8,
-            frequency,
-            cache_sizes,
-            features,
-            microarch: None,
-            hypervisor: None,
-            peak_flops: None,
-            p_cores: None,
-            e_cores: None,
-        }
+        let mut info = CpuInfo::default();
+        info.vendor = Vendor::Intel;
+        info.brand_string = "Mock Intel CPU @ 2.8GHz".to_string();
+        info.version = version;
+        info.physical_cores = 4;
+        info.logical_cores = 8;
+        info.frequency = frequency;
+        info.cache_sizes = cache_sizes;
+        info.features = features;
+        info
     }
 
     #[cfg(feature = "cli")]
@@ -95,7 +82,7 @@ mod printer_tests {
         let args = create_mock_args();
 
         // Test basic printing
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info failed: {:?}", result.err());
     }
 
@@ -108,7 +95,7 @@ mod printer_tests {
         args.no_logo = true;
 
         // Test without logo
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info with no_logo failed: {:?}", result.err());
     }
 
@@ -121,7 +108,7 @@ mod printer_tests {
         args.no_color = true;
 
         // Test without color
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(
             result.is_ok(),
             "print_cpu_info with no_color failed: {:?}",
@@ -140,7 +127,7 @@ mod printer_tests {
         args.frequency = true;
         args.cache = false;
         args.features = false;
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(
             result.is_ok(),
             "print_cpu_info with only frequency failed: {:?}",
@@ -151,7 +138,7 @@ mod printer_tests {
         args.frequency = false;
         args.cache = true;
         args.features = false;
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(
             result.is_ok(),
             "print_cpu_info with only cache failed: {:?}",
@@ -162,7 +149,7 @@ mod printer_tests {
         args.frequency = false;
         args.cache = false;
         args.features = true;
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(
             result.is_ok(),
             "print_cpu_info with only features failed: {:?}",
@@ -177,10 +164,86 @@ mod printer_tests {
         let cpu_info = create_mock_cpu_info();
 
         // Test JSON printing
-        let result = printer::print_json(&cpu_info);
+        let result = printer::print_json(&mut std::io::stdout(), &cpu_info);
         assert!(result.is_ok(), "print_json failed: {:?}", result.err());
     }
 
+    #[test]
+    #[cfg(all(feature = "display", feature = "cli"))]
+    fn test_print_cpu_info_to_buffer() {
+        let cpu_info = create_mock_cpu_info();
+        let args = Args {
+            no_logo: true,
+            no_color: true,
+            ..create_mock_args()
+        };
+
+        let mut buffer = Vec::new();
+        printer::print_cpu_info(&mut buffer, &cpu_info, &args).expect("writing to a buffer cannot fail");
+
+        let output = String::from_utf8(buffer).expect("output is UTF-8");
+        assert!(output.starts_with("CPU Information\n"));
+        assert!(output.contains("Mock Intel CPU @ 2.8GHz"));
+        assert!(output.contains("L3 Cache:"));
+        assert!(!output.contains('\x1b'), "--no-color output must be plain");
+    }
+
+    #[test]
+    #[cfg(all(feature = "display", feature = "cli"))]
+    fn test_print_cpu_info_process_node_matches_uarch() {
+        use cpufetch_rs::cpu::Microarch;
+
+        let mut cpu_info = create_mock_cpu_info();
+        cpu_info.microarch = Some(Microarch::SapphireRapids);
+        cpu_info.process_node = Microarch::SapphireRapids.process_node().map(str::to_string);
+        let args = Args {
+            no_logo: true,
+            no_color: true,
+            uarch: true,
+            ..Args::default()
+        };
+
+        let mut buffer = Vec::new();
+        printer::print_cpu_info(&mut buffer, &cpu_info, &args).expect("writing to a buffer cannot fail");
+
+        let output = String::from_utf8(buffer).expect("output is UTF-8");
+        assert!(output.contains("Sapphire Rapids (Intel 7)"), "{output}");
+        let technology = output.lines().find(|line| line.contains("Technology:")).expect(&output);
+        assert!(technology.ends_with("Intel 7"), "{output}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "display", feature = "cli"))]
+    fn test_print_cpu_info_every_theme() {
+        let cpu_info = create_mock_cpu_info();
+        for name in printer::Theme::NAMES {
+            let args = Args {
+                no_logo: true,
+                no_color: true,
+                theme: Some((*name).to_string()),
+                ..create_mock_args()
+            };
+
+            let mut buffer = Vec::new();
+            printer::print_cpu_info(&mut buffer, &cpu_info, &args).expect("writing to a buffer cannot fail");
+
+            let output = String::from_utf8(buffer).expect("output is UTF-8");
+            assert!(output.contains("Mock Intel CPU @ 2.8GHz"), "theme {name}");
+            assert!(!output.contains('\x1b'), "--no-color must override theme {name}");
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "display", feature = "cli", feature = "json"))]
+    fn test_print_json_to_buffer() {
+        let mut buffer = Vec::new();
+        printer::print_json(&mut buffer, &create_mock_cpu_info()).expect("writing to a buffer cannot fail");
+
+        let json: serde_json::Value = serde_json::from_slice(&buffer).expect("buffer holds valid JSON");
+        assert_eq!(json["brand_string"], "Mock Intel CPU @ 2.8GHz");
+        assert_eq!(json["physical_cores"], 4);
+    }
+
     #[test]
     #[cfg(all(feature = "display", feature = "cli", not(feature = "json")))]
     fn test_print_json_feature_disabled() {
@@ -189,7 +252,7 @@ mod printer_tests {
 
         // Test JSON printing when the feature is disabled
         // This should return an error
-        let result = printer::print_json(&cpu_info);
+        let result = printer::print_json(&mut std::io::stdout(), &cpu_info);
         assert!(
             result.is_err(),
             "print_json should have failed when json feature is disabled"
@@ -205,31 +268,31 @@ mod printer_tests {
         // Test Intel
         cpu_info.vendor = Vendor::Intel;
         cpu_info.brand_string = "Mock Intel CPU @ 3.6GHz".to_string();
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info failed for Intel: {:?}", result.err());
 
         // Test AMD
         cpu_info.vendor = Vendor::AMD;
         cpu_info.brand_string = "Mock AMD CPU @ 3.4GHz".to_string();
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info failed for AMD: {:?}", result.err());
 
         // Test ARM
         cpu_info.vendor = Vendor::ARM;
         cpu_info.brand_string = "Mock ARM CPU @ 2.0GHz".to_string();
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info failed for ARM: {:?}", result.err());
 
         // Test Apple
         cpu_info.vendor = Vendor::Apple;
         cpu_info.brand_string = "Mock Apple M1 CPU @ 3.2GHz".to_string();
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info failed for Apple: {:?}", result.err());
 
         // Test Unknown
         cpu_info.vendor = Vendor::Unknown;
         cpu_info.brand_string = "Mock Unknown CPU".to_string();
-        let result = printer::print_cpu_info(&cpu_info, &args);
+        let result = printer::print_cpu_info(&mut std::io::stdout(), &cpu_info, &args);
         assert!(result.is_ok(), "print_cpu_info failed for Unknown: {:?}", result.err());
     }
 }
@@ -268,6 +331,49 @@ mod cli_integration_tests {
         assert!(!stdout.contains("======="), "Output still contains logo elements");
     }
 
+    #[test]
+    fn test_theme_option() {
+        let output = run_command(&["--no-logo", "--theme", "solarized"]).expect("Failed to run --theme");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+        assert!(String::from_utf8_lossy(&output.stdout).contains("Vendor:"));
+
+        let output = run_command(&["--theme", "dracula"]).expect("Failed to run --theme");
+        assert!(!output.status.success(), "an unknown theme must be rejected");
+    }
+
+    #[test]
+    fn test_output_option_writes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.txt");
+        let output = run_command(&["--no-logo", "--output", path.to_str().unwrap()]).expect("Failed to run --output");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+        assert!(output.stdout.is_empty(), "--output must leave stdout empty");
+
+        let report = std::fs::read_to_string(&path).expect("report file written");
+        assert!(report.contains("Vendor:"), "report doesn't contain 'Vendor:'");
+        if std::env::var_os("CLICOLOR_FORCE").is_none() {
+            assert!(!report.contains('\x1b'), "file output should be uncoloured");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_compare_against_own_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        let saved = run_command(&["--json", "--output", path.to_str().unwrap()]).expect("Failed to save snapshot");
+        assert!(saved.status.success(), "Command failed with status: {}", saved.status);
+
+        let output =
+            run_command(&["--no-color", "--compare", path.to_str().unwrap()]).expect("Failed to run --compare");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+        // Clock estimates can drift between runs on machines without cpufreq, the rest cannot
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for field in ["Vendor:", "Model:", "Cores:", "Cache:", "Features"] {
+            assert!(!stdout.contains(field), "unexpected difference in {field}: {stdout}");
+        }
+    }
+
     #[test]
     fn test_no_color_option() {
         let output = run_command(&["--no-color"]).expect("Failed to run --no-color command");
@@ -278,6 +384,127 @@ mod cli_integration_tests {
         assert!(stdout.contains("Vendor:"), "Output doesn't contain 'Vendor:'");
     }
 
+    #[test]
+    fn test_uarch_option() {
+        let output = run_command(&["--uarch", "--no-logo", "--no-color"]).expect("Failed to run --uarch command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("Microarchitecture:"),
+            "Output doesn't contain 'Microarchitecture:'"
+        );
+        assert!(
+            stdout.contains("Family/Model:"),
+            "Output doesn't contain 'Family/Model:'"
+        );
+    }
+
+    #[test]
+    fn test_vm_option() {
+        let output = run_command(&["--vm", "--no-logo", "--no-color"]).expect("Failed to run --vm command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Virtualized:"), "Output doesn't contain 'Virtualized:'");
+    }
+
+    #[test]
+    fn test_logo_only_option() {
+        let output = run_command(&["--logo-only", "--no-color"]).expect("Failed to run --logo-only command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.trim().is_empty(), "--logo-only printed nothing");
+        assert!(
+            !stdout.contains("CPU Information"),
+            "--logo-only printed the info block"
+        );
+    }
+
+    #[test]
+    fn test_info_only_option() {
+        let output = run_command(&["--info-only", "--no-color"]).expect("Failed to run --info-only command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.starts_with("CPU Information"),
+            "--info-only should start with the info header"
+        );
+        assert!(!stdout.contains('\x1b'), "--info-only ignored --no-color");
+    }
+
+    #[test]
+    fn test_logo_only_conflicts_with_info_only() {
+        let output = run_command(&["--logo-only", "--info-only"]).expect("Failed to run command");
+        assert!(
+            !output.status.success(),
+            "--logo-only with --info-only should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_watch_rejects_zero_interval() {
+        let output = run_command(&["--watch", "0"]).expect("Failed to run --watch command");
+        assert!(!output.status.success(), "--watch 0 should be rejected");
+    }
+
+    #[test]
+    fn test_debug_does_not_alter_stdout() {
+        let plain = run_command(&["--no-logo", "--no-color"]).expect("Failed to run command");
+        let debug = run_command(&["--no-logo", "--no-color", "--debug"]).expect("Failed to run --debug command");
+        assert!(debug.status.success(), "Command failed with status: {}", debug.status);
+
+        // Log records go to stderr; stdout keeps the same shape (values such as
+        // the estimated frequency may legitimately differ between runs)
+        let plain_stdout = String::from_utf8_lossy(&plain.stdout);
+        let debug_stdout = String::from_utf8_lossy(&debug.stdout);
+        assert_eq!(plain_stdout.lines().count(), debug_stdout.lines().count());
+        assert!(!debug_stdout.contains("DEBUG"), "log records leaked into stdout");
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_verbose_keeps_stdout_clean() {
+        let output = run_command(&["--verbose", "--json"]).expect("Failed to run --verbose command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+
+        // Warnings go to stderr, so stdout must still be valid JSON
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.trim_start().starts_with('{'),
+            "stdout polluted by --verbose output"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "frequency")]
+    fn test_per_core_option() {
+        let output = run_command(&["--per-core", "--no-logo"]).expect("Failed to run --per-core command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_format_yaml_option() {
+        let output = run_command(&["--format", "yaml"]).expect("Failed to run --format yaml command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("vendor:"), "Output doesn't contain vendor YAML field");
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_format_toml_option() {
+        let output = run_command(&["--format", "toml"]).expect("Failed to run --format toml command");
+        assert!(output.status.success(), "Command failed with status: {}", output.status);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("vendor = "), "Output doesn't contain vendor TOML field");
+    }
+
     #[test]
     #[cfg(feature = "json")]
     fn test_json_option() {
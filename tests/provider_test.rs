@@ -0,0 +1,97 @@
+//! Tests for driving the CLI through a mock `CpuInfoProvider`
+//!
+//! These run `cpufetch_rs::run` against fixed CPU data, so the checks do not
+//! depend on the machine the tests happen to run on.
+
+#[cfg(feature = "cli")]
+mod run_tests {
+    use cpufetch_rs::cli::Args;
+    use cpufetch_rs::{CpuInfo, CpuInfoProvider, Error, Vendor};
+
+    /// Provider whose detection always fails
+    struct FailingProvider;
+
+    impl CpuInfoProvider for FailingProvider {
+        fn cpu_info(&self) -> Result<CpuInfo, Error> {
+            Err(Error::CpuDetection("no CPU in this test".to_string()))
+        }
+    }
+
+    fn mock_cpu() -> CpuInfo {
+        let mut cpu_info = CpuInfo::default();
+        cpu_info.vendor = Vendor::AMD;
+        cpu_info.brand_string = "AMD Ryzen 9 5950X 16-Core Processor".to_string();
+        cpu_info.physical_cores = 16;
+        cpu_info.logical_cores = 32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            use cpufetch_rs::cpu::X86Features;
+            cpu_info.features = X86Features::SSE2 | X86Features::AVX2;
+        }
+        cpu_info
+    }
+
+    #[test]
+    fn test_run_reports_the_provided_cpu() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.txt");
+        let args = Args {
+            brief: true,
+            output: Some(path.clone()),
+            ..Args::default()
+        };
+
+        let code = cpufetch_rs::run(&mock_cpu(), &args).expect("run succeeds");
+        let report = std::fs::read_to_string(&path).expect("report file written");
+
+        assert_eq!(code, 0);
+        assert_eq!(report, format!("{}\n", mock_cpu().summary()));
+        assert!(report.contains("Ryzen 9 5950X"), "report: {report}");
+    }
+
+    #[test]
+    fn test_run_count() {
+        use cpufetch_rs::cli::CoreCount;
+
+        for (kind, expected) in [(CoreCount::Logical, "32\n"), (CoreCount::Physical, "16\n")] {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("count.txt");
+            let args = Args {
+                count: Some(kind),
+                output: Some(path.clone()),
+                ..Args::default()
+            };
+
+            assert_eq!(cpufetch_rs::run(&mock_cpu(), &args).unwrap(), 0);
+            let report = std::fs::read_to_string(&path).expect("report file written");
+            assert_eq!(report, expected);
+        }
+    }
+
+    #[test]
+    fn test_run_propagates_provider_error() {
+        let err = cpufetch_rs::run(&FailingProvider, &Args::default()).unwrap_err();
+        assert!(err.to_string().contains("no CPU in this test"), "error: {err}");
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn test_run_require_exit_codes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("verdict.txt");
+        let require = |list: &str| Args {
+            require: Some(list.to_string()),
+            output: Some(path.clone()),
+            ..Args::default()
+        };
+        let verdict = || std::fs::read_to_string(&path).expect("verdict file written");
+
+        assert_eq!(cpufetch_rs::run(&mock_cpu(), &require("sse2,avx2")).unwrap(), 0);
+        assert_eq!(verdict(), "PASS: SSE2, AVX2\n");
+        assert_eq!(cpufetch_rs::run(&mock_cpu(), &require("avx2,avx512f")).unwrap(), 1);
+        assert_eq!(verdict(), "FAIL: missing AVX512F\n");
+        // A usage error goes to stderr, not into the report
+        assert_eq!(cpufetch_rs::run(&mock_cpu(), &require("warp-drive")).unwrap(), 2);
+        assert_eq!(verdict(), "");
+    }
+}